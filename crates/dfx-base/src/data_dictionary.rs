@@ -4,10 +4,16 @@ use crate::field_map::FieldMapError;
 use crate::field_map::Group;
 use crate::field_map::Tag;
 use crate::fields;
+use crate::fields::converters::{
+    Decimal, LocalMktDate, MonthYear, MultipleCharValue, MultipleStringValue, SeqNum, TZTimeOnly,
+    TZTimestamp,
+};
 use crate::fields::types::FieldType;
 use crate::fields::ConversionError;
+use crate::fix_values::BusinessRejectReason;
 use crate::fix_values::SessionRejectReason;
 use crate::message::Message;
+use crate::orchestra;
 use crate::tags;
 use chrono::NaiveDate;
 use chrono::NaiveDateTime;
@@ -16,21 +22,31 @@ use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use xmltree::ParseError;
 
+use std::fmt::Display;
 use std::fs::File;
 use std::num::ParseIntError;
 use std::ops::Deref;
 use std::ops::DerefMut;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Clone, Debug)]
 pub enum MessageValidationError {
-    UnsupportedVersion { expected: String, actual: String },
+    UnsupportedVersion {
+        expected: String,
+        actual: String,
+    },
     TagException(TagException),
     FieldMapError(FieldMapError),
     // MissingGroupDefinition(),
     //DictionaryParseException(Arc<str>),
     ConversionError(ConversionError),
     //InvalidStructure(u32),
+    /// A [`ValidationRule`](crate::validation_rules::ValidationRule) rejected the message with
+    /// a BusinessMessageReject, `tag` is the field the rule checks.
+    BusinessReject {
+        reason: BusinessRejectReason,
+        tag: Tag,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -161,6 +177,12 @@ impl TagException {
         Self { field: counter_tag, session_reject_reason: SessionRejectReason::OTHER(format!("Group {counter_tag} contains a repeat occurrence of tag {trouble_tag} in a single group, which is illegal.")), inner: None, msg_type: None }
     }
 
+    #[must_use]
+    pub(crate) fn with_inner(mut self, inner: String) -> TagException {
+        self.inner = Some(inner);
+        self
+    }
+
     #[must_use]
     pub fn msg_type(&self) -> Option<&String> {
         self.msg_type.as_ref()
@@ -194,19 +216,200 @@ impl From<ConversionError> for MessageValidationError {
     }
 }
 
+/// A validation error found by [`DataDictionary::validate_all`] and the repeating group entries
+/// containing the field, e.g. `453[2]/447: Value is incorrect (out of range) for this tag` for
+/// PartyIDSource in the second NoPartyIDs entry.
+#[derive(Clone, Debug)]
+pub struct Violation {
+    path: Vec<(Tag, usize)>,
+    error: MessageValidationError,
+    advisory: bool,
+}
+
+impl Violation {
+    /// The counter tag and 1-based entry of each group containing the field, outermost first.
+    /// Empty for the header, body and trailer.
+    #[must_use]
+    pub fn path(&self) -> &[(Tag, usize)] {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn error(&self) -> &MessageValidationError {
+        &self.error
+    }
+
+    /// A required field missing from a repeating group entry, displayed with an `(advisory)`
+    /// suffix. [`DataDictionary::validate`] does not check these, so a message whose violations
+    /// are all advisory is accepted with `UseDataDictionary=Y`.
+    #[must_use]
+    pub fn is_advisory(&self) -> bool {
+        self.advisory
+    }
+
+    /// The field the error is about, if any.
+    #[must_use]
+    pub fn tag(&self) -> Option<Tag> {
+        match &self.error {
+            MessageValidationError::TagException(e) => Some(e.field()),
+            MessageValidationError::BusinessReject { tag, .. } => Some(*tag),
+            _ => None,
+        }
+    }
+}
+
+impl From<MessageValidationError> for Violation {
+    fn from(error: MessageValidationError) -> Self {
+        Violation {
+            path: Vec::new(),
+            error,
+            advisory: false,
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (tag, index) in &self.path {
+            write!(f, "{tag}[{index}]/")?;
+        }
+        match &self.error {
+            MessageValidationError::TagException(e) => {
+                write!(f, "{}: {}", e.field(), e.session_reject_reason().reason())?;
+                match e.inner() {
+                    Some(inner) => write!(f, " ({inner})"),
+                    None => Ok(()),
+                }
+            }
+            MessageValidationError::BusinessReject { reason, tag } => {
+                write!(f, "{tag}: {}", reason.reason())
+            }
+            MessageValidationError::UnsupportedVersion { expected, actual } => {
+                write!(f, "unsupported version '{actual}', expected '{expected}'")
+            }
+            e => write!(f, "{e:?}"),
+        }?;
+        if self.advisory {
+            write!(f, " (advisory)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the errors found by [`DataDictionary::validate`], the first one is returned unless
+/// `collect` is set. `path` is the group entry being validated.
+#[derive(Default)]
+struct Violations {
+    collect: bool,
+    path: Vec<(Tag, usize)>,
+    errors: Vec<Violation>,
+}
+
+impl Violations {
+    fn check(
+        &mut self,
+        result: Result<(), MessageValidationError>,
+    ) -> Result<(), MessageValidationError> {
+        match result {
+            Err(error) if self.collect => {
+                self.push(error);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    fn push(&mut self, error: MessageValidationError) {
+        self.errors.push(Violation {
+            path: self.path.clone(),
+            error,
+            advisory: false,
+        });
+    }
+
+    fn push_advisory(&mut self, error: MessageValidationError) {
+        self.errors.push(Violation {
+            path: self.path.clone(),
+            error,
+            advisory: true,
+        });
+    }
+}
+
 #[derive(Debug)]
-/// TODO
+/// An error loading a data dictionary. `path` is the element the error is located at, e.g.
+/// `message[NewOrderSingle]/component[Parties]/group[NoPartyIDs]`.
 pub enum DataDictionaryError {
     IoError(std::io::Error),
     ParseError(ParseError),
+    /// The attribute, field or component `name` referenced at `path` is not defined.
     Missing {
+        path: Arc<str>,
+        entry_type: Arc<str>,
+        name: Arc<str>,
+    },
+    /// The field or component `name` is defined more than once.
+    Duplicate {
+        path: Arc<str>,
         entry_type: Arc<str>,
         name: Arc<str>,
     },
+    Malformed {
+        path: Arc<str>,
+        reason: Arc<str>,
+    },
     InvalidVersionType {
         version_type: Arc<str>,
     },
     ParseIntError(ParseIntError),
+    /// An error loading the file `file`.
+    File {
+        file: PathBuf,
+        error: Box<DataDictionaryError>,
+    },
+}
+
+impl Display for DataDictionaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataDictionaryError::IoError(e) => write!(f, "{e}"),
+            DataDictionaryError::ParseError(e) => write!(f, "{e}"),
+            DataDictionaryError::Missing {
+                path,
+                entry_type,
+                name,
+            } if entry_type.as_ref() == "attribute" => {
+                write!(f, "{path}: missing attribute '{name}'")
+            }
+            DataDictionaryError::Missing {
+                path,
+                entry_type,
+                name,
+            } => write!(f, "{path}: {entry_type} '{name}' is not defined"),
+            DataDictionaryError::Duplicate {
+                path,
+                entry_type,
+                name,
+            } => write!(f, "{path}: {entry_type} '{name}' is defined more than once"),
+            DataDictionaryError::Malformed { path, reason } => write!(f, "{path}: {reason}"),
+            DataDictionaryError::InvalidVersionType { version_type } => {
+                write!(f, "invalid version type '{version_type}'")
+            }
+            DataDictionaryError::ParseIntError(e) => write!(f, "{e}"),
+            DataDictionaryError::File { file, error } => write!(f, "{}: {error}", file.display()),
+        }
+    }
+}
+
+impl std::error::Error for DataDictionaryError {}
+
+impl DataDictionaryError {
+    fn in_file(self, file: &Path) -> Self {
+        DataDictionaryError::File {
+            file: file.to_path_buf(),
+            error: Box::new(self),
+        }
+    }
 }
 
 impl From<std::io::Error> for DataDictionaryError {
@@ -265,15 +468,8 @@ impl Default for DataDictionary {
 impl DataDictionary {
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<DataDictionary, DataDictionaryError> {
         let path: &Path = path.as_ref();
-        let mut reader = match File::open(path) {
-            Err(why) => panic!("couldn't open {}: {}", path.display(), why),
-            Ok(file) => file,
-        };
-
-        let mut contents = String::new();
-        reader.read_to_string(&mut contents)?;
-        let dd = DataDictionary::load_from_string(&contents)?;
-        Ok(dd)
+        let contents = read_file(path)?;
+        DataDictionary::load_from_string(&contents).map_err(|e| e.in_file(path))
     }
 
     #[must_use]
@@ -306,6 +502,54 @@ impl DataDictionary {
         app_data_dictionary: &DataDictionary,
         begin_string: &str,
         msg_type: &str,
+    ) -> Result<(), MessageValidationError> {
+        let mut violations = Violations::default();
+        DataDictionary::validate_into(
+            message,
+            session_data_dictionary,
+            app_data_dictionary,
+            begin_string,
+            msg_type,
+            &mut violations,
+        )
+    }
+
+    /// Like [`DataDictionary::validate`] but returns every violation instead of the first, at
+    /// most one per field, with the path of the group entry it is in. Validation stops early only
+    /// when the version or message type is unknown. Missing required fields in group entries are
+    /// reported too, as [advisory](Violation::is_advisory) violations `validate` does not check.
+    #[must_use]
+    pub fn validate_all(
+        message: &Message,
+        session_data_dictionary: Option<&DataDictionary>,
+        app_data_dictionary: &DataDictionary,
+        begin_string: &str,
+        msg_type: &str,
+    ) -> Vec<Violation> {
+        let mut violations = Violations {
+            collect: true,
+            ..Violations::default()
+        };
+        if let Err(e) = DataDictionary::validate_into(
+            message,
+            session_data_dictionary,
+            app_data_dictionary,
+            begin_string,
+            msg_type,
+            &mut violations,
+        ) {
+            violations.push(e);
+        }
+        violations.errors
+    }
+
+    fn validate_into(
+        message: &Message,
+        session_data_dictionary: Option<&DataDictionary>,
+        app_data_dictionary: &DataDictionary,
+        begin_string: &str,
+        msg_type: &str,
+        violations: &mut Violations,
     ) -> Result<(), MessageValidationError> {
         if let Some(dictionary) = session_data_dictionary {
             if let Some(version) = dictionary.version() {
@@ -322,20 +566,20 @@ impl DataDictionary {
             session_data_dictionary.is_some_and(DataDictionary::check_fields_out_of_order);
         let check_order_app = app_data_dictionary.check_fields_out_of_order();
         if check_order_session || check_order_app {
-            message.has_valid_structure()?;
+            violations.check(message.has_valid_structure())?;
         }
 
         if app_data_dictionary.version().is_some() {
             app_data_dictionary.check_msg_type(msg_type)?;
-            app_data_dictionary.check_has_required(message, msg_type)?;
+            app_data_dictionary.check_has_required(message, msg_type, violations)?;
         }
 
         if let Some(dictionary) = session_data_dictionary {
-            dictionary.iterate(message.header(), msg_type)?;
-            dictionary.iterate(message.trailer(), msg_type)?;
+            dictionary.iterate(message.header(), msg_type, violations)?;
+            dictionary.iterate(message.trailer(), msg_type, violations)?;
         }
 
-        app_data_dictionary.iterate(message, msg_type)?;
+        app_data_dictionary.iterate(message, msg_type, violations)?;
         Ok(())
     }
 
@@ -353,28 +597,29 @@ impl DataDictionary {
         &self,
         message: &Message,
         msg_type: &str,
+        violations: &mut Violations,
     ) -> Result<(), MessageValidationError> {
         for field in self.header.required_fields() {
             if !message.header().is_field_set(*field) {
-                return Err(MessageValidationError::TagException(
+                violations.check(Err(MessageValidationError::TagException(
                     TagException::required_tag_missing(*field),
-                ));
+                )))?;
             }
         }
 
         for field in self.trailer.required_fields() {
             if !message.trailer().is_field_set(*field) {
-                return Err(MessageValidationError::TagException(
+                violations.check(Err(MessageValidationError::TagException(
                     TagException::required_tag_missing(*field),
-                ));
+                )))?;
             }
         }
 
         for field in self.messages[msg_type].required_fields() {
             if !message.is_field_set(*field) {
-                return Err(MessageValidationError::TagException(
+                violations.check(Err(MessageValidationError::TagException(
                     TagException::required_tag_missing(*field),
-                ));
+                )))?;
             }
         }
         Ok(())
@@ -418,10 +663,17 @@ impl DataDictionary {
                     FieldType::Char => field.as_value::<char>().err(),
                     FieldType::DateOnly => field.as_value::<NaiveDate>().err(),
                     FieldType::DateTime => field.as_value::<NaiveDateTime>().err(),
-                    FieldType::Decimal => field.as_value::<f32>().err(),
-                    FieldType::Int => field.as_value::<i32>().err(),
+                    FieldType::Decimal => field.as_value::<Decimal>().err(),
+                    FieldType::Int => field.as_value::<i64>().err(),
+                    FieldType::LocalMktDate => field.as_value::<LocalMktDate>().err(),
+                    FieldType::MonthYear => field.as_value::<MonthYear>().err(),
+                    FieldType::MultipleCharValue => field.as_value::<MultipleCharValue>().err(),
+                    FieldType::MultipleStringValue => field.as_value::<MultipleStringValue>().err(),
+                    FieldType::SeqNum => field.as_value::<SeqNum>().err(),
                     FieldType::String => unreachable!(),
                     FieldType::TimeOnly => field.as_value::<NaiveTime>().err(),
+                    FieldType::TZTimeOnly => field.as_value::<TZTimeOnly>().err(),
+                    FieldType::TZTimestamp => field.as_value::<TZTimestamp>().err(),
                 },
                 Err(msg) => todo!("{msg}"),
             };
@@ -531,37 +783,60 @@ impl DataDictionary {
         true
     }
 
-    fn iterate(&self, message: &FieldMap, msg_type: &str) -> Result<(), MessageValidationError> {
-        DataDictionary::check_has_no_repeated_tags(message)?;
-
-        // check non-group fields
-        let mut last_field = 0;
-        for (_k, v) in message.entries() {
-            let field = v;
-            if last_field != 0 && field.tag() == last_field {
-                return Err(MessageValidationError::TagException(
-                    TagException::repeated_tag(field.tag()),
-                ));
-            }
-            self.check_has_value(field)?;
+    /// Checks the value, format and definition of `field` in `map`, the message body or a group
+    /// with `group_definition`.
+    fn check_field(
+        &self,
+        field: &FieldBase,
+        map: &FieldMap,
+        group_definition: Option<&DDGroup>,
+        msg_type: &str,
+    ) -> Result<(), MessageValidationError> {
+        self.check_has_value(field)?;
 
-            if self.version.is_some()
-                && !matches!(&self.version, Some(version) if version.is_empty())
-            {
-                self.check_valid_format(field)?;
+        if self.version.is_some() && !matches!(&self.version, Some(version) if version.is_empty()) {
+            self.check_valid_format(field)?;
 
-                if self.should_check_tag(field) {
-                    self.check_valid_tag_number(field.tag())?;
+            if self.should_check_tag(field) {
+                self.check_valid_tag_number(field.tag())?;
 
-                    self.check_value(field)?;
-                    if !Message::is_header_field(field.tag(), Some(self))
-                        && !Message::is_trailer_field(field.tag(), Some(self))
+                self.check_value(field)?;
+                match group_definition {
+                    Some(group_definition) => {
+                        self.check_is_in_group(field, group_definition, msg_type)?;
+                        self.check_group_count(field, map, msg_type)?;
+                    }
+                    None if !Message::is_header_field(field.tag(), Some(self))
+                        && !Message::is_trailer_field(field.tag(), Some(self)) =>
                     {
                         self.check_is_in_message(field, msg_type)?;
-                        self.check_group_count(field, message, msg_type)?;
+                        self.check_group_count(field, map, msg_type)?;
                     }
+                    None => {}
                 }
             }
+        }
+        Ok(())
+    }
+
+    fn iterate(
+        &self,
+        message: &FieldMap,
+        msg_type: &str,
+        violations: &mut Violations,
+    ) -> Result<(), MessageValidationError> {
+        violations.check(DataDictionary::check_has_no_repeated_tags(message))?;
+
+        // check non-group fields
+        let mut last_field = 0;
+        for (_k, v) in message.entries() {
+            let field = v;
+            if last_field != 0 && field.tag() == last_field {
+                violations.check(Err(MessageValidationError::TagException(
+                    TagException::repeated_tag(field.tag()),
+                )))?;
+            }
+            violations.check(self.check_field(field, message, None, msg_type))?;
 
             last_field = field.tag();
         }
@@ -571,7 +846,9 @@ impl DataDictionary {
             for i in 1..=message.group_count(*tag)? {
                 let g = message.get_group(i as u32, *tag)?;
                 let ddg = self.messages[msg_type].get_group(*tag);
-                self.iterate_group(g, ddg, msg_type)?;
+                violations.path.push((*tag, i));
+                self.iterate_group(g, ddg, msg_type, violations)?;
+                violations.path.pop();
             }
         }
 
@@ -583,36 +860,40 @@ impl DataDictionary {
         group: &Group,
         group_definition: Option<&ArcGroup>,
         msg_type: &str,
+        violations: &mut Violations,
     ) -> Result<(), MessageValidationError> {
         match group_definition {
             Some(group_definition) => {
-                DataDictionary::check_has_no_repeated_tags(group)?;
+                violations.check(DataDictionary::check_has_no_repeated_tags(group))?;
 
                 let mut last_field = 0;
                 for (_, v) in group.entries() {
                     let field = v;
 
                     if last_field != 0 && field.tag() == last_field {
-                        return Err(MessageValidationError::TagException(
+                        violations.check(Err(MessageValidationError::TagException(
                             TagException::repeated_tag(last_field),
-                        ));
+                        )))?;
                     }
-                    self.check_has_value(field)?;
-
-                    if self.version.is_some()
-                        && !matches!(&self.version, Some(version) if version.is_empty())
-                    {
-                        self.check_valid_format(field)?;
-
-                        if self.should_check_tag(field) {
-                            self.check_valid_tag_number(field.tag())?;
+                    violations.check(self.check_field(
+                        field,
+                        group,
+                        Some(group_definition),
+                        msg_type,
+                    ))?;
+                    last_field = field.tag();
+                }
 
-                            self.check_value(field)?;
-                            self.check_is_in_group(field, group_definition, msg_type)?;
-                            self.check_group_count(field, group, msg_type)?;
+                // only validate_all reports missing required group fields, as advisory
+                // violations, validate does not check them
+                if violations.collect {
+                    for field in group_definition.required_fields() {
+                        if !group.is_field_set(*field) {
+                            violations.push_advisory(MessageValidationError::TagException(
+                                TagException::required_tag_missing(*field),
+                            ));
                         }
                     }
-                    last_field = field.tag();
                 }
 
                 // check contents of each nested group
@@ -620,7 +901,9 @@ impl DataDictionary {
                     for i in 1..=group.group_count(*tag)? {
                         let g = group.get_group(i as u32, *tag)?;
                         let ddg = group_definition.get_group(*tag);
-                        self.iterate_group(g, ddg, msg_type)?;
+                        violations.path.push((*tag, i));
+                        self.iterate_group(g, ddg, msg_type, violations)?;
+                        violations.path.pop();
                     }
                 }
 
@@ -640,6 +923,11 @@ impl DataDictionary {
         self.fields_by_name.get(field_name)
     }
 
+    #[must_use]
+    pub fn get_field_by_tag(&self, tag: Tag) -> Option<&Field> {
+        self.fields_by_tag.get(&tag)
+    }
+
     pub(crate) fn is_length_field(&self, tag: Tag) -> bool {
         // match self.fields_by_tag.get(&tag) {
         //     Some(field) => field.field_type().as_ref() == "LENGTH" && field.name().as_ref() != "BodyLength",
@@ -962,9 +1250,10 @@ impl DerefMut for GoM<'_> {
     }
 }
 
+use std::fs;
 use std::io::Read;
 use std::sync::Arc;
-use xmltree::Element;
+use xmltree::{Element, XMLNode};
 
 impl DataDictionary {
     #[must_use]
@@ -993,14 +1282,84 @@ impl DataDictionary {
     }
 
     pub fn load_from_string(contents: &str) -> Result<Self, DataDictionaryError> {
-        let root_doc = Element::parse(contents.as_bytes())?;
+        Self::load_from_element(&parse_document(contents)?)
+    }
+
+    /// Loads `path` with the overlays in `overlays` merged in order, see
+    /// [`DataDictionary::load_with_overlays`].
+    pub fn from_file_with_overlays<P: AsRef<Path>, Q: AsRef<Path>>(
+        path: P,
+        overlays: &[Q],
+    ) -> Result<Self, DataDictionaryError> {
+        let path = path.as_ref();
+        let mut root_doc = parse_document(&read_file(path)?).map_err(|e| e.in_file(path))?;
+        for overlay in overlays {
+            let overlay = overlay.as_ref();
+            let overlay_doc =
+                parse_document(&read_file(overlay)?).map_err(|e| e.in_file(overlay))?;
+            merge_overlay(&mut root_doc, &overlay_doc);
+        }
+        // The error path locates the element in the merged dictionary
+        Self::load_from_element(&root_doc).map_err(|e| {
+            if overlays.is_empty() {
+                e.in_file(path)
+            } else {
+                e
+            }
+        })
+    }
+
+    /// Loads a base dictionary with overlays merged in order, for the custom fields and messages
+    /// of a venue. An overlay has the layout of a QuickFIX dictionary, every section is optional:
+    ///
+    /// - A field of `<fields>` is added, or adds its enum values to the field with the same
+    ///   number and replaces its type.
+    /// - A message (by msgtype) of `<messages>`, a component (by name) of `<components>`, the
+    ///   `<header>` and the `<trailer>` are added, or add their fields, groups and components to
+    ///   the existing one. The `required` attribute of an existing entry marks it required or
+    ///   optional.
+    ///
+    /// ```
+    /// use dfx_base::data_dictionary::DataDictionary;
+    ///
+    /// let overlay = r#"<fix>
+    ///   <messages>
+    ///     <message name="NewOrderSingle" msgtype="D" msgcat="app">
+    ///       <field name="Price" required="Y" />
+    ///       <field name="VenueOrderFlag" required="N" />
+    ///     </message>
+    ///   </messages>
+    ///   <fields>
+    ///     <field number="20001" name="VenueOrderFlag" type="CHAR" />
+    ///   </fields>
+    /// </fix>"#;
+    /// let dd = DataDictionary::load_with_overlays(
+    ///     include_str!("../../../spec/FIX44.xml"),
+    ///     &[overlay],
+    /// )
+    /// .unwrap();
+    /// let order = dd.get_map_for_message("D").unwrap();
+    /// assert!(order.is_field(20001));
+    /// assert!(order.required_fields().contains(&44));
+    /// ```
+    pub fn load_with_overlays(
+        contents: &str,
+        overlays: &[&str],
+    ) -> Result<Self, DataDictionaryError> {
+        let mut root_doc = parse_document(contents)?;
+        for overlay in overlays {
+            merge_overlay(&mut root_doc, &parse_document(overlay)?);
+        }
+        Self::load_from_element(&root_doc)
+    }
 
-        let (_major_version, _minor_version, version) = get_version_info(&root_doc)?;
-        let (fields_by_tag, fields_by_name) = parse_fields(&root_doc)?;
-        let components_by_name = cache_components(&root_doc)?;
-        let messages = parse_messages(&root_doc, &fields_by_name, &components_by_name)?;
-        let header = parse_header(&root_doc, &fields_by_name, &components_by_name)?;
-        let trailer = parse_trailer(&root_doc, &fields_by_name, &components_by_name)?;
+    fn load_from_element(root_doc: &Element) -> Result<Self, DataDictionaryError> {
+        let (_major_version, _minor_version, version) = get_version_info(root_doc)?;
+        let (fields_by_tag, fields_by_name) = parse_fields(root_doc)?;
+        let components_by_name = cache_components(root_doc)?;
+        let messages = parse_messages(root_doc, &fields_by_name, &components_by_name)?;
+        let header = parse_header(root_doc, &fields_by_name, &components_by_name)?;
+        let trailer = parse_trailer(root_doc, &fields_by_name, &components_by_name)?;
 
         let length_fields = fields_by_tag
             .iter()
@@ -1029,23 +1388,318 @@ impl DataDictionary {
     }
 }
 
+/// Checks a dictionary for every dangling field or component reference, duplicate field tag or
+/// name, duplicate component and component including itself, where loading stops at the first
+/// error. A valid dictionary returns no errors.
+///
+/// ```
+/// use dfx_base::data_dictionary::validate_dictionary;
+///
+/// let errors = validate_dictionary(
+///     r#"<fix major="4" minor="4">
+///       <messages>
+///         <message name="Heartbeat" msgtype="0" msgcat="admin">
+///           <field name="TestReqID" required="N" />
+///         </message>
+///       </messages>
+///       <fields>
+///         <field number="112" name="TestReqID" type="STRING" />
+///         <field number="112" name="TestRequestID" type="STRING" />
+///       </fields>
+///     </fix>"#,
+/// );
+/// assert_eq!(
+///     errors[0].to_string(),
+///     "fields/field[TestRequestID]: tag '112' is defined more than once"
+/// );
+/// ```
+#[must_use]
+pub fn validate_dictionary(contents: &str) -> Vec<DataDictionaryError> {
+    let doc = match parse_document(contents) {
+        Ok(doc) => doc,
+        Err(e) => return vec![e],
+    };
+    let mut errors = Vec::new();
+    if let Err(e) = get_version_info(&doc) {
+        errors.push(e);
+    }
+
+    let mut tags = BTreeSet::new();
+    let mut fields = BTreeSet::new();
+    for (index, field) in child_elements(&doc, "fields", "field") {
+        let path = element_path("fields", field, index);
+        let attributes = ["number", "name", "type"].map(|a| required_attribute(field, a, &path));
+        let [number, name, _field_type] = attributes.map(|a| a.map_err(|e| errors.push(e)).ok());
+        if let Some(number) = number {
+            if number.parse::<Tag>().is_err() {
+                errors.push(DataDictionaryError::Malformed {
+                    path: path.as_str().into(),
+                    reason: format!("invalid number '{number}'").into(),
+                });
+            } else if !tags.insert(number.as_str()) {
+                errors.push(duplicate(&path, "tag", number));
+            }
+        }
+        if let Some(name) = name {
+            if !fields.insert(name.as_str()) {
+                errors.push(duplicate(&path, "field", name));
+            }
+        }
+    }
+
+    let mut components = BTreeMap::new();
+    for (index, component) in child_elements(&doc, "components", "component") {
+        let path = element_path("components", component, index);
+        match required_attribute(component, "name", &path) {
+            Ok(name) if components.contains_key(name.as_str()) => {
+                errors.push(duplicate(&path, "component", name));
+            }
+            Ok(name) => {
+                components.insert(name.as_str(), component);
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    let mut validator = Validator {
+        fields: &fields,
+        components: &components,
+        errors,
+    };
+    for (name, component) in &components {
+        let path = format!("component[{name}]");
+        validator.members(component, &path, &mut BTreeMap::new(), true, &[name]);
+    }
+    for section in ["header", "trailer"] {
+        if let Some(node) = doc.get_child(section) {
+            validator.members(node, section, &mut BTreeMap::new(), true, &[]);
+        }
+    }
+    for (index, message) in child_elements(&doc, "messages", "message") {
+        let path = element_path("", message, index);
+        if let Err(e) = required_attribute(message, "msgtype", &path) {
+            validator.errors.push(e);
+        }
+        validator.members(message, &path, &mut BTreeMap::new(), true, &[]);
+    }
+    validator.errors
+}
+
+fn duplicate(path: &str, entry_type: &str, name: &str) -> DataDictionaryError {
+    DataDictionaryError::Duplicate {
+        path: path.into(),
+        entry_type: entry_type.into(),
+        name: name.into(),
+    }
+}
+
+struct Validator<'a> {
+    fields: &'a BTreeSet<&'a str>,
+    components: &'a BTreeMap<&'a str, &'a Element>,
+    errors: Vec<DataDictionaryError>,
+}
+
+impl<'a> Validator<'a> {
+    /// Checks the fields, groups and components of `node`. The fields of a component are in the
+    /// scope of the message or group using it, `report` is false within a component used by a
+    /// message, those references are reported for the component itself.
+    fn members(
+        &mut self,
+        node: &'a Element,
+        path: &str,
+        scope: &mut BTreeMap<&'a str, bool>,
+        report: bool,
+        components: &[&str],
+    ) {
+        for (index, child) in node
+            .children
+            .iter()
+            .filter_map(|c| c.as_element())
+            .enumerate()
+        {
+            let child_path = element_path(path, child, index);
+            let name = match required_attribute(child, "name", &child_path) {
+                Ok(name) => name.as_str(),
+                Err(e) => {
+                    if report {
+                        self.errors.push(e);
+                    }
+                    continue;
+                }
+            };
+            match child.name.as_str() {
+                "field" | "group" => {
+                    if report && !self.fields.contains(name) {
+                        self.errors.push(DataDictionaryError::Missing {
+                            path: child_path.as_str().into(),
+                            entry_type: "field".into(),
+                            name: name.into(),
+                        });
+                    }
+                    match scope.get(name) {
+                        Some(reported) if report || *reported => {
+                            self.errors.push(duplicate(&child_path, "field", name));
+                        }
+                        Some(_) => {}
+                        None => {
+                            scope.insert(name, report);
+                        }
+                    }
+                    if child.name == "group" {
+                        self.members(child, &child_path, &mut BTreeMap::new(), report, components);
+                    }
+                }
+                "component" => match self.components.get(name) {
+                    None if report => self.errors.push(DataDictionaryError::Missing {
+                        path: child_path.as_str().into(),
+                        entry_type: "component".into(),
+                        name: name.into(),
+                    }),
+                    Some(_) if report && components.contains(&name) => {
+                        self.errors.push(DataDictionaryError::Malformed {
+                            path: child_path.as_str().into(),
+                            reason: "component includes itself".into(),
+                        });
+                    }
+                    Some(component) if !components.contains(&name) => {
+                        let components = [components, &[name]].concat();
+                        self.members(component, &child_path, scope, false, &components);
+                    }
+                    _ => {}
+                },
+                _ => {
+                    if report {
+                        self.errors.push(DataDictionaryError::Malformed {
+                            path: child_path.as_str().into(),
+                            reason: format!(
+                                "'{}' should be one of {{field,group,component}}",
+                                child.name
+                            )
+                            .into(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, DataDictionaryError> {
+    fs::read_to_string(path).map_err(|e| DataDictionaryError::IoError(e).in_file(path))
+}
+
+/// A QuickFIX dictionary, or an Orchestra repository translated to one.
+fn parse_document(contents: &str) -> Result<Element, DataDictionaryError> {
+    let root_doc = Element::parse(contents.as_bytes())?;
+    if orchestra::is_repository(&root_doc) {
+        orchestra::to_quickfix(&root_doc)
+    } else {
+        Ok(root_doc)
+    }
+}
+
+/// Merges the sections of `overlay` into `doc`, see [`DataDictionary::load_with_overlays`].
+fn merge_overlay(doc: &mut Element, overlay: &Element) {
+    for section in overlay.children.iter().filter_map(XMLNode::as_element) {
+        let Some(base) = doc.get_mut_child(section.name.as_str()) else {
+            doc.children.push(XMLNode::Element(section.clone()));
+            continue;
+        };
+        match section.name.as_str() {
+            "header" | "trailer" => merge_members(base, section),
+            "messages" => merge_entries(base, section, "msgtype"),
+            "components" => merge_entries(base, section, "name"),
+            "fields" => merge_fields(base, section),
+            _ => {}
+        }
+    }
+}
+
+/// Merges messages or components, matched by the `key` attribute.
+fn merge_entries(base: &mut Element, overlay: &Element, key: &str) {
+    for entry in overlay.children.iter().filter_map(XMLNode::as_element) {
+        let value = entry
+            .attributes
+            .get(key)
+            .map(String::as_str)
+            .unwrap_or_default();
+        match find_child_mut(base, &entry.name, key, value) {
+            Some(existing) => merge_members(existing, entry),
+            None => base.children.push(XMLNode::Element(entry.clone())),
+        }
+    }
+}
+
+/// Merges the fields, groups and components of a message, component or group.
+fn merge_members(base: &mut Element, overlay: &Element) {
+    for member in overlay.children.iter().filter_map(XMLNode::as_element) {
+        let name = member
+            .attributes
+            .get("name")
+            .map(String::as_str)
+            .unwrap_or_default();
+        match find_child_mut(base, &member.name, "name", name) {
+            Some(existing) => {
+                if let Some(required) = member.attributes.get("required") {
+                    existing
+                        .attributes
+                        .insert("required".into(), required.clone());
+                }
+                if member.name == "group" {
+                    merge_members(existing, member);
+                }
+            }
+            None => base.children.push(XMLNode::Element(member.clone())),
+        }
+    }
+}
+
+fn merge_fields(base: &mut Element, overlay: &Element) {
+    for field in overlay.children.iter().filter_map(XMLNode::as_element) {
+        let number = field
+            .attributes
+            .get("number")
+            .map(String::as_str)
+            .unwrap_or_default();
+        let Some(existing) = find_child_mut(base, &field.name, "number", number) else {
+            base.children.push(XMLNode::Element(field.clone()));
+            continue;
+        };
+        if let Some(field_type) = field.attributes.get("type") {
+            existing
+                .attributes
+                .insert("type".into(), field_type.clone());
+        }
+        for value in field.children.iter().filter_map(XMLNode::as_element) {
+            let enum_value = value
+                .attributes
+                .get("enum")
+                .map(String::as_str)
+                .unwrap_or_default();
+            match find_child_mut(existing, &value.name, "enum", enum_value) {
+                Some(existing) => existing.attributes = value.attributes.clone(),
+                None => existing.children.push(XMLNode::Element(value.clone())),
+            }
+        }
+    }
+}
+
+fn find_child_mut<'a>(
+    parent: &'a mut Element,
+    name: &str,
+    key: &str,
+    value: &str,
+) -> Option<&'a mut Element> {
+    parent
+        .children
+        .iter_mut()
+        .filter_map(XMLNode::as_mut_element)
+        .find(|child| child.name == name && child.attributes.get(key).is_some_and(|v| v == value))
+}
+
 fn get_version_info(doc: &Element) -> Result<(Arc<str>, Arc<str>, Arc<str>), DataDictionaryError> {
-    let major_version = doc
-        .attributes
-        .get("major")
-        .ok_or(DataDictionaryError::Missing {
-            entry_type: "attribute".into(),
-            name: "major".into(),
-        })?
-        .to_string();
-    let minor_version = doc
-        .attributes
-        .get("minor")
-        .ok_or(DataDictionaryError::Missing {
-            entry_type: "attribute".into(),
-            name: "minor".into(),
-        })?
-        .to_string();
+    let major_version = required_attribute(doc, "major", &doc.name)?;
+    let minor_version = required_attribute(doc, "minor", &doc.name)?;
     let version = "FIX".to_string();
     let version_type = doc.attributes.get("type").unwrap_or(&version);
     if version_type != "FIX" && version_type != "FIXT" {
@@ -1054,7 +1708,56 @@ fn get_version_info(doc: &Element) -> Result<(Arc<str>, Arc<str>, Arc<str>), Dat
         });
     }
     let version = format!("{version_type}.{major_version}.{minor_version}");
-    Ok((major_version.into(), minor_version.into(), version.into()))
+    Ok((
+        major_version.as_str().into(),
+        minor_version.as_str().into(),
+        version.into(),
+    ))
+}
+
+/// The attribute `name` of `element` at `path`.
+fn required_attribute<'a>(
+    element: &'a Element,
+    name: &str,
+    path: &str,
+) -> Result<&'a String, DataDictionaryError> {
+    element
+        .attributes
+        .get(name)
+        .ok_or_else(|| DataDictionaryError::Missing {
+            path: path.into(),
+            entry_type: "attribute".into(),
+            name: name.into(),
+        })
+}
+
+/// The path of the `index`th child `element` of `parent`, e.g. `message[NewOrderSingle]`.
+fn element_path(parent: &str, element: &Element, index: usize) -> String {
+    let name = element
+        .attributes
+        .get("name")
+        .cloned()
+        .unwrap_or_else(|| format!("#{}", index + 1));
+    if parent.is_empty() {
+        format!("{}[{name}]", element.name)
+    } else {
+        format!("{parent}/{}[{name}]", element.name)
+    }
+}
+
+fn child_elements<'a>(
+    doc: &'a Element,
+    section: &'a str,
+    name: &'a str,
+) -> impl Iterator<Item = (usize, &'a Element)> {
+    doc.children
+        .iter()
+        .filter_map(|c| c.as_element())
+        .filter(move |c| c.name == section)
+        .flat_map(|node| node.children.iter())
+        .filter_map(|c| c.as_element())
+        .filter(move |node| node.name == name)
+        .enumerate()
 }
 
 fn parse_fields(
@@ -1062,39 +1765,19 @@ fn parse_fields(
 ) -> Result<(BTreeMap<i32, Field>, BTreeMap<Arc<str>, Field>), DataDictionaryError> {
     let mut fields_by_tag: BTreeMap<i32, Field> = BTreeMap::new();
     let mut fields_by_name: BTreeMap<Arc<str>, Field> = BTreeMap::new();
-    let field_nodes = doc
-        .children
-        .iter()
-        .filter_map(|c| c.as_element())
-        .filter(|c| c.name == "fields")
-        .flat_map(|node| node.children.iter())
-        .filter_map(|c| c.as_element())
-        .filter(|node| node.name == "field");
 
-    for field_node in field_nodes {
-        let tag_str = field_node
-            .attributes
-            .get("number")
-            .ok_or(DataDictionaryError::Missing {
-                entry_type: "attribute".into(),
-                name: "major".into(),
+    for (index, field_node) in child_elements(doc, "fields", "field") {
+        let path = element_path("fields", field_node, index);
+        let tag_str = required_attribute(field_node, "number", &path)?;
+        let name = required_attribute(field_node, "name", &path)?;
+        let field_type = required_attribute(field_node, "type", &path)?;
+
+        let tag = tag_str
+            .parse::<i32>()
+            .map_err(|e| DataDictionaryError::Malformed {
+                path: path.as_str().into(),
+                reason: format!("invalid number '{tag_str}': {e}").into(),
             })?;
-        let name = field_node
-            .attributes
-            .get("name")
-            .ok_or(DataDictionaryError::Missing {
-                entry_type: "attribute".into(),
-                name: "name".into(),
-            })?;
-        let field_type = field_node
-            .attributes
-            .get("type")
-            .ok_or(DataDictionaryError::Missing {
-                entry_type: "attribute".into(),
-                name: "type".into(),
-            })?;
-
-        let tag = tag_str.parse::<i32>()?;
         let mut enums = BTreeMap::new();
         for enum_node in field_node
             .children
@@ -1102,14 +1785,8 @@ fn parse_fields(
             .filter_map(|c| c.as_element())
             .filter(|c| c.name == "value")
         {
-            let enum_value = enum_node
-                .attributes
-                .get("enum")
-                .ok_or(DataDictionaryError::Missing {
-                    entry_type: "attribute".into(),
-                    name: "enum".into(),
-                })?
-                .clone();
+            let enum_value =
+                required_attribute(enum_node, "enum", &format!("{path}/value"))?.clone();
             let description = enum_node
                 .attributes
                 .get("description")
@@ -1140,24 +1817,10 @@ fn parse_fields(
 
 fn cache_components(doc: &Element) -> Result<BTreeMap<Arc<str>, Element>, DataDictionaryError> {
     let mut components_by_name: BTreeMap<Arc<str>, Element> = BTreeMap::new();
-    let component_nodes = doc
-        .children
-        .iter()
-        .filter_map(|c| c.as_element())
-        .filter(|c| c.name == "components")
-        .flat_map(|node| node.children.iter())
-        .filter_map(|c| c.as_element())
-        .filter(|node| node.name == "component");
 
-    for component_node in component_nodes {
-        let name = component_node
-            .attributes
-            .get("name")
-            .ok_or(DataDictionaryError::Missing {
-                entry_type: "attribute".into(),
-                name: "name".into(),
-            })?
-            .clone();
+    for (index, component_node) in child_elements(doc, "components", "component") {
+        let path = element_path("components", component_node, index);
+        let name = required_attribute(component_node, "name", &path)?.clone();
         components_by_name.insert(name.into(), component_node.clone());
     }
     Ok(components_by_name)
@@ -1169,41 +1832,23 @@ fn parse_messages(
     components_by_name: &BTreeMap<Arc<str>, Element>,
 ) -> Result<BTreeMap<Arc<str>, DDMap>, DataDictionaryError> {
     let mut messages: BTreeMap<Arc<str>, DDMap> = BTreeMap::new();
-    let message_nodes = doc
-        .children
-        .iter()
-        .filter_map(|c| c.as_element())
-        .filter(|c| c.name == "messages")
-        .flat_map(|node| node.children.iter())
-        .filter_map(|c| c.as_element())
-        .filter(|node| node.name == "message");
 
-    for message_node in message_nodes {
-        let name: Arc<str> = message_node
-            .attributes
-            .get("name")
-            .ok_or(DataDictionaryError::Missing {
-                entry_type: "attribute".into(),
-                name: "name".into(),
-            })?
+    for (index, message_node) in child_elements(doc, "messages", "message") {
+        let path = element_path("", message_node, index);
+        let name: Arc<str> = required_attribute(message_node, "name", &path)?
+            .clone()
+            .into();
+        let msg_type: Arc<str> = required_attribute(message_node, "msgtype", &path)?
             .clone()
             .into();
         let mut dd_map = DDMap::new(name);
         parse_msg_element(
             message_node,
+            &path,
             &mut dd_map,
             fields_by_name,
             components_by_name,
         )?;
-        let msg_type: Arc<str> = message_node
-            .attributes
-            .get("msgtype")
-            .ok_or(DataDictionaryError::Missing {
-                entry_type: "attribute".into(),
-                name: "msgtype".into(),
-            })?
-            .clone()
-            .into();
         messages.insert(msg_type, dd_map);
     }
     Ok(messages)
@@ -1216,7 +1861,13 @@ fn parse_header(
 ) -> Result<DDMap, DataDictionaryError> {
     let mut dd_map = DDMap::new("header".into());
     if let Some(header_node) = doc.get_child("header") {
-        parse_msg_element(header_node, &mut dd_map, fields_by_name, components_by_name)?;
+        parse_msg_element(
+            header_node,
+            "header",
+            &mut dd_map,
+            fields_by_name,
+            components_by_name,
+        )?;
     }
     Ok(dd_map)
 }
@@ -1230,6 +1881,7 @@ fn parse_trailer(
     if let Some(trailer_node) = doc.get_child("trailer") {
         parse_msg_element(
             trailer_node,
+            "trailer",
             &mut dd_map,
             fields_by_name,
             components_by_name,
@@ -1238,33 +1890,16 @@ fn parse_trailer(
     Ok(dd_map)
 }
 
-fn verify_child_node(child_node: &Element, parent_node: &Element) {
-    assert!(
-        !child_node.attributes.is_empty(),
-        "Malformed data dictionary: Found text-only node containing '{}'",
-        child_node.get_text().unwrap_or_default().trim()
-    );
-    if !child_node.attributes.contains_key("name") {
-        let message_type_name = parent_node
-            .attributes
-            .get("name")
-            .cloned()
-            .unwrap_or_else(|| parent_node.name.clone());
-        panic!(
-            "Malformed data dictionary: Found '{}' node without 'name' within parent '{}/{}'",
-            child_node.name, parent_node.name, message_type_name
-        );
-    }
-}
-
 fn parse_msg_element(
     node: &Element,
+    path: &str,
     dd_map: &mut DDMap,
     fields_by_name: &BTreeMap<Arc<str>, Field>,
     components_by_name: &BTreeMap<Arc<str>, Element>,
 ) -> Result<(), DataDictionaryError> {
     parse_msg_element_inner(
         node,
+        path,
         &mut GoM::Map(dd_map),
         fields_by_name,
         components_by_name,
@@ -1274,91 +1909,117 @@ fn parse_msg_element(
 
 fn parse_msg_element_inner(
     node: &Element,
+    path: &str,
     dd_map: &mut GoM<'_>,
     fields_by_name: &BTreeMap<Arc<str>, Field>,
     components_by_name: &BTreeMap<Arc<str>, Element>,
     component_required: Option<bool>,
 ) -> Result<(), DataDictionaryError> {
-    let message_type_name = node
-        .attributes
-        .get("name")
-        .cloned()
-        .unwrap_or_else(|| node.name.clone());
-
-    if node.children.is_empty() {
-        return Ok(());
-    }
+    for (index, child_node) in node
+        .children
+        .iter()
+        .filter_map(|c| c.as_element())
+        .enumerate()
+    {
+        let child_path = element_path(path, child_node, index);
+        let name_attribute: Arc<str> = required_attribute(child_node, "name", &child_path)?
+            .clone()
+            .into();
 
-    for child_node in &node.children {
-        if let Some(child_node) = child_node.as_element() {
-            verify_child_node(child_node, node);
+        match child_node.name.as_str() {
+            "field" | "group" => {
+                let dd_field = fields_by_name
+                    .get(&name_attribute)
+                    .ok_or_else(|| DataDictionaryError::Missing {
+                        path: child_path.as_str().into(),
+                        entry_type: "field".into(),
+                        name: name_attribute.clone(),
+                    })?
+                    .clone();
+                let required = child_node
+                    .attributes
+                    .get("required")
+                    .is_some_and(|v| v == "Y")
+                    && component_required.unwrap_or(true);
+
+                if required {
+                    dd_map.required_fields.insert(dd_field.tag);
+                }
 
-            let name_attribute: Arc<str> = child_node
-                .attributes
-                .get("name")
-                .ok_or(DataDictionaryError::Missing {
-                    entry_type: "attribute".into(),
-                    name: "name".into(),
-                })?
-                .clone()
-                .into();
-
-            match child_node.name.as_str() {
-                "field" | "group" => {
-                    assert!(fields_by_name.contains_key(&name_attribute),
-                            "Field '{name_attribute}' is not defined in <fields> section."
-                        );
-                    let dd_field = fields_by_name.get(&name_attribute)
-                        .ok_or(DataDictionaryError::Missing { entry_type: "field".into(), name: name_attribute.clone() })?.clone();
-                    let required = child_node.attributes.get("required").is_some_and(|v| v == "Y")
-                        && component_required.unwrap_or(true);
+                if !dd_map.is_field(dd_field.tag) {
+                    dd_map.fields.insert(dd_field.tag, dd_field.clone());
+                }
 
-                    if required {
-                        dd_map.required_fields.insert(dd_field.tag);
+                //TODO check if ddmap is a ddgroup and set delim!
+                if let GoM::Group(grp) = dd_map {
+                    if grp.delim == 0 {
+                        grp.delim = dd_field.tag;
                     }
+                }
 
-                    if !dd_map.is_field(dd_field.tag) {
-                        dd_map.fields.insert(dd_field.tag, dd_field.clone());
-                    }
+                if child_node.name == "group" {
+                    let mut dd_grp = DDGroup::new();
+                    dd_grp.num_fld = dd_field.tag;
 
-                    //TODO check if ddmap is a ddgroup and set delim!
-                    if let GoM::Group(grp) = dd_map {
-                        if grp.delim == 0 {
-                            grp.delim = dd_field.tag;
-                        }
+                    if required {
+                        dd_grp.required = true;
                     }
 
-                    if child_node.name == "group" {
-                        let mut dd_grp = DDGroup::new();
-                        dd_grp.num_fld = dd_field.tag;
-
-                        if required {
-                            dd_grp.required = true;
-                        }
-
-                        {
-                            let mut dd_map = GoM::Group(&mut dd_grp);
-                            parse_msg_element_inner(child_node, &mut dd_map, fields_by_name, components_by_name, None)?;
-                        }
-
-                        dd_map.groups.insert(dd_field.tag, dd_grp.into());
+                    {
+                        let mut dd_map = GoM::Group(&mut dd_grp);
+                        parse_msg_element_inner(
+                            child_node,
+                            &child_path,
+                            &mut dd_map,
+                            fields_by_name,
+                            components_by_name,
+                            None,
+                        )?;
                     }
+
+                    dd_map.groups.insert(dd_field.tag, dd_grp.into());
                 }
-                "component" => {
-                    let component_node = components_by_name
-                        .get(&name_attribute)
-                        .ok_or(DataDictionaryError::Missing { entry_type: "component".into(), name: name_attribute.clone() })?
-                        .clone();
-
-                    let required = child_node.attributes.get("required").is_some_and(|v| v == "Y");
-                    parse_msg_element_inner(&component_node, dd_map, fields_by_name, components_by_name, Some(required))?;
+            }
+            "component" => {
+                let component_node = components_by_name.get(&name_attribute).ok_or_else(|| {
+                    DataDictionaryError::Missing {
+                        path: child_path.as_str().into(),
+                        entry_type: "component".into(),
+                        name: name_attribute.clone(),
+                    }
+                })?;
+                if path
+                    .split('/')
+                    .any(|parent| parent == format!("component[{name_attribute}]"))
+                {
+                    return Err(DataDictionaryError::Malformed {
+                        path: child_path.as_str().into(),
+                        reason: "component includes itself".into(),
+                    });
                 }
-                _ => panic!(
-                    "Malformed data dictionary: child node type should be one of {{field,group,component}} but is '{}' within parent '{}/{}'",
-                    child_node.name,
-                    node.name,
-                    message_type_name
-                ),
+
+                let required = child_node
+                    .attributes
+                    .get("required")
+                    .is_some_and(|v| v == "Y");
+                parse_msg_element_inner(
+                    component_node,
+                    &child_path,
+                    dd_map,
+                    fields_by_name,
+                    components_by_name,
+                    Some(required),
+                )?;
+            }
+            _ => {
+                return Err(DataDictionaryError::Malformed {
+                    path: child_path.as_str().into(),
+                    reason: format!(
+                        "'{}' should be one of {{field,group,component}}",
+                        child_node.name
+                    )
+                    .into(),
+                })
             }
         }
     }
@@ -1367,7 +2028,10 @@ fn parse_msg_element_inner(
 
 #[cfg(test)]
 mod tests {
-    use super::DataDictionary;
+    use super::{validate_dictionary, DataDictionary, DataDictionaryError, MessageValidationError};
+    use crate::field_map::{Field, Group, Tag};
+    use crate::message::Message;
+    use crate::tags;
 
     #[test]
     pub fn fix40() {
@@ -1417,6 +2081,271 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    pub fn field_types() {
+        let dd = DataDictionary::load_from_string(include_str!("../../../spec/FIX44.xml")).unwrap();
+        let valid = |tag, value: &str| dd.check_valid_format(&Field::new(tag, value)).is_ok();
+        assert!(valid(tags::MaturityMonthYear, "202403w2"));
+        assert!(!valid(tags::MaturityMonthYear, "202413"));
+        assert!(valid(tags::TradeDate, "20240229"));
+        assert!(!valid(tags::TradeDate, "2024-02-29"));
+        assert!(valid(tags::ExecInst, "1 G"));
+        assert!(!valid(tags::ExecInst, "1  G"));
+        assert!(valid(tags::MsgSeqNum, "4294967296"));
+        assert!(!valid(tags::MsgSeqNum, "-1"));
+        assert!(valid(tags::Price, "1234567890.123456789"));
+        assert!(!valid(tags::Price, "1,5"));
+    }
+
+    #[test]
+    pub fn validate_all() {
+        let dd = DataDictionary::load_from_string(include_str!("../../../spec/FIX44.xml")).unwrap();
+        let mut order = Message::default();
+        for (tag, value) in [
+            (tags::BeginString, "FIX.4.4"),
+            (tags::BodyLength, "0"),
+            (tags::MsgType, "D"),
+            (tags::SenderCompID, "SENDER"),
+            (tags::TargetCompID, "TARGET"),
+            (tags::MsgSeqNum, "2"),
+            (tags::SendingTime, "20240229-12:00:00"),
+        ] {
+            order.header_mut().set_tag_value(tag, value);
+        }
+        order.trailer_mut().set_tag_value(tags::CheckSum, "000");
+        for (tag, value) in [
+            (tags::Symbol, "EUR/USD"),
+            (tags::Side, "Z"),
+            (tags::TransactTime, "20240229-12:00:00"),
+            (tags::OrdType, "2"),
+            (tags::Price, "1,5"),
+        ] {
+            order.set_tag_value(tag, value);
+        }
+
+        let errors = DataDictionary::validate_all(&order, Some(&dd), &dd, "FIX.4.4", "D");
+        let errors: Vec<(Tag, &str)> = errors
+            .iter()
+            .map(|e| match e.error() {
+                MessageValidationError::TagException(e) => {
+                    (e.field(), e.session_reject_reason().reason())
+                }
+                e => panic!("{e:?}"),
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (tags::ClOrdID, "Required tag missing"),
+                (tags::Price, "Incorrect data format for value"),
+                (tags::Side, "Value is incorrect (out of range) for this tag"),
+            ]
+        );
+        assert!(matches!(
+            DataDictionary::validate(&order, Some(&dd), &dd, "FIX.4.4", "D"),
+            Err(MessageValidationError::TagException(e)) if e.field() == tags::ClOrdID
+        ));
+
+        let errors = DataDictionary::validate_all(&order, Some(&dd), &dd, "FIX.4.2", "D");
+        assert!(matches!(
+            errors.as_slice(),
+            [e] if matches!(e.error(), MessageValidationError::UnsupportedVersion { .. })
+        ));
+
+        let mut parties = Group::new(tags::NoPartyIDs, tags::PartyID);
+        parties.set_tag_value(tags::PartyID, "BROKER");
+        parties.set_tag_value(tags::PartyIDSource, "D");
+        order.add_group(tags::NoPartyIDs, &parties, Some(true));
+        let mut sub_parties = Group::new(tags::NoPartySubIDs, tags::PartySubID);
+        sub_parties.set_tag_value(tags::PartySubID, "DESK");
+        sub_parties.set_tag_value(tags::PartySubIDType, "X");
+        parties.remove_field(tags::PartyID);
+        parties.set_tag_value(tags::PartyIDSource, "Z");
+        parties.add_group(tags::NoPartySubIDs, &sub_parties, Some(true));
+        order.add_group(tags::NoPartyIDs, &parties, Some(true));
+        order.set_tag_value(tags::ClOrdID, "1");
+        order.set_tag_value(tags::Side, "1");
+        order.set_tag_value(tags::Price, "1.5");
+
+        let overlay = r#"<fix>
+          <components>
+            <component name="Parties">
+              <group name="NoPartyIDs" required="N">
+                <field name="PartyID" required="Y" />
+              </group>
+            </component>
+          </components>
+        </fix>"#;
+        let dd =
+            DataDictionary::load_with_overlays(include_str!("../../../spec/FIX44.xml"), &[overlay])
+                .unwrap();
+        let errors = DataDictionary::validate_all(&order, Some(&dd), &dd, "FIX.4.4", "D");
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            [
+                "453[2]/447: Value is incorrect (out of range) for this tag",
+                "453[2]/448: Required tag missing (advisory)",
+                "453[2]/802[1]/803: Incorrect data format for value (IntParseErr)",
+            ]
+        );
+
+        // required group fields are only checked by validate_all, as advisory violations
+        order.remove_group(2, tags::NoPartyIDs).unwrap();
+        let mut parties = Group::new(tags::NoPartyIDs, tags::PartyID);
+        parties.set_tag_value(tags::PartyIDSource, "D");
+        order.add_group(tags::NoPartyIDs, &parties, Some(true));
+        assert!(DataDictionary::validate(&order, Some(&dd), &dd, "FIX.4.4", "D").is_ok());
+        let errors = DataDictionary::validate_all(&order, Some(&dd), &dd, "FIX.4.4", "D");
+        assert!(matches!(errors.as_slice(), [e] if e.is_advisory()));
+        assert_eq!(
+            errors[0].to_string(),
+            "453[2]/448: Required tag missing (advisory)"
+        );
+    }
+
+    #[test]
+    pub fn overlays() {
+        let fields = r#"<fix>
+          <fields>
+            <field number="20001" name="VenueOrderFlag" type="CHAR">
+              <value enum="Y" description="YES" />
+            </field>
+            <field number="54" name="Side">
+              <value enum="Z" description="VENUE_SIDE" />
+            </field>
+          </fields>
+        </fix>"#;
+        let messages = r#"<fix>
+          <header>
+            <field name="OnBehalfOfCompID" required="Y" />
+          </header>
+          <messages>
+            <message name="NewOrderSingle" msgtype="D" msgcat="app">
+              <field name="Side" required="N" />
+              <field name="VenueOrderFlag" required="Y" />
+            </message>
+            <message name="VenueStatus" msgtype="U1" msgcat="app">
+              <field name="Text" required="Y" />
+            </message>
+          </messages>
+          <components>
+            <component name="Parties">
+              <group name="NoPartyIDs" required="N">
+                <field name="VenueOrderFlag" required="N" />
+              </group>
+            </component>
+          </components>
+        </fix>"#;
+        let dd = DataDictionary::load_with_overlays(
+            include_str!("../../../spec/FIX44.xml"),
+            &[fields, messages],
+        )
+        .unwrap();
+
+        let side = dd.get_field_by_name("Side").unwrap();
+        assert_eq!(side.field_type().as_ref(), "CHAR");
+        assert_eq!(side.enums().get("1").unwrap().as_ref(), "BUY");
+        assert_eq!(side.enums().get("Z").unwrap().as_ref(), "VENUE_SIDE");
+
+        let order = dd.get_map_for_message("D").unwrap();
+        assert!(order.required_fields().contains(&20001));
+        assert!(!order.required_fields().contains(&tags::Side));
+        let parties = order.get_group(tags::NoPartyIDs).unwrap();
+        assert_eq!(parties.delim(), tags::PartyID);
+        assert!(parties.is_field(20001));
+
+        let status = dd.get_map_for_message("U1").unwrap();
+        assert!(status.required_fields().contains(&tags::Text));
+        assert!(dd
+            .header()
+            .required_fields()
+            .contains(&tags::OnBehalfOfCompID));
+    }
+
+    #[test]
+    pub fn load_errors() {
+        let error = DataDictionary::from_file("does/not/exist.xml").unwrap_err();
+        assert!(matches!(
+            &error,
+            DataDictionaryError::File { error, .. } if matches!(**error, DataDictionaryError::IoError(_))
+        ));
+        assert!(error.to_string().starts_with("does/not/exist.xml: "));
+
+        let overlay = r#"<fix>
+          <components>
+            <component name="Parties">
+              <group name="NoPartyIDs" required="N">
+                <field name="PartyNickname" required="N" />
+              </group>
+            </component>
+          </components>
+        </fix>"#;
+        let error =
+            DataDictionary::load_with_overlays(include_str!("../../../spec/FIX44.xml"), &[overlay])
+                .unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("component[Parties]/group[NoPartyIDs]/field[PartyNickname]: field 'PartyNickname' is not defined"));
+        assert!(error.to_string().starts_with("message["));
+    }
+
+    #[test]
+    pub fn validate() {
+        for spec in [
+            include_str!("../../../spec/FIX40.xml"),
+            include_str!("../../../spec/FIX44.xml"),
+            include_str!("../../../spec/FIXT11.xml"),
+        ] {
+            let errors = validate_dictionary(spec);
+            assert!(errors.is_empty(), "{errors:?}");
+        }
+
+        let errors = validate_dictionary(
+            r#"<fix major="4" minor="4">
+              <header>
+                <field name="BeginString" required="Y" />
+              </header>
+              <messages>
+                <message name="NewOrderSingle" msgtype="D" msgcat="app">
+                  <field name="ClOrdID" required="Y" />
+                  <component name="Instrument" required="N" />
+                  <component name="Parties" required="N" />
+                  <field name="Symbol" required="N" />
+                </message>
+              </messages>
+              <components>
+                <component name="Instrument">
+                  <field name="Symbol" required="N" />
+                  <field name="SecurityID" required="N" />
+                  <component name="Instrument" required="N" />
+                </component>
+                <component name="Instrument" />
+              </components>
+              <fields>
+                <field number="8" name="BeginString" type="STRING" />
+                <field number="11" name="ClOrdID" type="STRING" />
+                <field number="55" name="Symbol" type="STRING" />
+                <field number="11" name="ClientOrderID" type="STRING" />
+                <field number="1x" name="Bad" type="STRING" />
+              </fields>
+            </fix>"#,
+        );
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            [
+                "fields/field[ClientOrderID]: tag '11' is defined more than once",
+                "fields/field[Bad]: invalid number '1x'",
+                "components/component[Instrument]: component 'Instrument' is defined more than once",
+                "component[Instrument]/field[SecurityID]: field 'SecurityID' is not defined",
+                "component[Instrument]/component[Instrument]: component includes itself",
+                "message[NewOrderSingle]/component[Parties]: component 'Parties' is not defined",
+                "message[NewOrderSingle]/field[Symbol]: field 'Symbol' is defined more than once",
+            ]
+        );
+    }
+
     #[test]
     pub fn fix50() {
         //let result = DataDictionary::load("../../../spec/FIX43.xml");
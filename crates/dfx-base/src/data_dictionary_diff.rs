@@ -0,0 +1,607 @@
+//! Differences between two [`DataDictionary`]s, e.g. the dictionary of a venue against ours.
+//!
+//! [`diff`] reports added and removed fields, type and enum changes, and per message (the header
+//! and trailer as `header` and `trailer`) added and removed fields and groups, required changes
+//! and group delimiter changes. A [`DictionaryDiff`] displays as text, [`DictionaryDiff::to_json`]
+//! writes it as JSON.
+//!
+//! ```
+//! use dfx_base::data_dictionary::DataDictionary;
+//! use dfx_base::data_dictionary_diff::{diff, MessageChange};
+//!
+//! let fix44 = include_str!("../../../spec/FIX44.xml");
+//! let ours = DataDictionary::load_from_string(fix44).unwrap();
+//! let overlay = r#"<fix><messages>
+//!   <message name="NewOrderSingle" msgtype="D" msgcat="app">
+//!     <field name="Price" required="Y" />
+//!   </message>
+//! </messages></fix>"#;
+//! let venue = DataDictionary::load_with_overlays(fix44, &[overlay]).unwrap();
+//!
+//! let diff = diff(&ours, &venue);
+//! assert_eq!(diff.messages.len(), 1);
+//! assert_eq!(
+//!     diff.messages[0].change,
+//!     MessageChange::RequiredChanged { required: true }
+//! );
+//! assert_eq!(diff.to_string(), "messages:\n  D NewOrderSingle: Price(44) is required\n");
+//! ```
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Write};
+
+use crate::data_dictionary::{DDField, DDMap, DataDictionary};
+use crate::field_map::Tag;
+
+/// The differences from one dictionary to another, see the [module documentation](self).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DictionaryDiff {
+    /// The versions of both dictionaries when they differ.
+    pub version: Option<(String, String)>,
+    pub fields: Vec<FieldDiff>,
+    pub messages: Vec<MessageDiff>,
+}
+
+/// A field, `Name(tag)` as text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldRef {
+    pub tag: Tag,
+    pub name: String,
+}
+
+/// A change of a field definition.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub field: FieldRef,
+    pub change: FieldChange,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldChange {
+    Added { field_type: String },
+    Removed,
+    Renamed { from: String },
+    TypeChanged { from: String, to: String },
+    EnumAdded { value: String, description: String },
+    EnumRemoved { value: String, description: String },
+}
+
+/// A change of a field or group within a message, `path` holds the groups it is nested in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MessageDiff {
+    pub msg_type: String,
+    pub name: String,
+    pub path: Vec<FieldRef>,
+    /// The field or group, `None` when the message itself is added or removed.
+    pub field: Option<FieldRef>,
+    pub change: MessageChange,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MessageChange {
+    MessageAdded,
+    MessageRemoved,
+    FieldAdded { required: bool },
+    FieldRemoved,
+    GroupAdded { required: bool },
+    GroupRemoved,
+    RequiredChanged { required: bool },
+    GroupDelimiterChanged { from: FieldRef, to: FieldRef },
+}
+
+/// The differences from `from` to `to`.
+#[must_use]
+pub fn diff(from: &DataDictionary, to: &DataDictionary) -> DictionaryDiff {
+    let version = |dd: &DataDictionary| dd.version().map(ToString::to_string).unwrap_or_default();
+    let (from_version, to_version) = (version(from), version(to));
+
+    let mut messages = Vec::new();
+    let mut maps = vec![
+        ("header", Some(from.header()), Some(to.header())),
+        ("trailer", Some(from.trailer()), Some(to.trailer())),
+    ];
+    let msg_types: BTreeSet<&str> = from
+        .messages()
+        .keys()
+        .chain(to.messages().keys())
+        .map(AsRef::as_ref)
+        .collect();
+    for msg_type in msg_types {
+        maps.push((
+            msg_type,
+            from.get_map_for_message(msg_type),
+            to.get_map_for_message(msg_type),
+        ));
+    }
+    for (msg_type, from_map, to_map) in maps {
+        let message = |name: &str, field, change| MessageDiff {
+            msg_type: msg_type.into(),
+            name: name.into(),
+            path: Vec::new(),
+            field,
+            change,
+        };
+        match (from_map, to_map) {
+            (Some(from_map), Some(to_map)) => {
+                let mut changes = Vec::new();
+                diff_map(from_map, to_map, &[], &mut changes);
+                messages.extend(
+                    changes
+                        .into_iter()
+                        .map(|(path, field, change)| MessageDiff {
+                            path,
+                            ..message(to_map.name(), Some(field), change)
+                        }),
+                );
+            }
+            (None, Some(to_map)) => {
+                messages.push(message(to_map.name(), None, MessageChange::MessageAdded));
+            }
+            (Some(from_map), None) => {
+                messages.push(message(
+                    from_map.name(),
+                    None,
+                    MessageChange::MessageRemoved,
+                ));
+            }
+            (None, None) => {}
+        }
+    }
+
+    DictionaryDiff {
+        version: (from_version != to_version).then_some((from_version, to_version)),
+        fields: diff_fields(from, to),
+        messages,
+    }
+}
+
+fn diff_fields(from: &DataDictionary, to: &DataDictionary) -> Vec<FieldDiff> {
+    fn by_tag(dd: &DataDictionary) -> BTreeMap<Tag, &DDField> {
+        dd.fields_by_name()
+            .values()
+            .map(|field| (field.tag(), field.as_ref()))
+            .collect()
+    }
+    let (from_fields, to_fields) = (by_tag(from), by_tag(to));
+    let tags: BTreeSet<Tag> = from_fields
+        .keys()
+        .chain(to_fields.keys())
+        .copied()
+        .collect();
+
+    let mut fields = Vec::new();
+    for tag in tags {
+        let mut push = |field: &DDField, change| {
+            fields.push(FieldDiff {
+                field: FieldRef::of(field),
+                change,
+            });
+        };
+        match (from_fields.get(&tag), to_fields.get(&tag)) {
+            (None, Some(to_field)) => push(
+                to_field,
+                FieldChange::Added {
+                    field_type: to_field.field_type().to_string(),
+                },
+            ),
+            (Some(from_field), None) => push(from_field, FieldChange::Removed),
+            (Some(from_field), Some(to_field)) => {
+                if from_field.name() != to_field.name() {
+                    push(
+                        to_field,
+                        FieldChange::Renamed {
+                            from: from_field.name().to_string(),
+                        },
+                    );
+                }
+                if from_field.field_type() != to_field.field_type() {
+                    push(
+                        to_field,
+                        FieldChange::TypeChanged {
+                            from: from_field.field_type().to_string(),
+                            to: to_field.field_type().to_string(),
+                        },
+                    );
+                }
+                for (value, description) in to_field.enums() {
+                    if !from_field.enums().contains_key(value) {
+                        push(
+                            to_field,
+                            FieldChange::EnumAdded {
+                                value: value.to_string(),
+                                description: description.to_string(),
+                            },
+                        );
+                    }
+                }
+                for (value, description) in from_field.enums() {
+                    if !to_field.enums().contains_key(value) {
+                        push(
+                            to_field,
+                            FieldChange::EnumRemoved {
+                                value: value.to_string(),
+                                description: description.to_string(),
+                            },
+                        );
+                    }
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    fields
+}
+
+fn diff_map(
+    from: &DDMap,
+    to: &DDMap,
+    path: &[FieldRef],
+    changes: &mut Vec<(Vec<FieldRef>, FieldRef, MessageChange)>,
+) {
+    let tags: BTreeSet<Tag> = from
+        .fields()
+        .keys()
+        .chain(to.fields().keys())
+        .copied()
+        .collect();
+    for tag in tags {
+        let required = to.required_fields().contains(&tag);
+        let (field, change) = match (from.get_field(tag), to.get_field(tag)) {
+            (None, Some(field)) if to.is_group(tag) => {
+                (field, MessageChange::GroupAdded { required })
+            }
+            (None, Some(field)) => (field, MessageChange::FieldAdded { required }),
+            (Some(field), None) if from.is_group(tag) => (field, MessageChange::GroupRemoved),
+            (Some(field), None) => (field, MessageChange::FieldRemoved),
+            (Some(_), Some(field)) => {
+                if from.required_fields().contains(&tag) != required {
+                    changes.push((
+                        path.to_vec(),
+                        FieldRef::of(field),
+                        MessageChange::RequiredChanged { required },
+                    ));
+                }
+                match (from.get_group(tag), to.get_group(tag)) {
+                    (Some(from_group), Some(to_group)) => {
+                        if from_group.delim() != to_group.delim() {
+                            let delim = |group: &DDMap, tag| FieldRef {
+                                tag,
+                                name: group
+                                    .get_field(tag)
+                                    .map(|field| field.name().to_string())
+                                    .unwrap_or_default(),
+                            };
+                            changes.push((
+                                path.to_vec(),
+                                FieldRef::of(field),
+                                MessageChange::GroupDelimiterChanged {
+                                    from: delim(from_group, from_group.delim()),
+                                    to: delim(to_group, to_group.delim()),
+                                },
+                            ));
+                        }
+                        let mut group_path = path.to_vec();
+                        group_path.push(FieldRef::of(field));
+                        diff_map(from_group, to_group, &group_path, changes);
+                    }
+                    (None, Some(_)) => {
+                        changes.push((
+                            path.to_vec(),
+                            FieldRef::of(field),
+                            MessageChange::GroupAdded { required },
+                        ));
+                    }
+                    (Some(_), None) => {
+                        changes.push((
+                            path.to_vec(),
+                            FieldRef::of(field),
+                            MessageChange::GroupRemoved,
+                        ));
+                    }
+                    (None, None) => {}
+                }
+                continue;
+            }
+            (None, None) => continue,
+        };
+        changes.push((path.to_vec(), FieldRef::of(field), change));
+    }
+}
+
+impl FieldRef {
+    fn of(field: &DDField) -> Self {
+        FieldRef {
+            tag: field.tag(),
+            name: field.name().to_string(),
+        }
+    }
+}
+
+impl DictionaryDiff {
+    /// Whether both dictionaries define the same fields and messages.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.version.is_none() && self.fields.is_empty() && self.messages.is_empty()
+    }
+
+    /// The differences as a JSON object with `version`, `fields` and `messages`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"version\":");
+        match &self.version {
+            Some((from, to)) => {
+                let _ = write!(json, "{{\"from\":{},\"to\":{}}}", quote(from), quote(to));
+            }
+            None => json.push_str("null"),
+        }
+        json.push_str(",\"fields\":[");
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "{{\"tag\":{},\"name\":{},{}}}",
+                field.field.tag,
+                quote(&field.field.name),
+                field.change.json()
+            );
+        }
+        json.push_str("],\"messages\":[");
+        for (index, message) in self.messages.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let path: Vec<String> = message.path.iter().map(FieldRef::json).collect();
+            let _ = write!(
+                json,
+                "{{\"msg_type\":{},\"name\":{},\"path\":[{}],\"field\":{},{}}}",
+                quote(&message.msg_type),
+                quote(&message.name),
+                path.join(","),
+                message.field.as_ref().map_or("null".into(), FieldRef::json),
+                message.change.json()
+            );
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+impl FieldRef {
+    fn json(&self) -> String {
+        format!("{{\"tag\":{},\"name\":{}}}", self.tag, quote(&self.name))
+    }
+}
+
+impl FieldChange {
+    fn json(&self) -> String {
+        match self {
+            FieldChange::Added { field_type } => {
+                format!("\"change\":\"added\",\"type\":{}", quote(field_type))
+            }
+            FieldChange::Removed => "\"change\":\"removed\"".into(),
+            FieldChange::Renamed { from } => {
+                format!("\"change\":\"renamed\",\"from\":{}", quote(from))
+            }
+            FieldChange::TypeChanged { from, to } => format!(
+                "\"change\":\"type_changed\",\"from\":{},\"to\":{}",
+                quote(from),
+                quote(to)
+            ),
+            FieldChange::EnumAdded { value, description } => format!(
+                "\"change\":\"enum_added\",\"value\":{},\"description\":{}",
+                quote(value),
+                quote(description)
+            ),
+            FieldChange::EnumRemoved { value, description } => format!(
+                "\"change\":\"enum_removed\",\"value\":{},\"description\":{}",
+                quote(value),
+                quote(description)
+            ),
+        }
+    }
+}
+
+impl MessageChange {
+    fn json(&self) -> String {
+        match self {
+            MessageChange::MessageAdded => "\"change\":\"message_added\"".into(),
+            MessageChange::MessageRemoved => "\"change\":\"message_removed\"".into(),
+            MessageChange::FieldAdded { required } => {
+                format!("\"change\":\"field_added\",\"required\":{required}")
+            }
+            MessageChange::FieldRemoved => "\"change\":\"field_removed\"".into(),
+            MessageChange::GroupAdded { required } => {
+                format!("\"change\":\"group_added\",\"required\":{required}")
+            }
+            MessageChange::GroupRemoved => "\"change\":\"group_removed\"".into(),
+            MessageChange::RequiredChanged { required } => {
+                format!("\"change\":\"required_changed\",\"required\":{required}")
+            }
+            MessageChange::GroupDelimiterChanged { from, to } => format!(
+                "\"change\":\"group_delimiter_changed\",\"from\":{},\"to\":{}",
+                from.json(),
+                to.json()
+            ),
+        }
+    }
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Display for FieldRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", self.name, self.tag)
+    }
+}
+
+impl Display for FieldDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.field)?;
+        match &self.change {
+            FieldChange::Added { field_type } => write!(f, "added, {field_type}"),
+            FieldChange::Removed => write!(f, "removed"),
+            FieldChange::Renamed { from } => write!(f, "renamed from {from}"),
+            FieldChange::TypeChanged { from, to } => write!(f, "type {from} -> {to}"),
+            FieldChange::EnumAdded { value, description } => {
+                write!(f, "enum {value} ({description}) added")
+            }
+            FieldChange::EnumRemoved { value, description } => {
+                write!(f, "enum {value} ({description}) removed")
+            }
+        }
+    }
+}
+
+impl Display for MessageDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.msg_type, self.name)?;
+        if let Some(field) = &self.field {
+            f.write_str(": ")?;
+            for group in &self.path {
+                write!(f, "{group}/")?;
+            }
+            write!(f, "{field}")?;
+        }
+        let optional = |required: &bool| if *required { "required" } else { "optional" };
+        match &self.change {
+            MessageChange::MessageAdded => write!(f, " added"),
+            MessageChange::MessageRemoved => write!(f, " removed"),
+            MessageChange::FieldAdded { required } => {
+                write!(f, " added, {}", optional(required))
+            }
+            MessageChange::FieldRemoved => write!(f, " removed"),
+            MessageChange::GroupAdded { required } => {
+                write!(f, " group added, {}", optional(required))
+            }
+            MessageChange::GroupRemoved => write!(f, " group removed"),
+            MessageChange::RequiredChanged { required } => write!(f, " is {}", optional(required)),
+            MessageChange::GroupDelimiterChanged { from, to } => {
+                write!(f, " group delimiter {from} -> {to}")
+            }
+        }
+    }
+}
+
+impl Display for DictionaryDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((from, to)) = &self.version {
+            writeln!(f, "version: {from} -> {to}")?;
+        }
+        if !self.fields.is_empty() {
+            writeln!(f, "fields:")?;
+            for field in &self.fields {
+                writeln!(f, "  {field}")?;
+            }
+        }
+        if !self.messages.is_empty() {
+            writeln!(f, "messages:")?;
+            for message in &self.messages {
+                writeln!(f, "  {message}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, FieldChange, FieldRef, MessageChange};
+    use crate::data_dictionary::DataDictionary;
+    use crate::tags;
+
+    #[test]
+    fn diff_overlay() {
+        let fix44 = include_str!("../../../spec/FIX44.xml");
+        let ours = DataDictionary::load_from_string(fix44).unwrap();
+        assert!(diff(&ours, &ours).is_empty());
+
+        let overlay = r#"<fix>
+          <fields>
+            <field number="20001" name="VenueOrderFlag" type="CHAR" />
+            <field number="54" name="Side" type="CHAR">
+              <value enum="Z" description="VENUE &quot;SIDE&quot;" />
+            </field>
+          </fields>
+          <messages>
+            <message name="NewOrderSingle" msgtype="D" msgcat="app">
+              <field name="Price" required="Y" />
+            </message>
+            <message name="VenueStatus" msgtype="U1" msgcat="app">
+              <field name="Text" required="Y" />
+            </message>
+          </messages>
+          <components>
+            <component name="Parties">
+              <group name="NoPartyIDs" required="N">
+                <field name="VenueOrderFlag" required="N" />
+              </group>
+            </component>
+          </components>
+        </fix>"#;
+        let venue = DataDictionary::load_with_overlays(fix44, &[overlay]).unwrap();
+        let diff = diff(&ours, &venue);
+        assert_eq!(diff.version, None);
+
+        assert_eq!(diff.fields.len(), 2);
+        assert_eq!(diff.fields[0].field.tag, tags::Side);
+        assert!(matches!(
+            &diff.fields[0].change,
+            FieldChange::EnumAdded { value, .. } if value == "Z"
+        ));
+        assert_eq!(
+            diff.fields[1].change,
+            FieldChange::Added {
+                field_type: "CHAR".into()
+            }
+        );
+
+        let order: Vec<_> = diff.messages.iter().filter(|m| m.msg_type == "D").collect();
+        assert_eq!(order.len(), 2);
+        assert_eq!(
+            order[0].change,
+            MessageChange::RequiredChanged { required: true }
+        );
+        assert_eq!(
+            order[1].path,
+            vec![FieldRef {
+                tag: tags::NoPartyIDs,
+                name: "NoPartyIDs".into()
+            }]
+        );
+        assert_eq!(
+            order[1].to_string(),
+            "D NewOrderSingle: NoPartyIDs(453)/VenueOrderFlag(20001) added, optional"
+        );
+        assert!(diff
+            .messages
+            .iter()
+            .any(|m| m.msg_type == "U1" && m.change == MessageChange::MessageAdded));
+
+        let json = diff.to_json();
+        assert!(json.starts_with(
+            r#"{"version":null,"fields":[{"tag":54,"name":"Side","change":"enum_added","value":"Z","description":"VENUE \"SIDE\""}"#
+        ));
+        assert!(json.contains(
+            r#"{"msg_type":"D","name":"NewOrderSingle","path":[],"field":{"tag":44,"name":"Price"},"change":"required_changed","required":true}"#
+        ));
+    }
+}
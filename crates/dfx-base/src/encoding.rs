@@ -0,0 +1,190 @@
+//! Character encodings of FIX messages.
+//!
+//! A message string holds one `char` per byte of the message, the `char` with the same code
+//! point as the byte (ISO-8859-1). [`encode_latin1`] turns such a string back into the bytes
+//! that are sent, so values in any encoding pass through unchanged and BodyLength and CheckSum
+//! match the bytes on the wire.
+//!
+//! The Encoded* fields (EncodedText, EncodedIssuer, ...) are encoded with the
+//! [`MessageEncoding`] named in MessageEncoding (347), their length fields (EncodedTextLen,
+//! EncodedIssuerLen, ...) count bytes.
+//!
+//! ```
+//! use dfx_base::encoding::MessageEncoding;
+//! use dfx_base::message::Message;
+//! use dfx_base::tags;
+//!
+//! let mut message = Message::default();
+//! message.set_encoding(MessageEncoding::for_label("Shift_JIS").unwrap());
+//! message.set_encoded(tags::EncodedText, "日本語").unwrap();
+//! assert_eq!(message.get_int(tags::EncodedTextLen).unwrap(), 6);
+//! assert_eq!(message.get_encoded(tags::EncodedText).unwrap(), "日本語");
+//! ```
+use std::fmt::Display;
+
+use encoding_rs::Encoding;
+
+use crate::field_map::Tag;
+use crate::fields::ConversionError;
+use crate::tags;
+
+/// A character set of MessageEncoding (347), e.g. `UTF-8`, `Shift_JIS`, `EUC-JP`,
+/// `ISO-2022-JP` or `ISO-8859-1`. Defaults to UTF-8.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageEncoding {
+    name: &'static str,
+    encoding: &'static Encoding,
+}
+
+impl Default for MessageEncoding {
+    fn default() -> Self {
+        MessageEncoding {
+            name: encoding_rs::UTF_8.name(),
+            encoding: encoding_rs::UTF_8,
+        }
+    }
+}
+
+impl Display for MessageEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl MessageEncoding {
+    /// Returns `None` for an unknown character set.
+    #[must_use]
+    pub fn for_label(label: &str) -> Option<Self> {
+        let encoding = Encoding::for_label(label.as_bytes())?;
+        // ISO-8859-1 is decoded as its superset windows-1252, keep the name FIX uses
+        let name = if label.eq_ignore_ascii_case("ISO-8859-1") {
+            "ISO-8859-1"
+        } else {
+            encoding.name()
+        };
+        Some(MessageEncoding { name, encoding })
+    }
+
+    /// The value of MessageEncoding (347).
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, ConversionError> {
+        self.encoding
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .map(|value| value.into_owned())
+            .ok_or(ConversionError::EncodingError)
+    }
+
+    pub fn encode(&self, value: &str) -> Result<Vec<u8>, ConversionError> {
+        let (bytes, _encoding, unmappable) = self.encoding.encode(value);
+        if unmappable {
+            Err(ConversionError::EncodingError)
+        } else {
+            Ok(bytes.into_owned())
+        }
+    }
+}
+
+/// The length field of an Encoded* field, e.g. EncodedTextLen for EncodedText.
+#[must_use]
+pub fn encoded_length_tag(tag: Tag) -> Option<Tag> {
+    match tag {
+        tags::EncodedIssuer => Some(tags::EncodedIssuerLen),
+        tags::EncodedSecurityDesc => Some(tags::EncodedSecurityDescLen),
+        tags::EncodedListExecInst => Some(tags::EncodedListExecInstLen),
+        tags::EncodedText => Some(tags::EncodedTextLen),
+        tags::EncodedSubject => Some(tags::EncodedSubjectLen),
+        tags::EncodedHeadline => Some(tags::EncodedHeadlineLen),
+        tags::EncodedAllocText => Some(tags::EncodedAllocTextLen),
+        tags::EncodedUnderlyingIssuer => Some(tags::EncodedUnderlyingIssuerLen),
+        tags::EncodedUnderlyingSecurityDesc => Some(tags::EncodedUnderlyingSecurityDescLen),
+        tags::EncodedListStatusText => Some(tags::EncodedListStatusTextLen),
+        tags::EncodedLegIssuer => Some(tags::EncodedLegIssuerLen),
+        tags::EncodedLegSecurityDesc => Some(tags::EncodedLegSecurityDescLen),
+        tags::DerivativeEncodedIssuer => Some(tags::DerivativeEncodedIssuerLen),
+        tags::DerivativeEncodedSecurityDesc => Some(tags::DerivativeEncodedSecurityDescLen),
+        tags::EncodedSymbol => Some(tags::EncodedSymbolLen),
+        tags::EncodedMktSegmDesc => Some(tags::EncodedMktSegmDescLen),
+        tags::EncodedSecurityListDesc => Some(tags::EncodedSecurityListDescLen),
+        tags::RelationshipRiskEncodedSecurityDesc => {
+            Some(tags::RelationshipRiskEncodedSecurityDescLen)
+        }
+        tags::RiskEncodedSecurityDesc => Some(tags::RiskEncodedSecurityDescLen),
+        _ => None,
+    }
+}
+
+/// Whether `tag` is the length field of an Encoded* field, e.g. EncodedTextLen.
+#[must_use]
+pub fn is_encoded_length_tag(tag: Tag) -> bool {
+    tag.checked_add(1).and_then(encoded_length_tag) == Some(tag)
+}
+
+/// The bytes of a message string, see the [module documentation](self).
+#[must_use]
+pub fn encode_latin1(message: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(message.len());
+    for c in message.chars() {
+        match u8::try_from(c) {
+            Ok(byte) => bytes.push(byte),
+            // Not from a field value, written as is
+            Err(_) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+    bytes
+}
+
+/// The message string of `bytes`, see the [module documentation](self).
+#[must_use]
+pub fn decode_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| char::from(*b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_latin1, encode_latin1, is_encoded_length_tag, MessageEncoding, Tag};
+    use crate::message::Message;
+    use crate::tags;
+
+    #[test]
+    fn message_encoding() {
+        let shift_jis = MessageEncoding::for_label("Shift_JIS").unwrap();
+        assert_eq!(shift_jis.name(), "Shift_JIS");
+        assert_eq!(
+            shift_jis.encode("日本").unwrap(),
+            vec![0x93, 0xfa, 0x96, 0x7b]
+        );
+        assert_eq!(shift_jis.decode(&[0x93, 0xfa, 0x96, 0x7b]).unwrap(), "日本");
+        assert!(MessageEncoding::default().decode(&[0xe9]).is_err());
+        assert!(MessageEncoding::for_label("EBCDIC-FIX").is_none());
+
+        assert!(is_encoded_length_tag(tags::EncodedTextLen));
+        assert!(!is_encoded_length_tag(tags::EncodedText));
+        assert!(!is_encoded_length_tag(Tag::MAX));
+
+        let bytes = vec![b'o', b'l', 0xc3, 0xa9, 0xe9];
+        assert_eq!(encode_latin1(&decode_latin1(&bytes)), bytes);
+    }
+
+    #[test]
+    fn transcode_encoded_fields() {
+        let mut message = Message::default();
+        message.set_encoded(tags::EncodedText, "olé").unwrap();
+        assert_eq!(message.get_int(tags::EncodedTextLen).unwrap(), 4);
+
+        let latin1 = MessageEncoding::for_label("ISO-8859-1").unwrap();
+        message
+            .transcode_encoded_fields(MessageEncoding::default(), latin1)
+            .unwrap();
+        message.set_encoding(latin1);
+        assert_eq!(
+            message.header().get_string(tags::MessageEncoding).unwrap(),
+            "ISO-8859-1"
+        );
+        assert_eq!(message.get_int(tags::EncodedTextLen).unwrap(), 3);
+        assert_eq!(message.get_encoded(tags::EncodedText).unwrap(), "olé");
+    }
+}
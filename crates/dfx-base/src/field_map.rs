@@ -1,6 +1,8 @@
 use chrono::DateTime;
 use chrono::Utc;
 
+use crate::encoding::encoded_length_tag;
+use crate::encoding::MessageEncoding;
 use crate::fields::converters::IntoFieldValue;
 use crate::fields::converters::TryFromFieldValue;
 use crate::fields::ConversionError;
@@ -237,6 +239,54 @@ impl FieldMap {
     pub fn get_datetime(&self, tag: Tag) -> Result<DateTime<Utc>, ConversionError> {
         self.fields[&tag].as_value()
     }
+    pub fn get_encoded_string(
+        &self,
+        tag: Tag,
+        encoding: MessageEncoding,
+    ) -> Result<String, FieldMapError> {
+        match self.fields.get(&tag) {
+            None => Err(FieldMapError::FieldNotFound(tag)),
+            Some(field) => Ok(encoding.decode(field.value())?),
+        }
+    }
+    /// Sets the Encoded* field `tag` and its length field, e.g. EncodedTextLen for EncodedText.
+    pub fn set_encoded_string(
+        &mut self,
+        tag: Tag,
+        value: &str,
+        encoding: MessageEncoding,
+    ) -> Result<(), FieldMapError> {
+        let bytes = encoding.encode(value)?;
+        if let Some(length_tag) = encoded_length_tag(tag) {
+            self.set_tag_value(length_tag, bytes.len());
+        }
+        self.set_field_base(Field::from_bytes(tag, bytes.into()), None);
+        Ok(())
+    }
+    /// Re-encodes the Encoded* fields of this map and its groups from `from` to `to`.
+    pub fn transcode_encoded_fields(
+        &mut self,
+        from: MessageEncoding,
+        to: MessageEncoding,
+    ) -> Result<(), FieldMapError> {
+        if from == to {
+            return Ok(());
+        }
+        let encoded_tags: Vec<Tag> = self
+            .fields
+            .keys()
+            .copied()
+            .filter(|tag| encoded_length_tag(*tag).is_some())
+            .collect();
+        for tag in encoded_tags {
+            let value = self.get_encoded_string(tag, from)?;
+            self.set_encoded_string(tag, &value, to)?;
+        }
+        for group in self.groups.values_mut().flatten() {
+            group.transcode_encoded_fields(from, to)?;
+        }
+        Ok(())
+    }
     // VALUES
 
     pub fn get_field_mut(&mut self, tag: Tag) -> Option<&mut Field> {
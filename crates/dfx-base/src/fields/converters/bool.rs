@@ -13,7 +13,7 @@ impl<'a> TryFromFieldValue<&'a FieldValue> for bool {
         match ref_str {
             "Y" => Ok(true),
             "N" => Ok(false),
-            _ => todo!(),
+            _ => Err(ConversionError::EncodingError),
         }
     }
 }
@@ -63,8 +63,12 @@ impl std::convert::TryFrom<String> for DateTimeFormat {
     }
 }
 
+use std::fmt::Display;
+use std::str::FromStr;
+
 use chrono::{
-    DateTime as ChronoDateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    DateTime as ChronoDateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Utc,
 };
 
 use crate::field_map::FieldValue;
@@ -77,14 +81,8 @@ impl<'a> TryFromFieldValue<&'a FieldValue> for ChronoDateTime<Utc> {
     type Error = ConversionError;
 
     fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
-        let time: &str = TryFromFieldValue::try_from_field_value(value)?;
-        match Utc.datetime_from_str(time, DATE_TIME_FORMAT_WITHOUT_MILLISECONDS) {
-            Ok(t) => Ok(t),
-            Err(e) => match Utc.datetime_from_str(time, DATE_TIME_FORMAT_WITH_MILLISECONDS) {
-                Ok(t) => Ok(t),
-                Err(_) => todo!(),
-            },
-        }
+        let datetime: DateTime = TryFromFieldValue::try_from_field_value(value)?;
+        Ok(Utc.from_utc_datetime(&datetime))
     }
 }
 
@@ -92,6 +90,148 @@ pub type Date = NaiveDate;
 pub type Time = NaiveTime;
 pub type DateTime = NaiveDateTime;
 
+/// TZTimestamp, a timestamp with the offset from UTC it was taken in, e.g.
+/// `20060901-07:39Z` or `20060901-02:39:00.123-05:00`.
+pub type TZTimestamp = ChronoDateTime<FixedOffset>;
+
+/// LocalMktDate, a date in the local time of the market, `YYYYMMDD`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocalMktDate(pub Date);
+
+/// TZTimeOnly, a time of day with its offset from UTC, e.g. `07:39Z` or `02:39:00-05:00`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TZTimeOnly {
+    pub time: Time,
+    pub offset: FixedOffset,
+}
+
+impl TZTimeOnly {
+    /// The time of day in UTC.
+    #[must_use]
+    pub fn utc_time(&self) -> Time {
+        self.time - chrono::Duration::seconds(i64::from(self.offset.local_minus_utc()))
+    }
+}
+
+fn parse_date(value: &str) -> Result<Date, ConversionError> {
+    if value.len() != 8 || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ConversionError::EncodingError);
+    }
+    NaiveDate::parse_from_str(value, DATE_ONLY_FORMAT).map_err(|_e| ConversionError::EncodingError)
+}
+
+/// `HH:MM:SS` with an optional fraction of up to nanoseconds, `HH:MM` when `allow_minutes`.
+fn parse_time(value: &str, allow_minutes: bool) -> Result<Time, ConversionError> {
+    let bytes = value.as_bytes();
+    let valid = match bytes.len() {
+        5 => allow_minutes,
+        8 => bytes[5] == b':',
+        10..=18 => bytes[5] == b':' && bytes[8] == b'.',
+        _ => false,
+    };
+    if !valid || bytes[2] != b':' {
+        return Err(ConversionError::EncodingError);
+    }
+    let format = if bytes.len() == 5 {
+        "%H:%M"
+    } else {
+        "%H:%M:%S%.f"
+    };
+    NaiveTime::parse_from_str(value, format).map_err(|_e| ConversionError::EncodingError)
+}
+
+/// `Z`, `+hh`, `-hh`, `+hh:mm` or `-hh:mm`.
+fn parse_offset(value: &str) -> Result<FixedOffset, ConversionError> {
+    if value == "Z" {
+        return Ok(Utc.fix());
+    }
+    let sign = match value.as_bytes().first() {
+        Some(b'+') => 1,
+        Some(b'-') => -1,
+        _ => return Err(ConversionError::EncodingError),
+    };
+    let (hours, minutes) = match value.len() {
+        3 => (&value[1..3], "00"),
+        6 if &value[3..4] == ":" => (&value[1..3], &value[4..6]),
+        _ => return Err(ConversionError::EncodingError),
+    };
+    if !hours
+        .bytes()
+        .chain(minutes.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return Err(ConversionError::EncodingError);
+    }
+    let hours: i32 = hours.parse().map_err(|_e| ConversionError::EncodingError)?;
+    let minutes: i32 = minutes
+        .parse()
+        .map_err(|_e| ConversionError::EncodingError)?;
+    if hours > 14 || minutes > 59 {
+        return Err(ConversionError::EncodingError);
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+        .ok_or(ConversionError::EncodingError)
+}
+
+/// Splits `value` into its time of day and offset.
+fn parse_tz_time(value: &str) -> Result<(Time, FixedOffset), ConversionError> {
+    if !value.is_ascii() {
+        return Err(ConversionError::EncodingError);
+    }
+    let split = if value.ends_with('Z') {
+        value.len() - 1
+    } else {
+        value
+            .rfind(['+', '-'])
+            .ok_or(ConversionError::EncodingError)?
+    };
+    let offset = parse_offset(&value[split..])?;
+    let time = parse_time(&value[..split], true)?;
+    Ok((time, offset))
+}
+
+fn format_offset(offset: &FixedOffset) -> String {
+    if offset.local_minus_utc() == 0 {
+        "Z".into()
+    } else {
+        offset.to_string()
+    }
+}
+
+impl FromStr for LocalMktDate {
+    type Err = ConversionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_date(value).map(LocalMktDate)
+    }
+}
+
+impl Display for LocalMktDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.format(DATE_ONLY_FORMAT))
+    }
+}
+
+impl FromStr for TZTimeOnly {
+    type Err = ConversionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (time, offset) = parse_tz_time(value)?;
+        Ok(TZTimeOnly { time, offset })
+    }
+}
+
+impl Display for TZTimeOnly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            self.time.format("%H:%M:%S%.f"),
+            format_offset(&self.offset)
+        )
+    }
+}
+
 impl<'a> TryFromFieldValue<&'a FieldValue> for DateTime {
     type Error = ConversionError;
 
@@ -104,10 +244,7 @@ impl<'a> TryFromFieldValue<&'a FieldValue> for DateTime {
             DATE_TIME_FORMAT_WITHOUT_MILLISECONDS_LEN => Ok(DATE_TIME_FORMAT_WITHOUT_MILLISECONDS),
             len => Err(ConversionError::EncodingError),
         }?;
-        match NaiveDateTime::parse_from_str(time, format) {
-            Ok(v) => Ok(v),
-            Err(e) => todo!("{e}"),
-        }
+        NaiveDateTime::parse_from_str(time, format).map_err(|_e| ConversionError::EncodingError)
     }
 }
 
@@ -115,7 +252,8 @@ impl<'a> TryFromFieldValue<&'a FieldValue> for Time {
     type Error = ConversionError;
 
     fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
-        todo!()
+        let time: &str = TryFromFieldValue::try_from_field_value(value)?;
+        parse_time(time, false)
     }
 }
 
@@ -123,24 +261,158 @@ impl<'a> TryFromFieldValue<&'a FieldValue> for Date {
     type Error = ConversionError;
 
     fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
-        todo!()
+        let date: &str = TryFromFieldValue::try_from_field_value(value)?;
+        parse_date(date)
+    }
+}
+
+impl<'a> TryFromFieldValue<&'a FieldValue> for LocalMktDate {
+    type Error = ConversionError;
+
+    fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
+        let date: &str = TryFromFieldValue::try_from_field_value(value)?;
+        date.parse()
+    }
+}
+
+impl<'a> TryFromFieldValue<&'a FieldValue> for TZTimestamp {
+    type Error = ConversionError;
+
+    fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
+        let timestamp: &str = TryFromFieldValue::try_from_field_value(value)?;
+        if timestamp.len() < 9 || timestamp.as_bytes()[8] != b'-' {
+            return Err(ConversionError::EncodingError);
+        }
+        let date = parse_date(&timestamp[..8])?;
+        let (time, offset) = parse_tz_time(&timestamp[9..])?;
+        offset
+            .from_local_datetime(&date.and_time(time))
+            .single()
+            .ok_or(ConversionError::EncodingError)
+    }
+}
+
+impl<'a> TryFromFieldValue<&'a FieldValue> for TZTimeOnly {
+    type Error = ConversionError;
+
+    fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
+        let time: &str = TryFromFieldValue::try_from_field_value(value)?;
+        time.parse()
     }
 }
 
 impl IntoFieldValue<FieldValue> for DateTime {
     fn into_field_value(&self) -> FieldValue {
-        todo!()
+        self.format(DATE_TIME_FORMAT_WITH_MILLISECONDS)
+            .to_string()
+            .into_bytes()
+            .into()
     }
 }
 
 impl IntoFieldValue<FieldValue> for Date {
     fn into_field_value(&self) -> FieldValue {
-        todo!()
+        self.format(DATE_ONLY_FORMAT)
+            .to_string()
+            .into_bytes()
+            .into()
     }
 }
 
 impl IntoFieldValue<FieldValue> for Time {
     fn into_field_value(&self) -> FieldValue {
-        todo!()
+        self.format(TIME_ONLY_FORMAT_WITH_MILLISECONDS)
+            .to_string()
+            .into_bytes()
+            .into()
+    }
+}
+
+impl IntoFieldValue<FieldValue> for LocalMktDate {
+    fn into_field_value(&self) -> FieldValue {
+        self.to_string().into_bytes().into()
+    }
+}
+
+impl IntoFieldValue<FieldValue> for TZTimestamp {
+    fn into_field_value(&self) -> FieldValue {
+        format!(
+            "{}{}",
+            self.format("%Y%m%d-%H:%M:%S%.f"),
+            format_offset(self.offset())
+        )
+        .into_bytes()
+        .into()
+    }
+}
+
+impl IntoFieldValue<FieldValue> for TZTimeOnly {
+    fn into_field_value(&self) -> FieldValue {
+        self.to_string().into_bytes().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime, Timelike};
+
+    use crate::field_map::Field;
+
+    use super::{LocalMktDate, TZTimeOnly, TZTimestamp};
+
+    #[test]
+    fn date_and_time() {
+        let field = Field::new(1, "20240229");
+        assert_eq!(
+            field.as_value::<NaiveDate>().unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        assert!(Field::new(1, "20230229").as_value::<NaiveDate>().is_err());
+        assert!(Field::new(1, "2024229").as_value::<NaiveDate>().is_err());
+
+        let time = Field::new(1, "12:30:01.123456")
+            .as_value::<NaiveTime>()
+            .unwrap();
+        assert_eq!(time.nanosecond(), 123_456_000);
+        assert!(Field::new(1, "12:30:01").as_value::<NaiveTime>().is_ok());
+        assert!(Field::new(1, "12:30").as_value::<NaiveTime>().is_err());
+        assert!(Field::new(1, "12:30:01.").as_value::<NaiveTime>().is_err());
+
+        let date = Field::new(1, "20240101")
+            .as_value::<LocalMktDate>()
+            .unwrap();
+        assert_eq!(Field::new(1, date).value().as_ref(), b"20240101");
+    }
+
+    #[test]
+    fn tz_timestamp() {
+        let timestamp = Field::new(1, "20060901-02:39:00.123-05:00")
+            .as_value::<TZTimestamp>()
+            .unwrap();
+        assert_eq!(timestamp.offset().local_minus_utc(), -5 * 3600);
+        assert_eq!(timestamp.naive_utc().to_string(), "2006-09-01 07:39:00.123");
+        assert_eq!(
+            Field::new(1, timestamp).value().as_ref(),
+            b"20060901-02:39:00.123-05:00"
+        );
+
+        let timestamp = Field::new(1, "20060901-07:39Z")
+            .as_value::<TZTimestamp>()
+            .unwrap();
+        assert_eq!(
+            Field::new(1, timestamp).value().as_ref(),
+            b"20060901-07:39:00Z"
+        );
+        assert!(Field::new(1, "20060901-07:39:00")
+            .as_value::<TZTimestamp>()
+            .is_err());
+        assert!(Field::new(1, "20060901-07:39:00+5")
+            .as_value::<TZTimestamp>()
+            .is_err());
+
+        let time = Field::new(1, "13:00+09").as_value::<TZTimeOnly>().unwrap();
+        assert_eq!(time.utc_time(), NaiveTime::from_hms_opt(4, 0, 0).unwrap());
+        assert_eq!(time.to_string(), "13:00:00+09:00");
+        assert!(Field::new(1, "13:00").as_value::<TZTimeOnly>().is_err());
     }
 }
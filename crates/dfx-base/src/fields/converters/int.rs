@@ -4,6 +4,9 @@ use crate::fields::ConversionError;
 
 use super::IntoFieldValue;
 
+/// SeqNum, a message sequence number. Also used to validate Length, NumInGroup and TagNum.
+pub type SeqNum = u64;
+
 impl<'a> TryFromFieldValue<&'a FieldValue> for usize {
     type Error = ConversionError;
 
@@ -168,3 +171,9 @@ impl IntoFieldValue<FieldValue> for i64 {
         format!("{self}").as_bytes().to_vec().into()
     }
 }
+
+impl IntoFieldValue<FieldValue> for u64 {
+    fn into_field_value(&self) -> FieldValue {
+        format!("{self}").as_bytes().to_vec().into()
+    }
+}
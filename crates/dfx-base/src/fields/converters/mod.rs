@@ -1,9 +1,16 @@
 pub mod datetime;
-pub use datetime::{Date, DateTime, Time};
+pub use datetime::{Date, DateTime, LocalMktDate, TZTimeOnly, TZTimestamp, Time};
 mod string;
 
 pub mod r#bool;
 mod int;
+pub use int::SeqNum;
+
+mod month_year;
+pub use month_year::{MonthYear, MonthYearPeriod};
+
+mod multiple_value;
+pub use multiple_value::{MultipleCharValue, MultipleStringValue};
 
 mod decimal;
 pub use decimal::*;
@@ -0,0 +1,127 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+
+use crate::field_map::FieldValue;
+use crate::fields::converters::TryFromFieldValue;
+use crate::fields::ConversionError;
+
+use super::IntoFieldValue;
+
+/// MonthYear, e.g. the maturity of a future: `YYYYMM`, `YYYYMMDD` or `YYYYMMwN` for the Nth
+/// week of the month.
+///
+/// ```
+/// use dfx_base::fields::converters::{MonthYear, MonthYearPeriod};
+///
+/// let month_year: MonthYear = "202403w2".parse().unwrap();
+/// assert_eq!(month_year.period, Some(MonthYearPeriod::Week(2)));
+/// assert!("202413".parse::<MonthYear>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonthYear {
+    pub year: i32,
+    pub month: u32,
+    pub period: Option<MonthYearPeriod>,
+}
+
+/// The day or week of a [`MonthYear`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MonthYearPeriod {
+    Day(u32),
+    /// Week 1 to 5 of the month.
+    Week(u32),
+}
+
+fn parse_digits(value: &str) -> Result<u32, ConversionError> {
+    if value.bytes().all(|b| b.is_ascii_digit()) {
+        value.parse().map_err(|_e| ConversionError::EncodingError)
+    } else {
+        Err(ConversionError::EncodingError)
+    }
+}
+
+impl FromStr for MonthYear {
+    type Err = ConversionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if !value.is_ascii() || (value.len() != 6 && value.len() != 8) {
+            return Err(ConversionError::EncodingError);
+        }
+        let year = parse_digits(&value[..4])? as i32;
+        let month = parse_digits(&value[4..6])?;
+        if !(1..=12).contains(&month) {
+            return Err(ConversionError::EncodingError);
+        }
+        let period = match value.get(6..7) {
+            None => None,
+            Some("w") => match parse_digits(&value[7..])? {
+                week @ 1..=5 => Some(MonthYearPeriod::Week(week)),
+                _ => return Err(ConversionError::EncodingError),
+            },
+            Some(_) => {
+                let day = parse_digits(&value[6..])?;
+                NaiveDate::from_ymd_opt(year, month, day).ok_or(ConversionError::EncodingError)?;
+                Some(MonthYearPeriod::Day(day))
+            }
+        };
+        Ok(MonthYear {
+            year,
+            month,
+            period,
+        })
+    }
+}
+
+impl Display for MonthYear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}{:02}", self.year, self.month)?;
+        match self.period {
+            Some(MonthYearPeriod::Day(day)) => write!(f, "{day:02}"),
+            Some(MonthYearPeriod::Week(week)) => write!(f, "w{week}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> TryFromFieldValue<&'a FieldValue> for MonthYear {
+    type Error = ConversionError;
+
+    fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
+        let month_year: &str = TryFromFieldValue::try_from_field_value(value)?;
+        month_year.parse()
+    }
+}
+
+impl IntoFieldValue<FieldValue> for MonthYear {
+    fn into_field_value(&self) -> FieldValue {
+        self.to_string().into_bytes().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MonthYear, MonthYearPeriod};
+
+    #[test]
+    fn month_year() {
+        let month_year: MonthYear = "202402".parse().unwrap();
+        assert_eq!(month_year.period, None);
+        assert_eq!(month_year.to_string(), "202402");
+
+        let month_year: MonthYear = "20240229".parse().unwrap();
+        assert_eq!(month_year.period, Some(MonthYearPeriod::Day(29)));
+        assert_eq!(month_year.to_string(), "20240229");
+
+        let month_year: MonthYear = "202402w5".parse().unwrap();
+        assert_eq!(month_year.period, Some(MonthYearPeriod::Week(5)));
+        assert_eq!(month_year.to_string(), "202402w5");
+
+        for invalid in [
+            "2024", "202400", "20230229", "202402w6", "202402w", "2024-02",
+        ] {
+            assert!(invalid.parse::<MonthYear>().is_err(), "{invalid}");
+        }
+    }
+}
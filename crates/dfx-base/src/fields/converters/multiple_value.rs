@@ -0,0 +1,138 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::field_map::FieldValue;
+use crate::fields::converters::TryFromFieldValue;
+use crate::fields::ConversionError;
+
+use super::IntoFieldValue;
+
+/// MultipleCharValue, single character values separated by a space, e.g. ExecInst `1 G`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MultipleCharValue(pub Vec<char>);
+
+/// MultipleStringValue (MultipleValueString before FIX 4.4), values separated by a space,
+/// e.g. QuoteCondition `A AB`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MultipleStringValue(pub Vec<String>);
+
+impl MultipleCharValue {
+    #[must_use]
+    pub fn contains(&self, value: char) -> bool {
+        self.0.contains(&value)
+    }
+}
+
+impl MultipleStringValue {
+    #[must_use]
+    pub fn contains(&self, value: &str) -> bool {
+        self.0.iter().any(|v| v == value)
+    }
+}
+
+/// The values of `value`, an empty value (e.g. a double or trailing space) is invalid.
+fn split(value: &str) -> impl Iterator<Item = Result<&str, ConversionError>> {
+    value.split(' ').map(|v| {
+        if v.is_empty() {
+            Err(ConversionError::EncodingError)
+        } else {
+            Ok(v)
+        }
+    })
+}
+
+impl FromStr for MultipleCharValue {
+    type Err = ConversionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        split(value)
+            .map(|v| {
+                let mut chars = v?.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(ConversionError::EncodingError),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(MultipleCharValue)
+    }
+}
+
+impl FromStr for MultipleStringValue {
+    type Err = ConversionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        split(value)
+            .map(|v| v.map(String::from))
+            .collect::<Result<_, _>>()
+            .map(MultipleStringValue)
+    }
+}
+
+impl Display for MultipleCharValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<String> = self.0.iter().map(char::to_string).collect();
+        f.write_str(&values.join(" "))
+    }
+}
+
+impl Display for MultipleStringValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.join(" "))
+    }
+}
+
+impl<'a> TryFromFieldValue<&'a FieldValue> for MultipleCharValue {
+    type Error = ConversionError;
+
+    fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
+        let values: &str = TryFromFieldValue::try_from_field_value(value)?;
+        values.parse()
+    }
+}
+
+impl<'a> TryFromFieldValue<&'a FieldValue> for MultipleStringValue {
+    type Error = ConversionError;
+
+    fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
+        let values: &str = TryFromFieldValue::try_from_field_value(value)?;
+        values.parse()
+    }
+}
+
+impl IntoFieldValue<FieldValue> for MultipleCharValue {
+    fn into_field_value(&self) -> FieldValue {
+        self.to_string().into_bytes().into()
+    }
+}
+
+impl IntoFieldValue<FieldValue> for MultipleStringValue {
+    fn into_field_value(&self) -> FieldValue {
+        self.to_string().into_bytes().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultipleCharValue, MultipleStringValue};
+
+    #[test]
+    fn multiple_values() {
+        let chars: MultipleCharValue = "1 G".parse().unwrap();
+        assert_eq!(chars, MultipleCharValue(vec!['1', 'G']));
+        assert!(chars.contains('G'));
+        assert_eq!(chars.to_string(), "1 G");
+        assert!("1 GH".parse::<MultipleCharValue>().is_err());
+
+        let strings: MultipleStringValue = "A AB".parse().unwrap();
+        assert!(strings.contains("AB"));
+        assert!(!strings.contains("B"));
+        assert_eq!(strings.to_string(), "A AB");
+        for invalid in ["", "A  AB", "A ", " A"] {
+            assert!(
+                invalid.parse::<MultipleStringValue>().is_err(),
+                "{invalid:?}"
+            );
+        }
+    }
+}
@@ -1,14 +1,16 @@
+use crate::encoding::decode_latin1;
 use crate::field_map::FieldValue;
 use crate::fields::converters::TryFromFieldValue;
 use crate::fields::ConversionError;
 
 use super::IntoFieldValue;
 
+/// One `char` per byte, see [`encoding`](crate::encoding).
 impl<'a> TryFromFieldValue<&'a FieldValue> for String {
     type Error = ConversionError;
 
     fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
-        Ok(value.iter().map(|b| *b as char).collect())
+        Ok(decode_latin1(value))
     }
 }
 
@@ -18,11 +20,12 @@ impl IntoFieldValue<FieldValue> for String {
     }
 }
 
+/// Requires UTF-8, Encoded* fields in other encodings are read with
+/// [`Message::get_encoded`](crate::message::Message::get_encoded).
 impl<'a> TryFromFieldValue<&'a FieldValue> for &'a str {
     type Error = ConversionError;
 
     fn try_from_field_value(value: &'a FieldValue) -> Result<Self, Self::Error> {
-        // TODO encoding latin1
         std::str::from_utf8(value).map_err(|_| ConversionError::EncodingError)
     }
 }
@@ -6,8 +6,16 @@ pub enum FieldType {
     DateTime,
     Decimal,
     Int,
+    LocalMktDate,
+    MonthYear,
+    MultipleCharValue,
+    MultipleStringValue,
+    /// SEQNUM, LENGTH, NUMINGROUP and TAGNUM, non-negative integers.
+    SeqNum,
     String,
     TimeOnly,
+    TZTimeOnly,
+    TZTimestamp,
 }
 
 impl FieldType {
@@ -20,36 +28,36 @@ impl FieldType {
             "AMT" => Ok(Self::Decimal),
             "QTY" => Ok(Self::Decimal),
             "CURRENCY" => Ok(Self::String),
-            "MULTIPLEVALUESTRING" => Ok(Self::String),
-            "MULTIPLESTRINGVALUE" => Ok(Self::String),
-            "MULTIPLECHARVALUE" => Ok(Self::String),
+            "MULTIPLEVALUESTRING" => Ok(Self::MultipleStringValue),
+            "MULTIPLESTRINGVALUE" => Ok(Self::MultipleStringValue),
+            "MULTIPLECHARVALUE" => Ok(Self::MultipleCharValue),
             "EXCHANGE" => Ok(Self::String),
             "UTCTIMESTAMP" => Ok(Self::DateTime),
             "BOOLEAN" => Ok(Self::Boolean),
-            "LOCALMKTDATE" => Ok(Self::String),
-            "LOCALMKTTIME" => Ok(Self::String),
+            "LOCALMKTDATE" => Ok(Self::LocalMktDate),
+            "LOCALMKTTIME" => Ok(Self::TimeOnly),
             "DATA" => Ok(Self::String),
             "FLOAT" => Ok(Self::Decimal),
             "PRICEOFFSET" => Ok(Self::Decimal),
-            "MONTHYEAR" => Ok(Self::String),
+            "MONTHYEAR" => Ok(Self::MonthYear),
             "DAYOFMONTH" => Ok(Self::String),
             "UTCDATE" => Ok(Self::DateOnly),
             "UTCDATEONLY" => Ok(Self::DateOnly),
             "UTCTIMEONLY" => Ok(Self::TimeOnly),
-            "NUMINGROUP" => Ok(Self::Int),
+            "NUMINGROUP" => Ok(Self::SeqNum),
             "PERCENTAGE" => Ok(Self::Decimal),
-            "SEQNUM" => Ok(Self::Int),
-            "TAGNUM" => Ok(Self::Int),
-            "LENGTH" => Ok(Self::Int),
+            "SEQNUM" => Ok(Self::SeqNum),
+            "TAGNUM" => Ok(Self::SeqNum),
+            "LENGTH" => Ok(Self::SeqNum),
             "COUNTRY" => Ok(Self::String),
-            "TZTIMEONLY" => Ok(Self::String),
-            "TZTIMESTAMP" => Ok(Self::String),
+            "TZTIMEONLY" => Ok(Self::TZTimeOnly),
+            "TZTIMESTAMP" => Ok(Self::TZTimestamp),
             "XMLDATA" => Ok(Self::String),
             "LANGUAGE" => Ok(Self::String),
             "XID" => Ok(Self::String),
             "XIDREF" => Ok(Self::String),
             "TIME" => Ok(Self::DateTime),
-            "DATE" => Ok(Self::String),
+            "DATE" => Ok(Self::DateOnly),
             _ => Err(format!(
                 "Invalid type: {}",
                 String::from_utf8_lossy(value.as_ref())
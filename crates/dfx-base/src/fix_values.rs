@@ -250,6 +250,10 @@ pub struct BusinessRejectReason {
 }
 
 impl BusinessRejectReason {
+    #[must_use]
+    pub fn OTHER(reason: String) -> BusinessRejectReason {
+        BusinessRejectReason { index: 0, reason }
+    }
     #[must_use]
     pub fn UNKNOWN_MESSAGE_TYPE() -> BusinessRejectReason {
         BusinessRejectReason {
@@ -257,6 +261,13 @@ impl BusinessRejectReason {
             reason: "Unsupported Message Type".to_string(),
         }
     }
+    #[must_use]
+    pub fn CONDITIONALLY_REQUIRED_FIELD_MISSING() -> BusinessRejectReason {
+        BusinessRejectReason {
+            index: 5,
+            reason: "Conditionally Required Field Missing".to_string(),
+        }
+    }
 
     #[must_use]
     pub fn index(&self) -> usize {
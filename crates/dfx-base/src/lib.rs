@@ -1,12 +1,17 @@
 mod checksum;
 pub mod data_dictionary;
+pub mod data_dictionary_diff;
 pub mod data_dictionary_provider;
+pub mod encoding;
 pub mod field_map;
 pub mod fields;
 pub mod fix_values;
+pub mod log_parser;
 pub mod message;
 pub mod message_factory;
+pub mod orchestra;
 pub mod parser;
 pub mod session_id;
 pub mod tags;
+pub mod validation_rules;
 pub use checksum::*;
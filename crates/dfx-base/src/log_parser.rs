@@ -0,0 +1,395 @@
+//! Reads FIX messages from engine logs and raw captures and pretty-prints them.
+//!
+//! A [`LogParser`] finds every message starting with `8=FIX`, delimited by SOH, `|` or `^A`,
+//! in any number per line. The text before a message on its line gives the [`Direction`], e.g.
+//! `[INCOMING]` of the `PrintLnLogger` or a `.in`/`.out` log target, and the timestamp, e.g.
+//! `20240229-12:00:00.000 : ` of QuickFIX logs. Without a timestamp in the log the SendingTime
+//! is used, without a direction in the log [`LogParser::with_local_comp_id`] tells them apart.
+//!
+//! [`pretty_print`] writes a message one field per line with the field names and enum
+//! descriptions of a [`DataDictionary`], group entries indented below their NumInGroup field.
+//!
+//! ```
+//! use dfx_base::data_dictionary::DataDictionary;
+//! use dfx_base::log_parser::{pretty_print, Direction, LogParser};
+//!
+//! let log = "[OUTGOING] FIX.4.4:SENDER->TARGET \
+//!     8=FIX.4.4|9=50|35=D|49=SENDER|56=TARGET|453=1|448=BROKER|447=D|54=1|10=000|";
+//! let entries = LogParser::new().parse(log.as_bytes());
+//! assert_eq!(entries[0].direction(), Some(Direction::Outgoing));
+//! assert_eq!(entries[0].msg_type(), Some("D"));
+//!
+//! let dd = DataDictionary::load_from_string(include_str!("../../../spec/FIX44.xml")).unwrap();
+//! let text = pretty_print(entries[0].message(), &dd);
+//! assert!(text.contains("MsgType (35) = D [ORDER_SINGLE]\n"));
+//! assert!(text.contains("NoPartyIDs (453) = 1\n- PartyID (448) = BROKER\n"));
+//! ```
+use std::fmt::Write;
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDateTime};
+
+use crate::data_dictionary::{DDMap, DataDictionary};
+use crate::field_map::Tag;
+use crate::tags;
+
+const SOH: &str = "\x01";
+const UTC_TIMESTAMP_FORMAT: &str = "%Y%m%d-%H:%M:%S%.f";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Incoming,
+    Outgoing,
+}
+
+/// A message found in a log.
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    line: usize,
+    timestamp: Option<NaiveDateTime>,
+    direction: Option<Direction>,
+    message: String,
+}
+
+impl LogEntry {
+    /// The 1-based line the message is on.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The time logged with the message, or its SendingTime.
+    #[must_use]
+    pub fn timestamp(&self) -> Option<NaiveDateTime> {
+        self.timestamp
+    }
+
+    #[must_use]
+    pub fn direction(&self) -> Option<Direction> {
+        self.direction
+    }
+
+    /// The message, SOH delimited.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The tags and values of the message in order, a field without `=` has tag 0.
+    pub fn fields(&self) -> impl Iterator<Item = (Tag, &str)> {
+        fields(&self.message)
+    }
+
+    /// The value of the first `tag` in the message.
+    #[must_use]
+    pub fn field(&self, tag: Tag) -> Option<&str> {
+        self.fields()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, value)| value)
+    }
+
+    #[must_use]
+    pub fn msg_type(&self) -> Option<&str> {
+        self.field(tags::MsgType)
+    }
+}
+
+/// Splits logs into [`LogEntry`]s, see the [module documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct LogParser {
+    local_comp_id: Option<String>,
+}
+
+impl LogParser {
+    #[must_use]
+    pub fn new() -> Self {
+        LogParser::default()
+    }
+
+    /// Messages without a direction in the log are outgoing when their SenderCompID is
+    /// `comp_id` and incoming when their TargetCompID is, e.g. for `FileLogger` output.
+    #[must_use]
+    pub fn with_local_comp_id<S: Into<String>>(mut self, comp_id: S) -> Self {
+        self.local_comp_id = Some(comp_id.into());
+        self
+    }
+
+    pub fn parse_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<Vec<LogEntry>> {
+        Ok(self.parse(&std::fs::read(path)?))
+    }
+
+    /// Reads a log or capture, bytes that are not UTF-8 are replaced.
+    #[must_use]
+    pub fn parse(&self, contents: &[u8]) -> Vec<LogEntry> {
+        let contents = String::from_utf8_lossy(contents);
+        let mut entries = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let mut rest = line;
+            while let Some(start) = find_begin_string(rest) {
+                let prefix = &rest[..start];
+                let (message, end) = read_message(&rest[start..]);
+                let mut entry = LogEntry {
+                    line: index + 1,
+                    timestamp: find_timestamp(prefix),
+                    direction: find_direction(prefix),
+                    message,
+                };
+                if entry.timestamp.is_none() {
+                    entry.timestamp = entry.field(tags::SendingTime).and_then(|sending_time| {
+                        NaiveDateTime::parse_from_str(sending_time, UTC_TIMESTAMP_FORMAT).ok()
+                    });
+                }
+                if entry.direction.is_none() {
+                    entry.direction = self.local_comp_id.as_deref().and_then(|comp_id| {
+                        if entry.field(tags::SenderCompID) == Some(comp_id) {
+                            Some(Direction::Outgoing)
+                        } else if entry.field(tags::TargetCompID) == Some(comp_id) {
+                            Some(Direction::Incoming)
+                        } else {
+                            None
+                        }
+                    });
+                }
+                entries.push(entry);
+                rest = &rest[start + end..];
+            }
+        }
+        entries
+    }
+}
+
+fn fields(message: &str) -> impl Iterator<Item = (Tag, &str)> {
+    message
+        .split(SOH)
+        .filter(|field| !field.is_empty())
+        .map(|field| match field.split_once('=') {
+            Some((tag, value)) => (tag.parse().unwrap_or(0), value),
+            None => (0, field),
+        })
+}
+
+/// The start of the next `8=FIX` that is not the end of another field, e.g. `58=FIX`.
+fn find_begin_string(text: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(index) = text[offset..].find("8=FIX") {
+        let start = offset + index;
+        if !text[..start].ends_with(|c: char| c.is_ascii_digit()) {
+            return Some(start);
+        }
+        offset = start + 1;
+    }
+    None
+}
+
+/// Reads the message at the start of `text` up to and including the CheckSum, or the next
+/// message or end of the line for truncated captures. Returns the message with SOH delimiters
+/// and the length read.
+fn read_message(text: &str) -> (String, usize) {
+    let delimiter = [SOH, "|", "^A"]
+        .into_iter()
+        .filter_map(|d| text.find(d).map(|index| (index, d)))
+        .min()
+        .map_or(SOH, |(_, d)| d);
+    let end = match text.find(&format!("{delimiter}10=")) {
+        Some(checksum) => {
+            let value = checksum + delimiter.len();
+            text[value..]
+                .find(delimiter)
+                .map_or(text.len(), |index| value + index + delimiter.len())
+        }
+        None => find_begin_string(&text[1..]).map_or(text.len(), |index| index + 1),
+    };
+    let mut message = text[..end].trim_end().replace(delimiter, SOH);
+    if !message.ends_with(SOH) {
+        message.push_str(SOH);
+    }
+    (message, end)
+}
+
+fn tokens(prefix: &str) -> impl Iterator<Item = &str> {
+    prefix
+        .split(|c: char| c.is_whitespace() || matches!(c, '[' | ']' | '(' | ')' | ','))
+        .filter(|token| !token.is_empty())
+}
+
+fn find_direction(prefix: &str) -> Option<Direction> {
+    tokens(prefix).find_map(|token| {
+        let token = token.trim_end_matches(':').to_ascii_lowercase();
+        match token.as_str() {
+            "incoming" | "in" | "received" | "recv" | "<" | "<<" | "<-" => {
+                Some(Direction::Incoming)
+            }
+            "outgoing" | "out" | "sent" | "send" | ">" | ">>" | "->" => Some(Direction::Outgoing),
+            _ if token.ends_with(".in") => Some(Direction::Incoming),
+            _ if token.ends_with(".out") => Some(Direction::Outgoing),
+            _ => None,
+        }
+    })
+}
+
+/// The first timestamp in `prefix` as UTCTimestamp, RFC 3339 or `%Y-%m-%d %H:%M:%S`.
+fn find_timestamp(prefix: &str) -> Option<NaiveDateTime> {
+    let tokens: Vec<&str> = tokens(prefix).collect();
+    tokens.iter().enumerate().find_map(|(index, token)| {
+        NaiveDateTime::parse_from_str(token, UTC_TIMESTAMP_FORMAT)
+            .ok()
+            .or_else(|| {
+                DateTime::parse_from_rfc3339(token)
+                    .ok()
+                    .map(|timestamp| timestamp.naive_utc())
+            })
+            .or_else(|| NaiveDateTime::parse_from_str(token, "%Y-%m-%dT%H:%M:%S%.f").ok())
+            .or_else(|| {
+                let time = tokens.get(index + 1)?;
+                NaiveDateTime::parse_from_str(&format!("{token} {time}"), "%Y-%m-%d %H:%M:%S%.f")
+                    .ok()
+            })
+    })
+}
+
+/// Writes `message`, SOH delimited, one `Name (tag) = value [DESCRIPTION]` field per line. The
+/// entries of a repeating group follow its NumInGroup field indented, each starting with `- `.
+/// Fields unknown to `data_dictionary` are written as `tag = value`.
+#[must_use]
+pub fn pretty_print(message: &str, data_dictionary: &DataDictionary) -> String {
+    let msg_type = fields(message)
+        .find(|(tag, _)| *tag == tags::MsgType)
+        .map(|(_, value)| value);
+    let body = msg_type.and_then(|msg_type| data_dictionary.get_map_for_message(msg_type));
+
+    let mut text = String::new();
+    let mut groups: Vec<(Tag, &DDMap)> = Vec::new();
+    for (tag, value) in fields(message) {
+        while let Some((_, group)) = groups.last() {
+            if group.is_field(tag) || group.is_group(tag) {
+                break;
+            }
+            groups.pop();
+        }
+
+        let indent = "  ".repeat(groups.len());
+        match groups.last() {
+            Some((delimiter, _)) if *delimiter == tag => {
+                let _ = write!(text, "{}- ", &indent[2..]);
+            }
+            _ => text.push_str(&indent),
+        }
+        match data_dictionary.get_field_by_tag(tag) {
+            Some(field) => {
+                let _ = write!(text, "{} ({tag}) = {value}", field.name());
+                let description = if field.is_multiple_value_field_with_enums() {
+                    value
+                        .split(' ')
+                        .map(|value| field.enums().get(value).map(AsRef::as_ref))
+                        .collect::<Option<Vec<&str>>>()
+                        .map(|descriptions| descriptions.join(" "))
+                } else {
+                    field.enums().get(value).map(ToString::to_string)
+                };
+                if let Some(description) = description {
+                    let _ = write!(text, " [{description}]");
+                }
+            }
+            None => {
+                let _ = write!(text, "{tag} = {value}");
+            }
+        }
+        text.push('\n');
+
+        let map = match groups.last() {
+            Some((_, group)) => Some(*group),
+            None if data_dictionary.header().is_group(tag) => Some(data_dictionary.header()),
+            None if data_dictionary.trailer().is_group(tag) => Some(data_dictionary.trailer()),
+            None => body,
+        };
+        if let Some(group) = map.and_then(|map| map.get_group(tag)) {
+            groups.push((group.delim(), group));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{pretty_print, Direction, LogParser};
+    use crate::data_dictionary::DataDictionary;
+    use crate::tags;
+
+    #[test]
+    pub fn parse() {
+        let log = "\
+20240229-12:00:00.123 : 8=FIX.4.4\x019=5\x0135=0\x0110=161\x01
+[INCOMING] FIX.4.4:TARGET->SENDER 8=FIX.4.4|9=60|35=D|58=8=FIX in Text|10=012|
+[2024-02-29T12:00:01Z INFO  FIX.4.4-SENDER-TARGET.out] 8=FIX.4.4^A9=5^A35=1^A10=000^A
+8=FIX.4.4|9=40|35=0|49=SENDER|56=TARGET|52=20240229-12:00:02|10=001|8=FIX.4.4|9=40|35=0|49=TARGET|56=SENDER|10=002|
+garbage 8=FIX.4.4|9=100|35=8|49=TARGET|56=SEND";
+        let entries = LogParser::new()
+            .with_local_comp_id("SENDER")
+            .parse(log.as_bytes());
+        assert_eq!(entries.len(), 6);
+
+        let day = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(entries[0].timestamp(), day.and_hms_milli_opt(12, 0, 0, 123));
+        assert_eq!(entries[0].direction(), None);
+        assert_eq!(
+            entries[0].message(),
+            "8=FIX.4.4\x019=5\x0135=0\x0110=161\x01"
+        );
+
+        assert_eq!(entries[1].direction(), Some(Direction::Incoming));
+        assert_eq!(entries[1].field(tags::Text), Some("8=FIX in Text"));
+        assert_eq!(entries[1].timestamp(), None);
+
+        assert_eq!(entries[2].line(), 3);
+        assert_eq!(entries[2].direction(), Some(Direction::Outgoing));
+        assert_eq!(entries[2].timestamp(), day.and_hms_opt(12, 0, 1));
+        assert_eq!(
+            entries[2].message(),
+            "8=FIX.4.4\x019=5\x0135=1\x0110=000\x01"
+        );
+
+        assert_eq!(entries[3].direction(), Some(Direction::Outgoing));
+        assert_eq!(entries[3].timestamp(), day.and_hms_opt(12, 0, 2));
+        assert_eq!(entries[4].direction(), Some(Direction::Incoming));
+        assert_eq!(entries[4].line(), 4);
+
+        assert_eq!(entries[5].msg_type(), Some("8"));
+        assert_eq!(entries[5].field(tags::TargetCompID), Some("SEND"));
+    }
+
+    #[test]
+    pub fn pretty() {
+        let dd = DataDictionary::load_from_string(include_str!("../../../spec/FIX44.xml")).unwrap();
+        let message = "8=FIX.4.4|9=100|35=D|49=SENDER|56=TARGET|627=1|628=HOP|11=1|\
+            453=2|448=BROKER|447=D|802=1|523=DESK|803=4|448=CLIENT|452=3|\
+            18=1 G|54=1|9999=X|10=000|"
+            .replace('|', "\x01");
+        assert_eq!(
+            pretty_print(&message, &dd),
+            "\
+BeginString (8) = FIX.4.4
+BodyLength (9) = 100
+MsgType (35) = D [ORDER_SINGLE]
+SenderCompID (49) = SENDER
+TargetCompID (56) = TARGET
+NoHops (627) = 1
+- HopCompID (628) = HOP
+ClOrdID (11) = 1
+NoPartyIDs (453) = 2
+- PartyID (448) = BROKER
+  PartyIDSource (447) = D [PROPRIETARY_CUSTOM_CODE]
+  NoPartySubIDs (802) = 1
+  - PartySubID (523) = DESK
+    PartySubIDType (803) = 4
+- PartyID (448) = CLIENT
+  PartyRole (452) = 3 [CLIENT_ID]
+ExecInst (18) = 1 G [NOT_HELD ALL_OR_NONE]
+Side (54) = 1 [BUY]
+9999 = X
+CheckSum (10) = 000
+"
+        );
+    }
+}
@@ -3,6 +3,8 @@ use crate::data_dictionary::DDMap;
 use crate::data_dictionary::DataDictionary;
 use crate::data_dictionary::MessageValidationError;
 use crate::data_dictionary::TagException;
+use crate::encoding;
+use crate::encoding::MessageEncoding;
 use crate::field_map::FieldBase;
 use crate::field_map::FieldMap;
 use crate::field_map::FieldMapError;
@@ -302,6 +304,7 @@ impl Message {
                     size_hint = f.to_usize();
                 }
                 (_, Some(app_dd)) if app_dd.is_length_field(f.tag()) => size_hint = f.to_usize(),
+                _ if encoding::is_encoded_length_tag(f.tag()) => size_hint = f.to_usize(),
                 _ => size_hint = None,
             };
 
@@ -565,6 +568,37 @@ impl Message {
         )
     }
 
+    /// The MessageEncoding (347) of the message, `None` when it is not set.
+    pub fn encoding(&self) -> Result<Option<MessageEncoding>, FieldMapError> {
+        if !self.header.is_field_set(tags::MessageEncoding) {
+            return Ok(None);
+        }
+        let label = self.header.get_string(tags::MessageEncoding)?;
+        match MessageEncoding::for_label(&label) {
+            Some(encoding) => Ok(Some(encoding)),
+            None => Err(ConversionError::EncodingError.into()),
+        }
+    }
+
+    pub fn set_encoding(&mut self, encoding: MessageEncoding) {
+        self.header
+            .set_tag_value(tags::MessageEncoding, encoding.name());
+    }
+
+    /// Decodes the Encoded* field `tag` with the MessageEncoding of the message, UTF-8 when it
+    /// is not set.
+    pub fn get_encoded(&self, tag: Tag) -> Result<String, FieldMapError> {
+        let encoding = self.encoding()?.unwrap_or_default();
+        self.body.get_encoded_string(tag, encoding)
+    }
+
+    /// Encodes the Encoded* field `tag` with the MessageEncoding of the message, UTF-8 when it
+    /// is not set, and sets its length field in bytes.
+    pub fn set_encoded(&mut self, tag: Tag, value: &str) -> Result<(), FieldMapError> {
+        let encoding = self.encoding()?.unwrap_or_default();
+        self.body.set_encoded_string(tag, value, encoding)
+    }
+
     #[must_use]
     pub fn is_admin(&self) -> bool {
         matches!(self.header.get_field(tags::MsgType), Some(field) if Message::is_admin_msg_type(field.value()))
@@ -0,0 +1,603 @@
+//! [FIX Orchestra](https://www.fixtrading.org/standards/fix-orchestra/) repositories as a
+//! [`DataDictionary`] source.
+//!
+//! [`DataDictionary::load_from_string`] and [`DataDictionary::from_file`] accept an Orchestra
+//! repository (root element `repository`) as well as a QuickFIX style dictionary. The
+//! repository is translated to the QuickFIX layout:
+//!
+//! - A field typed with a codeSet takes the type of the codeSet, its codes become the enum
+//!   values with the code name as description.
+//! - Datatype names map to the QuickFIX types, e.g. `Qty` to `QTY`. Unknown datatypes are read
+//!   as `STRING`.
+//! - The StandardHeader and StandardTrailer components become the header and trailer, groups
+//!   are named after their NumInGroup field and `presence="required"` makes an entry required.
+//! - Only the base scenario of a message is used.
+//!
+//! Conditional presence rules (`<fixr:rule presence="required"><fixr:when>...`) are read by
+//! [`validation_rules`]:
+//!
+//! ```
+//! use dfx_base::data_dictionary::DataDictionary;
+//! use dfx_base::message::Message;
+//! use dfx_base::orchestra;
+//! use dfx_base::tags;
+//!
+//! let repository = include_str!("../../../spec/orchestra/sample.xml");
+//! let dd = DataDictionary::load_from_string(repository).unwrap();
+//! let rules = orchestra::validation_rules(repository, &dd).unwrap();
+//!
+//! let mut order = Message::default();
+//! order.set_tag_value(tags::OrdType, "2");
+//! assert!(rules.validate(&order, "D").is_err());
+//! order.set_tag_value(tags::Price, "10.5");
+//! assert!(rules.validate(&order, "D").is_ok());
+//! ```
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use xmltree::{Element, XMLNode};
+
+use crate::data_dictionary::{DataDictionary, DataDictionaryError};
+use crate::fields::types::FieldType;
+use crate::validation_rules::{ValidationRule, ValidationRuleError, ValidationRules};
+
+const STANDARD_HEADER: &str = "StandardHeader";
+const STANDARD_TRAILER: &str = "StandardTrailer";
+
+/// Whether `doc` is the root element of an Orchestra repository.
+pub(crate) fn is_repository(doc: &Element) -> bool {
+    doc.name == "repository"
+}
+
+/// Translates an Orchestra repository to a QuickFIX style `<fix>` element.
+pub(crate) fn to_quickfix(doc: &Element) -> Result<Element, DataDictionaryError> {
+    let repository = Repository::new(doc)?;
+
+    let mut fix = Element::new("fix");
+    let (version_type, major, minor, service_pack) = version(doc)?;
+    fix.attributes.insert("type".into(), version_type);
+    fix.attributes.insert("major".into(), major);
+    fix.attributes.insert("minor".into(), minor);
+    if let Some(service_pack) = service_pack {
+        fix.attributes.insert("servicepack".into(), service_pack);
+    }
+
+    let mut header = Element::new("header");
+    let mut trailer = Element::new("trailer");
+    let mut components = Element::new("components");
+    for component in section(doc, "components", "component") {
+        let name = attribute(component, "name")?;
+        let members = repository.members(component, &located(component))?;
+        match name {
+            STANDARD_HEADER => header.children = members,
+            STANDARD_TRAILER => trailer.children = members,
+            name => {
+                let mut element = Element::new("component");
+                element.attributes.insert("name".into(), name.into());
+                element.children = members;
+                components.children.push(XMLNode::Element(element));
+            }
+        }
+    }
+
+    let mut messages = Element::new("messages");
+    for message in section(doc, "messages", "message") {
+        let mut element = Element::new("message");
+        element
+            .attributes
+            .insert("name".into(), attribute(message, "name")?.into());
+        element
+            .attributes
+            .insert("msgtype".into(), attribute(message, "msgType")?.into());
+        let category = match message.attributes.get("category").map(String::as_str) {
+            Some("Session") => "admin",
+            _ => "app",
+        };
+        element.attributes.insert("msgcat".into(), category.into());
+        if let Some(structure) = child(message, "structure") {
+            element.children = repository.members(structure, &located(message))?;
+        }
+        messages.children.push(XMLNode::Element(element));
+    }
+
+    let mut fields = Element::new("fields");
+    for field in section(doc, "fields", "field") {
+        fields
+            .children
+            .push(XMLNode::Element(repository.field(field)?));
+    }
+
+    fix.children = [header, trailer, messages, components, fields]
+        .into_iter()
+        .map(XMLNode::Element)
+        .collect();
+    Ok(fix)
+}
+
+/// Reads the conditional presence rules of the messages in an Orchestra repository, e.g.
+/// `<fixr:rule presence="required"><fixr:when>OrdType == ^Limit</fixr:when></fixr:rule>`
+/// becomes `D: Price required when OrdType == '2'`.
+///
+/// Rules of fields within components are included, rules within repeating groups are not. A
+/// `^Code` refers to a code of the field compared with, `&&`, `||` and `!` are read as `and`,
+/// `or` and `not`.
+pub fn validation_rules(
+    contents: &str,
+    data_dictionary: &DataDictionary,
+) -> Result<ValidationRules, ValidationRuleError> {
+    let parse_error = |message: String| ValidationRuleError::Parse { line: 0, message };
+    let doc = Element::parse(contents.as_bytes()).map_err(|e| parse_error(e.to_string()))?;
+    if !is_repository(&doc) {
+        return Err(parse_error("not an Orchestra repository".into()));
+    }
+    let repository = Repository::new(&doc).map_err(|e| parse_error(e.to_string()))?;
+
+    let mut rules = ValidationRules::new();
+    for message in section(&doc, "messages", "message") {
+        let (Ok(msg_type), Some(structure)) =
+            (attribute(message, "msgType"), child(message, "structure"))
+        else {
+            continue;
+        };
+        repository.presence_rules(
+            structure,
+            &located(message),
+            msg_type,
+            data_dictionary,
+            &mut rules,
+        )?;
+    }
+    Ok(rules)
+}
+
+struct Repository<'a> {
+    code_sets: HashMap<&'a str, &'a Element>,
+    fields: HashMap<&'a str, &'a Element>,
+    components: HashMap<&'a str, &'a Element>,
+    groups: HashMap<&'a str, &'a Element>,
+}
+
+impl<'a> Repository<'a> {
+    fn new(doc: &'a Element) -> Result<Self, DataDictionaryError> {
+        let by = |section_name, element_name, key| {
+            section(doc, section_name, element_name)
+                .map(|element| Ok((attribute(element, key)?, element)))
+                .collect::<Result<HashMap<_, _>, DataDictionaryError>>()
+        };
+        Ok(Repository {
+            code_sets: by("codeSets", "codeSet", "name")?,
+            fields: by("fields", "field", "id")?,
+            components: by("components", "component", "id")?,
+            groups: by("groups", "group", "id")?,
+        })
+    }
+
+    /// The entry `reference` at `path` refers to by id.
+    fn get(
+        entries: &HashMap<&'a str, &'a Element>,
+        entry_type: &str,
+        reference: &Element,
+        path: &str,
+    ) -> Result<&'a Element, DataDictionaryError> {
+        let id = attribute(reference, "id")?;
+        entries
+            .get(id)
+            .copied()
+            .ok_or_else(|| DataDictionaryError::Missing {
+                path: format!("{path}/{}", located(reference)).into(),
+                entry_type: entry_type.into(),
+                name: id.into(),
+            })
+    }
+
+    fn field_name(&self, reference: &Element, path: &str) -> Result<&'a str, DataDictionaryError> {
+        attribute(Self::get(&self.fields, "field", reference, path)?, "name")
+    }
+
+    /// The `<field>` of the `<fields>` section.
+    fn field(&self, field: &Element) -> Result<Element, DataDictionaryError> {
+        let mut element = Element::new("field");
+        element
+            .attributes
+            .insert("number".into(), attribute(field, "id")?.into());
+        element
+            .attributes
+            .insert("name".into(), attribute(field, "name")?.into());
+        let datatype = attribute(field, "type")?;
+        let datatype = match self.code_sets.get(datatype) {
+            Some(code_set) => {
+                for code in elements(code_set, "code") {
+                    let mut value = Element::new("value");
+                    value
+                        .attributes
+                        .insert("enum".into(), attribute(code, "value")?.into());
+                    value
+                        .attributes
+                        .insert("description".into(), attribute(code, "name")?.into());
+                    element.children.push(XMLNode::Element(value));
+                }
+                attribute(code_set, "type")?
+            }
+            None => datatype,
+        };
+        element
+            .attributes
+            .insert("type".into(), quickfix_type(datatype));
+        Ok(element)
+    }
+
+    /// The `<field>`, `<component>` and `<group>` entries of a structure, component or group.
+    fn members(&self, node: &Element, path: &str) -> Result<Vec<XMLNode>, DataDictionaryError> {
+        let mut members = Vec::new();
+        for reference in node.children.iter().filter_map(XMLNode::as_element) {
+            let (kind, name, children) = match reference.name.as_str() {
+                "fieldRef" => ("field", self.field_name(reference, path)?, None),
+                "componentRef" => {
+                    let component = Self::get(&self.components, "component", reference, path)?;
+                    match attribute(component, "name")? {
+                        STANDARD_HEADER | STANDARD_TRAILER => continue,
+                        name => ("component", name, None),
+                    }
+                }
+                "groupRef" => {
+                    let group = Self::get(&self.groups, "group", reference, path)?;
+                    let group_path = format!("{path}/{}", located(group));
+                    let num_in_group =
+                        child(group, "numInGroup").ok_or_else(|| DataDictionaryError::Missing {
+                            path: group_path.as_str().into(),
+                            entry_type: "element".into(),
+                            name: "numInGroup".into(),
+                        })?;
+                    let name = self.field_name(num_in_group, &group_path)?;
+                    ("group", name, Some(self.members(group, &group_path)?))
+                }
+                _ => continue,
+            };
+            let mut element = Element::new(kind);
+            element.attributes.insert("name".into(), name.into());
+            let required = is_required(reference);
+            element
+                .attributes
+                .insert("required".into(), if required { "Y" } else { "N" }.into());
+            if let Some(children) = children {
+                element.children = children;
+            }
+            members.push(XMLNode::Element(element));
+        }
+        Ok(members)
+    }
+
+    fn presence_rules(
+        &self,
+        node: &Element,
+        path: &str,
+        msg_type: &str,
+        data_dictionary: &DataDictionary,
+        rules: &mut ValidationRules,
+    ) -> Result<(), ValidationRuleError> {
+        let missing = |e: DataDictionaryError| ValidationRuleError::Parse {
+            line: 0,
+            message: e.to_string(),
+        };
+        for reference in node.children.iter().filter_map(XMLNode::as_element) {
+            match reference.name.as_str() {
+                "fieldRef" => {
+                    let field = self.field_name(reference, path).map_err(missing)?;
+                    for rule in elements(reference, "rule") {
+                        let check = match rule.attributes.get("presence").map(String::as_str) {
+                            Some("required") => "required",
+                            Some("forbidden") => "forbidden",
+                            _ => continue,
+                        };
+                        let Some(when) = child(rule, "when").and_then(|w| w.get_text()) else {
+                            continue;
+                        };
+                        let text = format!(
+                            "{msg_type}: {field} {check} when {}",
+                            translate_score(&when, data_dictionary).map_err(|message| {
+                                ValidationRuleError::Parse { line: 0, message }
+                            })?
+                        );
+                        let rule =
+                            ValidationRule::parse(&text, data_dictionary).map_err(|message| {
+                                ValidationRuleError::Parse {
+                                    line: 0,
+                                    message: format!("{text}: {message}"),
+                                }
+                            })?;
+                        rules.add(rule);
+                    }
+                }
+                "componentRef" => {
+                    let component = Self::get(&self.components, "component", reference, path)
+                        .map_err(missing)?;
+                    let path = format!("{path}/{}", located(component));
+                    self.presence_rules(component, &path, msg_type, data_dictionary, rules)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Translates a Score expression to a condition of a [`ValidationRule`].
+fn translate_score(expression: &str, data_dictionary: &DataDictionary) -> Result<String, String> {
+    let mut condition = String::new();
+    // The last field of the expression, the field a `^Code` belongs to
+    let mut field = None;
+    let mut chars = expression.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            '&' | '|' => {
+                chars.next();
+                if chars.next_if_eq(&c).is_none() {
+                    return Err(format!("unsupported operator '{c}' in '{expression}'"));
+                }
+                push_keyword(&mut condition, if c == '&' { "and" } else { "or" });
+            }
+            '!' => {
+                chars.next();
+                if chars.next_if_eq(&'=').is_some() {
+                    condition.push_str("!=");
+                } else {
+                    push_keyword(&mut condition, "not");
+                }
+            }
+            '{' | '}' => {
+                chars.next();
+                condition.push(if c == '{' { '(' } else { ')' });
+            }
+            '^' => {
+                chars.next();
+                let code = read_word(&mut chars);
+                let field = field
+                    .as_deref()
+                    .and_then(|name| data_dictionary.get_field_by_name(name))
+                    .ok_or_else(|| format!("no field for code '^{code}' in '{expression}'"))?;
+                let value = field
+                    .enums()
+                    .iter()
+                    .find(|(_, description)| same_code_name(description, &code))
+                    .map(|(value, _)| value)
+                    .ok_or_else(|| format!("unknown code '{code}' of {}", field.name()))?;
+                condition.push_str(&format!("'{value}'"));
+            }
+            '"' | '\'' => {
+                chars.next();
+                condition.push('\'');
+                for v in chars.by_ref() {
+                    if v == c {
+                        break;
+                    }
+                    condition.push(v);
+                }
+                condition.push('\'');
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let word = read_word(&mut chars);
+                if data_dictionary.get_field_by_name(&word).is_some() {
+                    field = Some(word.clone());
+                }
+                condition.push_str(&word);
+            }
+            c => {
+                chars.next();
+                condition.push(c);
+            }
+        }
+    }
+    Ok(condition.trim().to_string())
+}
+
+fn read_word(chars: &mut Peekable<Chars<'_>>) -> String {
+    let mut word = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.') {
+        word.push(c);
+    }
+    word
+}
+
+/// Appends `keyword` separated by spaces, `A&&B` becomes `A and B`.
+fn push_keyword(condition: &mut String, keyword: &str) {
+    if !condition.is_empty() && !condition.ends_with([' ', '(']) {
+        condition.push(' ');
+    }
+    condition.push_str(keyword);
+    condition.push(' ');
+}
+
+/// Code names compared with the descriptions of QuickFIX dictionaries, `StopLimit` is the same
+/// as `STOP_LIMIT`.
+fn same_code_name(description: &str, code: &str) -> bool {
+    let normalize = |name: &str| {
+        name.chars()
+            .filter(|c| *c != '_')
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+    normalize(description) == normalize(code)
+}
+
+/// `FIX.4.4`, `FIXT.1.1`, `FIX.5.0SP2` or `FIX.Latest`, read as FIX 5.0 SP2.
+fn version(doc: &Element) -> Result<(String, String, String, Option<String>), DataDictionaryError> {
+    let version = doc
+        .attributes
+        .get("version")
+        .or_else(|| doc.attributes.get("name"))
+        .ok_or_else(|| DataDictionaryError::Missing {
+            path: doc.name.as_str().into(),
+            entry_type: "attribute".into(),
+            name: "version".into(),
+        })?;
+    let (version_type, number) = version.split_once('.').unwrap_or((version, ""));
+    if version_type != "FIX" && version_type != "FIXT" {
+        return Err(DataDictionaryError::InvalidVersionType {
+            version_type: version_type.into(),
+        });
+    }
+    if number.starts_with("Latest") {
+        return Ok((
+            version_type.into(),
+            "5".into(),
+            "0".into(),
+            Some("2".into()),
+        ));
+    }
+    let (number, service_pack) = match number.split_once("SP") {
+        Some((number, service_pack)) => (number, Some(service_pack.to_string())),
+        None => (number, None),
+    };
+    let (major, minor) = number
+        .split_once('.')
+        .ok_or(DataDictionaryError::InvalidVersionType {
+            version_type: version.as_str().into(),
+        })?;
+    Ok((
+        version_type.into(),
+        major.into(),
+        minor.into(),
+        service_pack,
+    ))
+}
+
+/// The QuickFIX type of an Orchestra datatype.
+fn quickfix_type(datatype: &str) -> String {
+    let field_type = datatype.to_ascii_uppercase();
+    match field_type.as_str() {
+        "RESERVED100PLUS" | "RESERVED1000PLUS" | "RESERVED4000PLUS" => "INT".into(),
+        _ if FieldType::get(&field_type).is_ok() => field_type,
+        _ => "STRING".into(),
+    }
+}
+
+fn is_required(reference: &Element) -> bool {
+    reference
+        .attributes
+        .get("presence")
+        .is_some_and(|presence| presence == "required")
+}
+
+fn attribute<'e>(element: &'e Element, name: &str) -> Result<&'e str, DataDictionaryError> {
+    element
+        .attributes
+        .get(name)
+        .map(String::as_str)
+        .ok_or_else(|| DataDictionaryError::Missing {
+            path: located(element).into(),
+            entry_type: "attribute".into(),
+            name: name.into(),
+        })
+}
+
+/// `element[name]`, or `element[id]` for references.
+fn located(element: &Element) -> String {
+    let name = element
+        .attributes
+        .get("name")
+        .or_else(|| element.attributes.get("id"))
+        .map(String::as_str)
+        .unwrap_or_default();
+    format!("{}[{name}]", element.name)
+}
+
+fn child<'e>(element: &'e Element, name: &'e str) -> Option<&'e Element> {
+    elements(element, name).next()
+}
+
+/// The child elements named `name` of the base scenario.
+fn elements<'e>(element: &'e Element, name: &'e str) -> impl Iterator<Item = &'e Element> {
+    element
+        .children
+        .iter()
+        .filter_map(XMLNode::as_element)
+        .filter(move |child| child.name == name)
+        .filter(|child| {
+            child
+                .attributes
+                .get("scenario")
+                .is_none_or(|scenario| scenario == "base")
+        })
+}
+
+fn section<'e>(
+    doc: &'e Element,
+    section_name: &'e str,
+    element_name: &'e str,
+) -> impl Iterator<Item = &'e Element> {
+    elements(doc, section_name).flat_map(move |section| elements(section, element_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validation_rules;
+    use crate::data_dictionary::DataDictionary;
+    use crate::message::Message;
+    use crate::tags;
+
+    const SAMPLE: &str = include_str!("../../../spec/orchestra/sample.xml");
+
+    #[test]
+    fn load() {
+        let dd = DataDictionary::load_from_string(SAMPLE).unwrap();
+        assert_eq!(dd.version().unwrap().as_ref(), "FIX.4.4");
+
+        let side = dd.get_field_by_name("Side").unwrap();
+        assert_eq!(side.field_type().as_ref(), "CHAR");
+        assert_eq!(side.enums().get("1").unwrap().as_ref(), "Buy");
+        let exec_inst = dd.get_field_by_name("ExecInst").unwrap();
+        assert!(exec_inst.is_multiple_value_field_with_enums());
+        assert_eq!(
+            dd.get_field_by_name("OrderQty")
+                .unwrap()
+                .field_type()
+                .as_ref(),
+            "QTY"
+        );
+
+        assert!(dd.is_header_field(tags::SenderCompID));
+        assert!(dd.is_trailer_field(tags::CheckSum));
+
+        let order = dd.get_map_for_message("D").unwrap();
+        assert!(order.required_fields().contains(&tags::ClOrdID));
+        assert!(order.required_fields().contains(&tags::Side));
+        assert!(!order.required_fields().contains(&tags::Price));
+        // Instrument is required, its fields are not
+        assert!(order.is_field(tags::Symbol));
+        assert!(!order.required_fields().contains(&tags::Symbol));
+        assert!(!order.is_field(tags::SenderCompID));
+
+        let parties = order.get_group(tags::NoPartyIDs).unwrap();
+        assert_eq!(parties.delim(), tags::PartyID);
+        assert!(parties.is_field(tags::PartyRole));
+
+        let logon = dd.get_map_for_message("A").unwrap();
+        assert!(logon.required_fields().contains(&tags::HeartBtInt));
+    }
+
+    #[test]
+    fn presence_rules() {
+        let dd = DataDictionary::load_from_string(SAMPLE).unwrap();
+        let rules = validation_rules(SAMPLE, &dd).unwrap();
+        assert_eq!(rules.rules().len(), 3);
+        assert_eq!(
+            rules.rules()[0].text(),
+            "D: Price required when OrdType in ('2', '4')"
+        );
+
+        let mut order = Message::default();
+        order.set_tag_value(tags::OrdType, "3");
+        assert!(rules.validate(&order, "D").is_err());
+        order.set_tag_value(tags::StopPx, "9");
+        assert!(rules.validate(&order, "D").is_ok());
+        order.set_tag_value(tags::OrdType, "1");
+        assert!(rules.validate(&order, "D").is_err());
+
+        // Rules of a QuickFIX dictionary match the code names
+        let fix44 =
+            DataDictionary::load_from_string(include_str!("../../../spec/FIX44.xml")).unwrap();
+        let rules = validation_rules(SAMPLE, &fix44).unwrap();
+        assert_eq!(rules.rules().len(), 3);
+    }
+}
@@ -0,0 +1,659 @@
+//! Validation rules evaluated after [`DataDictionary::validate`], for constraints a data
+//! dictionary cannot express, e.g. a field that is only required for some values of another.
+//!
+//! A rules file holds one rule per line, `#` starts a comment:
+//!
+//! ```text
+//! # MsgTypes: check [when condition] [reject session|business]
+//! D,G: Price required when OrdType in (2, 4)
+//! D: StopPx forbidden when OrdType == 1
+//! D: OrderQty > 0
+//! *: 9999 required when exists 9998 reject business
+//! ```
+//!
+//! - MsgTypes is a comma separated list of MsgType values, `*` for all message types.
+//! - A check is `Field required`, `Field forbidden` or a comparison `Field op value`. A comparison
+//!   check only applies when the field is present.
+//! - A condition combines `exists Field`, `Field op value` and `Field in (value, ...)` with
+//!   `and`, `or`, `not` and parentheses. A comparison is false when the field is absent.
+//! - `op` is one of `==`, `!=`, `<`, `<=`, `>` or `>=`. Values are compared as numbers when
+//!   both sides are numbers, as strings otherwise. Quote values containing spaces: `'A B'`.
+//! - Fields are names from the [`DataDictionary`] or tag numbers.
+//!
+//! An Orchestra repository is read as a rules file too, see [`orchestra::validation_rules`].
+//!
+//! A violated rule rejects the message with a session level Reject, or with a
+//! BusinessMessageReject for `reject business`.
+//!
+//! ```
+//! use dfx_base::data_dictionary::DataDictionary;
+//! use dfx_base::message::Message;
+//! use dfx_base::tags;
+//! use dfx_base::validation_rules::ValidationRules;
+//!
+//! let dd = DataDictionary::load_from_string(include_str!("../../../spec/FIX44.xml")).unwrap();
+//! let rules = ValidationRules::load_from_string("D: Price required when OrdType == 2", &dd).unwrap();
+//!
+//! let mut order = Message::default();
+//! order.set_tag_value(tags::OrdType, "2");
+//! assert!(rules.validate(&order, "D").is_err());
+//! order.set_tag_value(tags::Price, "10.5");
+//! assert!(rules.validate(&order, "D").is_ok());
+//! ```
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+use crate::data_dictionary::{DataDictionary, MessageValidationError, TagException};
+use crate::field_map::Tag;
+use crate::fix_values::BusinessRejectReason;
+use crate::message::Message;
+use crate::orchestra;
+
+/// The rules of a session, see the [module documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct ValidationRules {
+    rules: Vec<ValidationRule>,
+}
+
+/// A single rule, see the [module documentation](self).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationRule {
+    text: String,
+    msg_types: Vec<String>,
+    check: Check,
+    when: Option<Condition>,
+    reject: RuleReject,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Check {
+    Required(Tag),
+    Forbidden(Tag),
+    Value(Comparison),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    Exists(Tag),
+    Compare(Comparison),
+    Not(Box<Condition>),
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub tag: Tag,
+    pub operator: Operator,
+    /// A single value, or the values of [`Operator::In`].
+    pub values: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+}
+
+/// How a message violating a rule is rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RuleReject {
+    /// A session level Reject (35=3).
+    #[default]
+    Session,
+    /// A BusinessMessageReject (35=j), or a Reject before FIX 4.2.
+    Business,
+}
+
+#[derive(Debug)]
+pub enum ValidationRuleError {
+    IoError(std::io::Error),
+    /// Rule on `line` (starting at 1) is invalid.
+    Parse {
+        line: usize,
+        message: String,
+    },
+}
+
+impl Display for ValidationRuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationRuleError::IoError(e) => write!(f, "{e}"),
+            ValidationRuleError::Parse { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for ValidationRuleError {
+    fn from(error: std::io::Error) -> Self {
+        Self::IoError(error)
+    }
+}
+
+impl ValidationRules {
+    #[must_use]
+    pub fn new() -> Self {
+        ValidationRules::default()
+    }
+
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        data_dictionary: &DataDictionary,
+    ) -> Result<Self, ValidationRuleError> {
+        let contents = fs::read_to_string(path)?;
+        ValidationRules::load_from_string(&contents, data_dictionary)
+    }
+
+    pub fn load_from_string(
+        contents: &str,
+        data_dictionary: &DataDictionary,
+    ) -> Result<Self, ValidationRuleError> {
+        if contents.trim_start().starts_with('<') {
+            return orchestra::validation_rules(contents, data_dictionary);
+        }
+        let mut rules = ValidationRules::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let rule = ValidationRule::parse(line, data_dictionary).map_err(|message| {
+                ValidationRuleError::Parse {
+                    line: index + 1,
+                    message,
+                }
+            })?;
+            rules.add(rule);
+        }
+        Ok(rules)
+    }
+
+    pub fn add(&mut self, rule: ValidationRule) {
+        self.rules.push(rule);
+    }
+
+    #[must_use]
+    pub fn rules(&self) -> &[ValidationRule] {
+        &self.rules
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Evaluates the rules for `msg_type` in order, returns the first violation.
+    pub fn validate(
+        &self,
+        message: &Message,
+        msg_type: &str,
+    ) -> Result<(), MessageValidationError> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.applies_to(msg_type))
+            .try_for_each(|rule| rule.validate(message, msg_type))
+    }
+
+    /// Evaluates the rules for `msg_type`, returns every violation.
+    #[must_use]
+    pub fn validate_all(&self, message: &Message, msg_type: &str) -> Vec<MessageValidationError> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.applies_to(msg_type))
+            .filter_map(|rule| rule.validate(message, msg_type).err())
+            .collect()
+    }
+}
+
+impl ValidationRule {
+    /// Parses a single line of a rules file.
+    pub fn parse(rule: &str, data_dictionary: &DataDictionary) -> Result<Self, String> {
+        let (msg_types, body) = rule
+            .split_once(':')
+            .ok_or_else(|| "expected 'MsgTypes: rule'".to_string())?;
+        let msg_types: Vec<String> = match msg_types.trim() {
+            "*" => Vec::new(),
+            msg_types => msg_types.split(',').map(|m| m.trim().to_string()).collect(),
+        };
+        if msg_types.iter().any(String::is_empty) {
+            return Err("empty MsgType".into());
+        }
+
+        let mut parser = RuleParser {
+            tokens: tokenize(body)?,
+            position: 0,
+            data_dictionary,
+        };
+        let check = parser.check()?;
+        let when = if parser.accept("when") {
+            Some(parser.condition()?)
+        } else {
+            None
+        };
+        let reject = if parser.accept("reject") {
+            match parser.next().as_deref() {
+                Some("session") => RuleReject::Session,
+                Some("business") => RuleReject::Business,
+                other => return Err(format!("expected 'session' or 'business', found {other:?}")),
+            }
+        } else {
+            RuleReject::Session
+        };
+        if let Some(token) = parser.next() {
+            return Err(format!("unexpected '{token}'"));
+        }
+
+        Ok(ValidationRule {
+            text: rule.trim().to_string(),
+            msg_types,
+            check,
+            when,
+            reject,
+        })
+    }
+
+    /// The rule as written.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The MsgTypes the rule applies to, empty for all.
+    #[must_use]
+    pub fn msg_types(&self) -> &[String] {
+        &self.msg_types
+    }
+
+    #[must_use]
+    pub fn check(&self) -> &Check {
+        &self.check
+    }
+
+    #[must_use]
+    pub fn when(&self) -> Option<&Condition> {
+        self.when.as_ref()
+    }
+
+    #[must_use]
+    pub fn reject(&self) -> RuleReject {
+        self.reject
+    }
+
+    #[must_use]
+    pub fn applies_to(&self, msg_type: &str) -> bool {
+        self.msg_types.is_empty() || self.msg_types.iter().any(|m| m == msg_type)
+    }
+
+    pub fn validate(
+        &self,
+        message: &Message,
+        msg_type: &str,
+    ) -> Result<(), MessageValidationError> {
+        if matches!(&self.when, Some(condition) if !condition.evaluate(message)) {
+            return Ok(());
+        }
+        let (tag, exception) = match &self.check {
+            Check::Required(tag) if field_value(message, *tag).is_none() => {
+                (*tag, TagException::required_tag_missing(*tag))
+            }
+            Check::Forbidden(tag) if field_value(message, *tag).is_some() => (
+                *tag,
+                TagException::other(format!("Tag {tag} not allowed: {}", self.text), *tag),
+            ),
+            Check::Value(comparison)
+                if field_value(message, comparison.tag).is_some()
+                    && !comparison.evaluate(message) =>
+            {
+                (
+                    comparison.tag,
+                    TagException::incorrect_tag_value(comparison.tag),
+                )
+            }
+            _ => return Ok(()),
+        };
+        let inner = format!("Validation rule failed for {msg_type}: {}", self.text);
+        Err(match self.reject {
+            RuleReject::Session => {
+                MessageValidationError::TagException(exception.with_inner(inner))
+            }
+            RuleReject::Business => {
+                let reason = match self.check {
+                    Check::Required(_) => {
+                        BusinessRejectReason::CONDITIONALLY_REQUIRED_FIELD_MISSING()
+                    }
+                    _ => BusinessRejectReason::OTHER(inner),
+                };
+                MessageValidationError::BusinessReject { reason, tag }
+            }
+        })
+    }
+}
+
+impl Condition {
+    #[must_use]
+    pub fn evaluate(&self, message: &Message) -> bool {
+        match self {
+            Condition::Exists(tag) => field_value(message, *tag).is_some(),
+            Condition::Compare(comparison) => comparison.evaluate(message),
+            Condition::Not(condition) => !condition.evaluate(message),
+            Condition::And(conditions) => conditions.iter().all(|c| c.evaluate(message)),
+            Condition::Or(conditions) => conditions.iter().any(|c| c.evaluate(message)),
+        }
+    }
+}
+
+impl Comparison {
+    /// False when the field is absent.
+    #[must_use]
+    pub fn evaluate(&self, message: &Message) -> bool {
+        let Some(value) = field_value(message, self.tag) else {
+            return false;
+        };
+        let compare = |expected: &String| compare_values(&value, expected);
+        match self.operator {
+            Operator::Eq => self.values.iter().any(|v| compare(v) == Ordering::Equal),
+            Operator::Ne => self.values.iter().all(|v| compare(v) != Ordering::Equal),
+            Operator::Lt => self.values.iter().all(|v| compare(v) == Ordering::Less),
+            Operator::Le => self.values.iter().all(|v| compare(v) != Ordering::Greater),
+            Operator::Gt => self.values.iter().all(|v| compare(v) == Ordering::Greater),
+            Operator::Ge => self.values.iter().all(|v| compare(v) != Ordering::Less),
+            Operator::In => self.values.iter().any(|v| compare(v) == Ordering::Equal),
+        }
+    }
+}
+
+/// The value of `tag` in the body or the header of `message`.
+fn field_value(message: &Message, tag: Tag) -> Option<String> {
+    message
+        .get_string(tag)
+        .or_else(|_| message.header().get_string(tag))
+        .ok()
+}
+
+fn compare_values(actual: &str, expected: &str) -> Ordering {
+    match (actual.parse::<f64>(), expected.parse::<f64>()) {
+        (Ok(actual), Ok(expected)) => actual.partial_cmp(&expected).unwrap_or(Ordering::Equal),
+        _ => actual.cmp(expected),
+    }
+}
+
+fn tokenize(rule: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = rule.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' | ')' | ',' => tokens.push(c.to_string()),
+            '=' | '!' | '<' | '>' => {
+                let mut operator = c.to_string();
+                if chars.peek() == Some(&'=') {
+                    operator.push('=');
+                    chars.next();
+                }
+                tokens.push(operator);
+            }
+            '\'' | '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some(v) => value.push(v),
+                        None => return Err(format!("unterminated {c}")),
+                    }
+                }
+                // Quoted values keep their quote so they are never read as a keyword
+                tokens.push(format!("'{value}"));
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "()=!<>,".contains(next) {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                tokens.push(word);
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct RuleParser<'a> {
+    tokens: Vec<String>,
+    position: usize,
+    data_dictionary: &'a DataDictionary,
+}
+
+impl RuleParser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.position).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn accept(&mut self, token: &str) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.accept(token) {
+            Ok(())
+        } else {
+            Err(format!("expected '{token}', found {:?}", self.peek()))
+        }
+    }
+
+    fn field(&mut self) -> Result<Tag, String> {
+        let name = self.next().ok_or_else(|| "expected a field".to_string())?;
+        if let Ok(tag @ 1..) = name.parse::<Tag>() {
+            return Ok(tag);
+        }
+        self.data_dictionary
+            .get_field_by_name(&name)
+            .map(|field| field.tag())
+            .ok_or_else(|| format!("unknown field '{name}'"))
+    }
+
+    fn value(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(value) if value.starts_with('\'') => Ok(value[1..].to_string()),
+            Some(value) if !matches!(value.as_str(), "(" | ")" | ",") => Ok(value),
+            other => Err(format!("expected a value, found {other:?}")),
+        }
+    }
+
+    fn check(&mut self) -> Result<Check, String> {
+        let tag = self.field()?;
+        if self.accept("required") {
+            Ok(Check::Required(tag))
+        } else if self.accept("forbidden") {
+            Ok(Check::Forbidden(tag))
+        } else {
+            Ok(Check::Value(self.comparison(tag)?))
+        }
+    }
+
+    fn comparison(&mut self, tag: Tag) -> Result<Comparison, String> {
+        let operator = match self.next().as_deref() {
+            Some("==" | "=") => Operator::Eq,
+            Some("!=") => Operator::Ne,
+            Some("<") => Operator::Lt,
+            Some("<=") => Operator::Le,
+            Some(">") => Operator::Gt,
+            Some(">=") => Operator::Ge,
+            Some("in") => Operator::In,
+            other => return Err(format!("expected an operator, found {other:?}")),
+        };
+        let values = if operator == Operator::In {
+            self.expect("(")?;
+            let mut values = vec![self.value()?];
+            while self.accept(",") {
+                values.push(self.value()?);
+            }
+            self.expect(")")?;
+            values
+        } else {
+            vec![self.value()?]
+        };
+        Ok(Comparison {
+            tag,
+            operator,
+            values,
+        })
+    }
+
+    fn condition(&mut self) -> Result<Condition, String> {
+        let mut conditions = vec![self.and_condition()?];
+        while self.accept("or") {
+            conditions.push(self.and_condition()?);
+        }
+        Ok(if conditions.len() == 1 {
+            conditions.remove(0)
+        } else {
+            Condition::Or(conditions)
+        })
+    }
+
+    fn and_condition(&mut self) -> Result<Condition, String> {
+        let mut conditions = vec![self.unary_condition()?];
+        while self.accept("and") {
+            conditions.push(self.unary_condition()?);
+        }
+        Ok(if conditions.len() == 1 {
+            conditions.remove(0)
+        } else {
+            Condition::And(conditions)
+        })
+    }
+
+    fn unary_condition(&mut self) -> Result<Condition, String> {
+        if self.accept("not") {
+            Ok(Condition::Not(Box::new(self.unary_condition()?)))
+        } else if self.accept("(") {
+            let condition = self.condition()?;
+            self.expect(")")?;
+            Ok(condition)
+        } else if self.accept("exists") {
+            Ok(Condition::Exists(self.field()?))
+        } else {
+            let tag = self.field()?;
+            Ok(Condition::Compare(self.comparison(tag)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Check, Condition, RuleReject, ValidationRule, ValidationRules};
+    use crate::data_dictionary::{DataDictionary, MessageValidationError};
+    use crate::message::Message;
+    use crate::tags;
+
+    fn fix44() -> DataDictionary {
+        DataDictionary::load_from_string(include_str!("../../../spec/FIX44.xml")).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let dd = fix44();
+        let rule = ValidationRule::parse(
+            "D,G: StopPx required when OrdType in (3, 4) and not exists 9999 reject business",
+            &dd,
+        )
+        .unwrap();
+        assert_eq!(rule.msg_types(), ["D", "G"]);
+        assert_eq!(rule.check(), &Check::Required(tags::StopPx));
+        assert!(matches!(rule.when(), Some(Condition::And(c)) if c.len() == 2));
+        assert_eq!(rule.reject(), RuleReject::Business);
+
+        for invalid in [
+            "Price required",
+            "D: Prize required",
+            "D: Price required when",
+            "D: OrderQty >",
+            "D: Price required when OrdType in (1, 2",
+            "D: Price required reject later",
+        ] {
+            assert!(ValidationRule::parse(invalid, &dd).is_err(), "{invalid}");
+        }
+
+        let rules = ValidationRules::load_from_string("# orders\n\nD: Price required\nD: x", &dd);
+        assert!(matches!(
+            rules,
+            Err(super::ValidationRuleError::Parse { line: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn validate() {
+        let rules = ValidationRules::load_from_string(
+            "D: Price required when OrdType == 2 or OrdType == 4\n\
+             D: StopPx forbidden when OrdType in (1, 2)\n\
+             D: OrderQty > 0\n\
+             *: Text required when SenderCompID == 'SENDER A' reject business",
+            &fix44(),
+        )
+        .unwrap();
+
+        let mut order = Message::default();
+        order.set_tag_value(tags::OrdType, "1");
+        assert!(rules.validate(&order, "D").is_ok());
+
+        order.set_tag_value(tags::OrdType, "2");
+        assert!(matches!(
+            rules.validate(&order, "D"),
+            Err(MessageValidationError::TagException(e)) if e.field() == tags::Price
+        ));
+        assert!(rules.validate(&order, "G").is_ok());
+
+        order.set_tag_value(tags::Price, "10");
+        order.set_tag_value(tags::StopPx, "9");
+        assert!(matches!(
+            rules.validate(&order, "D"),
+            Err(MessageValidationError::TagException(e)) if e.field() == tags::StopPx
+        ));
+
+        order.remove_field(tags::StopPx);
+        order.set_tag_value(tags::OrderQty, "0");
+        assert!(matches!(
+            rules.validate(&order, "D"),
+            Err(MessageValidationError::TagException(e)) if e.field() == tags::OrderQty
+        ));
+
+        order.set_tag_value(tags::OrderQty, "100.5");
+        order
+            .header_mut()
+            .set_tag_value(tags::SenderCompID, "SENDER A");
+        assert!(matches!(
+            rules.validate(&order, "D"),
+            Err(MessageValidationError::BusinessReject { reason, tag })
+                if tag == tags::Text && reason.index() == 5
+        ));
+
+        order.remove_field(tags::Price);
+        let errors = rules.validate_all(&order, "D");
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            &errors[0],
+            MessageValidationError::TagException(e) if e.field() == tags::Price
+        ));
+    }
+}
@@ -0,0 +1,56 @@
+//! Compares two data dictionaries, QuickFIX XML or Orchestra repositories.
+//!
+//! ```text
+//! dfx-dictionary-diff [--json] <from> <to>
+//! ```
+//!
+//! Exits with 0 when the dictionaries are the same, 1 when they differ and 2 on errors.
+use std::process::ExitCode;
+
+use dfx_base::data_dictionary::DataDictionary;
+use dfx_base::data_dictionary_diff::diff;
+
+const USAGE: &str = "usage: dfx-dictionary-diff [--json] <from> <to>";
+
+fn load(path: &str) -> Result<DataDictionary, String> {
+    DataDictionary::from_file(path).map_err(|e| e.to_string())
+}
+
+fn main() -> ExitCode {
+    let mut json = false;
+    let mut paths = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ => paths.push(arg),
+        }
+    }
+    let [from, to] = paths.as_slice() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let (from, to) = match (load(from), load(to)) {
+        (Ok(from), Ok(to)) => (from, to),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let diff = diff(&from, &to);
+    if json {
+        println!("{}", diff.to_json());
+    } else {
+        print!("{diff}");
+    }
+    if diff.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
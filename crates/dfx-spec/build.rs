@@ -8,27 +8,21 @@ fn field_type(field_type: &std::sync::Arc<str>) -> &'static str {
     match field_type.as_ref() {
         "CHAR" => "char",
         "INT" => "i64",
-        "LENGTH" | "NUMINGROUP" | "SEQNUM" => "usize",
+        "LENGTH" | "NUMINGROUP" | "SEQNUM" | "TAGNUM" => "usize",
         "AMT" | "PERCENTAGE" | "PRICE" | "QTY" | "PRICEOFFSET" | "FLOAT" => "Decimal",
-        "TZTIMESTAMP" | "UTCTIMESTAMP" | "TIME" => "DateTime",
+        "UTCTIMESTAMP" | "TIME" => "DateTime",
+        "TZTIMESTAMP" => "TZTimestamp",
         "UTCDATE" | "UTCDATEONLY" | "DATE" => "Date",
+        "LOCALMKTDATE" => "LocalMktDate",
         "UTCTIMEONLY" => "Time",
+        "TZTIMEONLY" => "TZTimeOnly",
+        "MONTHYEAR" => "MonthYear",
+        "MULTIPLECHARVALUE" => "MultipleCharValue",
+        "MULTIPLESTRINGVALUE" | "MULTIPLEVALUESTRING" => "MultipleStringValue",
         "BOOLEAN" => "bool",
         //String
-        "COUNTRY"
-        | "CURRENCY"
-        | "DATA"
-        | "DAYOFMONTH"
-        | "EXCHANGE"
-        | "LANGUAGE"
-        | "LOCALMKTDATE"
-        | "MONTHYEAR"
-        | "MULTIPLECHARVALUE"
-        | "MULTIPLESTRINGVALUE"
-        | "MULTIPLEVALUESTRING"
-        | "STRING"
-        | "TZTIMEONLY"
-        | "XMLDATA" => "&str",
+        "COUNTRY" | "CURRENCY" | "DATA" | "DAYOFMONTH" | "EXCHANGE" | "LANGUAGE" | "STRING"
+        | "XMLDATA" | "XID" | "XIDREF" => "&str",
         v => panic!("unknown type {v}"),
     }
 }
@@ -321,5 +315,9 @@ fn main() {
     println!("cargo:rerun-if-changed=crate/dfx-spec/build.rs");
     println!("cargo:rerun-if-changed=spec/");
 
-    codegen("../../spec/FIX44.xml");
+    // A QuickFIX dictionary or an Orchestra repository, the version names the module
+    println!("cargo:rerun-if-env-changed=DFX_SPEC_DICTIONARY");
+    let dictionary =
+        std::env::var("DFX_SPEC_DICTIONARY").unwrap_or_else(|_| "../../spec/FIX44.xml".into());
+    codegen(&dictionary);
 }
@@ -0,0 +1,327 @@
+//! Operator control endpoint, a line protocol over a Unix socket.
+//!
+//! Every request is one line, every response ends with a line starting with `OK` or `ERR`.
+//! Session ids are written as in the logs, e.g. `FIX.4.4:SENDER->TARGET`.
+//!
+//! | Request | Response |
+//! |---------|----------|
+//! | `list` | one status line per connected session |
+//! | `status <session>` | the status line of the session |
+//! | `logon <session>` | enables a session after `logout` |
+//! | `logout <session> [text]` | sends a Logout and disables the session |
+//! | `disconnect <session>` | drops the connection |
+//! | `reset <session>` | resets the message store |
+//! | `set-sender <session> <seq num>` | sets the next sender MsgSeqNum |
+//! | `set-target <session> <seq num>` | sets the next target MsgSeqNum |
+//! | `sequence-reset <session> <new seq no>` | sends a SequenceReset-Reset |
+//! | `resend <session> <begin> <end>` | sends a ResendRequest |
+//!
+//! A status line looks like
+//! `FIX.4.4:SENDER->TARGET enabled=Y logged_on=Y next_sender=12 next_target=34`.
+//!
+//! Only connected sessions are known to the registry, a session that is logged out disconnects
+//! and an initiator reconnects after `ReconnectInterval` as usual.
+//!
+//! ```ignore
+//! let admin = AdminServer::start("/tmp/dfx.sock", log)?;
+//! // echo "list" | nc -U /tmp/dfx.sock
+//! admin.stop();
+//! ```
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::logging::Logger;
+use crate::session::{Session, SessionError, SessionHandle, SessionStatus};
+
+const STATUS_TIMEOUT: Duration = Duration::from_secs(1);
+const SESSION_COMMANDS: [&str; 9] = [
+    "status",
+    "logon",
+    "logout",
+    "disconnect",
+    "reset",
+    "set-sender",
+    "set-target",
+    "sequence-reset",
+    "resend",
+];
+
+/// Serves the control endpoint on its own thread, see the [module documentation](self).
+pub struct AdminServer {
+    path: PathBuf,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl AdminServer {
+    /// Binds the Unix socket at `path` and starts accepting clients, failures are logged to `log`.
+    pub fn start<P: AsRef<Path>, Log: Logger + 'static>(path: P, log: Log) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let listener = UnixListener::bind(&path)?;
+        listener.set_nonblocking(true)?;
+        let running = Arc::new(AtomicBool::new(true));
+        let rt = running.clone();
+        let thread = thread::Builder::new()
+            .name("admin-server-thread".into())
+            .spawn(move || event_loop(&listener, &rt, &log))?;
+        Ok(AdminServer {
+            path,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stops accepting clients and removes the socket file.
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+impl Drop for AdminServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn event_loop(listener: &UnixListener, running: &AtomicBool, log: &dyn Logger) {
+    while running.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _addr)) => {
+                let _ = thread::Builder::new()
+                    .name("admin-client-thread".into())
+                    .spawn(move || serve(stream));
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(10));
+            }
+            Err(e) => log.on_event(&format!("Admin server accept failed: {e}")),
+        }
+    }
+}
+
+fn serve(stream: UnixStream) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match execute(&line) {
+            Ok(lines) => {
+                for line in lines {
+                    writeln!(writer, "{line}")?;
+                }
+                writeln!(writer, "OK")?;
+            }
+            Err(err) => writeln!(writer, "ERR {err}")?,
+        }
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// Executes a single request, returns the lines to write before `OK` or the `ERR` message.
+fn execute(line: &str) -> Result<Vec<String>, String> {
+    let mut args = line.split_whitespace();
+    let command = args.next().unwrap_or_default();
+    if command == "list" {
+        let mut sessions = Session::sessions();
+        sessions.sort_by_key(|session| session.session_id().to_string());
+        return sessions
+            .iter()
+            .map(|session| session.status(STATUS_TIMEOUT).map(|s| status_line(&s)))
+            .collect::<Result<_, _>>()
+            .map_err(error_message);
+    }
+
+    if !SESSION_COMMANDS.contains(&command) {
+        return Err(format!("unknown command '{command}'"));
+    }
+    let session = match args.next() {
+        Some(session_id) => find(session_id)?,
+        None => return Err(format!("{command}: missing session")),
+    };
+    let result = match command {
+        "status" => {
+            return session
+                .status(STATUS_TIMEOUT)
+                .map(|status| vec![status_line(&status)])
+                .map_err(error_message)
+        }
+        "logon" => session.logon(),
+        "logout" => {
+            let text = args.collect::<Vec<&str>>().join(" ");
+            session.logout((!text.is_empty()).then_some(text))
+        }
+        "disconnect" => session.disconnect(),
+        "reset" => session.reset(),
+        "set-sender" => session.set_next_sender_msg_seq_num(seq_num(args.next())?),
+        "set-target" => session.set_next_target_msg_seq_num(seq_num(args.next())?),
+        "sequence-reset" => session.send_sequence_reset(seq_num(args.next())?),
+        "resend" => {
+            let begin = seq_num(args.next())?;
+            let end = seq_num(args.next())?;
+            session.send_resend_request(begin, end)
+        }
+        _ => unreachable!(),
+    };
+    result.map(|()| Vec::new()).map_err(error_message)
+}
+
+fn find(session_id: &str) -> Result<SessionHandle, String> {
+    Session::sessions()
+        .into_iter()
+        .find(|session| session.session_id().to_string() == session_id)
+        .ok_or_else(|| format!("session {session_id} not found"))
+}
+
+fn seq_num(arg: Option<&str>) -> Result<u32, String> {
+    match arg {
+        Some(arg) => arg
+            .parse()
+            .map_err(|_| format!("invalid sequence number '{arg}'")),
+        None => Err("missing sequence number".into()),
+    }
+}
+
+fn status_line(status: &SessionStatus) -> String {
+    let flag = |value: bool| if value { "Y" } else { "N" };
+    format!(
+        "{} enabled={} logged_on={} next_sender={} next_target={}",
+        status.session_id,
+        flag(status.enabled),
+        flag(status.logged_on),
+        status.next_sender_msg_seq_num,
+        status.next_target_msg_seq_num
+    )
+}
+
+fn error_message(err: SessionError) -> String {
+    match err {
+        SessionError::NotConnected(session_id) => format!("session {session_id} not connected"),
+        SessionError::NotLoggedOn(session_id) => format!("session {session_id} not logged on"),
+        SessionError::SessionNotFound => "session not found".into(),
+        SessionError::Timeout(session_id) => format!("session {session_id} did not respond"),
+        SessionError::QueueFull(session_id) => format!("session {session_id} queue is full"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    use dfx_base::tags;
+
+    use super::AdminServer;
+    use crate::logging::NoLogger;
+    use crate::session::test_session::{lock_registry, TestSession};
+
+    #[test]
+    fn admin_server() {
+        let _registry = lock_registry();
+        let path = std::env::temp_dir().join(format!("dfx-admin-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut server = AdminServer::start(&path, NoLogger).unwrap();
+
+        let mut client = UnixStream::connect(server.path()).unwrap();
+        let mut reader = BufReader::new(client.try_clone().unwrap());
+        let mut request = |line: &str| {
+            writeln!(client, "{line}").unwrap();
+            let mut response = String::new();
+            reader.read_line(&mut response).unwrap();
+            response.trim_end().to_string()
+        };
+        assert_eq!(request("list"), "OK");
+        assert_eq!(
+            request("status FIX.4.4:ADMIN->TEST"),
+            "ERR session FIX.4.4:ADMIN->TEST not found"
+        );
+        assert_eq!(request("reset"), "ERR reset: missing session");
+        assert_eq!(
+            request("restart FIX.4.4:ADMIN->TEST"),
+            "ERR unknown command 'restart'"
+        );
+
+        server.stop();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn admin_session_commands() {
+        let mut test = TestSession::logged_on("ADMIN", "");
+        let path =
+            std::env::temp_dir().join(format!("dfx-admin-session-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut server = AdminServer::start(&path, NoLogger).unwrap();
+
+        let running = AtomicBool::new(true);
+        thread::scope(|scope| {
+            let session = &mut test.session;
+            let running = &running;
+            scope.spawn(move || {
+                while running.load(Ordering::Relaxed) {
+                    session.next();
+                }
+            });
+
+            let mut client = UnixStream::connect(server.path()).unwrap();
+            let mut reader = BufReader::new(client.try_clone().unwrap());
+            let mut request = |line: &str| {
+                writeln!(client, "{line}").unwrap();
+                let mut lines = Vec::new();
+                loop {
+                    let mut response = String::new();
+                    reader.read_line(&mut response).unwrap();
+                    let response = response.trim_end().to_string();
+                    let done = response == "OK" || response.starts_with("ERR");
+                    lines.push(response);
+                    if done {
+                        return lines;
+                    }
+                }
+            };
+            assert_eq!(request("set-sender FIX.4.4:ISLD->ADMIN 5"), ["OK"]);
+            assert_eq!(
+                request("status FIX.4.4:ISLD->ADMIN"),
+                [
+                    "FIX.4.4:ISLD->ADMIN enabled=Y logged_on=Y next_sender=5 next_target=2",
+                    "OK"
+                ]
+            );
+            assert_eq!(request("sequence-reset FIX.4.4:ISLD->ADMIN 8"), ["OK"]);
+            assert_eq!(
+                request("list"),
+                [
+                    "FIX.4.4:ISLD->ADMIN enabled=Y logged_on=Y next_sender=8 next_target=2",
+                    "OK"
+                ]
+            );
+            running.store(false, Ordering::Relaxed);
+        });
+        server.stop();
+
+        let messages = test.sent();
+        let [sequence_reset] = messages.as_slice() else {
+            panic!("{messages:?}");
+        };
+        assert_eq!(sequence_reset.header().get_int(tags::MsgSeqNum).unwrap(), 5);
+        assert_eq!(sequence_reset.get_int(tags::NewSeqNo).unwrap(), 8);
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
     logging::{LogFactory, Logger},
     message_store::MessageStoreFactory,
-    session::{Application, SessionSetting, SessionSettings},
+    session::{
+        Application, Interceptor, Interceptors, SessionSetting, SessionSettings,
+        SessionSettingsError,
+    },
 };
 use dfx_base::data_dictionary_provider::DataDictionaryProvider;
 use dfx_base::message_factory::MessageFactory;
@@ -29,6 +32,7 @@ pub(crate) struct SocketAcceptorThread<
     message_factory: MessageFactory,
     addr: SocketAddr,
     session_settings: Vec<SessionSetting>,
+    interceptors: Interceptors,
 }
 
 #[derive(Debug)]
@@ -65,6 +69,7 @@ pub struct SocketAcceptor<App, StoreFactory, DataDictionaryProvider, LogFactory,
     log_factory: LogFactory,
     message_factory: MessageFactory,
     session_settings: SessionSettings,
+    interceptors: Interceptors,
     thread: Vec<ThreadState>,
     running: Arc<AtomicBool>,
 }
@@ -93,11 +98,19 @@ where
             log_factory,
             message_factory,
             session_settings: session_settings.clone(),
+            interceptors: Interceptors::default(),
             thread: Vec::new(),
             running: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Adds an interceptor to every session, call before `start`.
+    #[must_use]
+    pub fn with_interceptor<I: Interceptor + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
     /// Starts the engine, creates one thread per socket address.
     pub fn start(&mut self) -> &mut Self {
         self.running
@@ -113,7 +126,8 @@ where
                 self.message_factory.clone(),
                 addr,
                 session_settings,
-            );
+            )
+            .with_interceptors(self.interceptors.clone());
             let thread = ac.start(&self.running);
             self.thread.push(thread);
         }
@@ -134,6 +148,15 @@ where
             .collect()
     }
 
+    /// Reloads the certificates of all sessions, see `SSLReloadCertificates`.
+    /// Established connections keep using the certificates they were opened with.
+    pub fn reload_certificates(&self) -> Result<(), SessionSettingsError> {
+        self.session_settings
+            .sessions()
+            .iter()
+            .try_for_each(SessionSetting::reload_certificates)
+    }
+
     /// Stops the engine, and waits for the threads to finish
     pub fn stop(&mut self) {
         self.running
@@ -187,9 +210,15 @@ where
             message_factory,
             addr,
             session_settings,
+            interceptors: Interceptors::default(),
         }
     }
 
+    fn with_interceptors(mut self, interceptors: Interceptors) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
     pub(crate) fn start(self, running: &Arc<AtomicBool>) -> ThreadState {
         let rt = running.clone();
         let endpoint = Arc::new(Mutex::new(None));
@@ -223,21 +252,31 @@ where
         //TODO static listener based on sessions/ports
         while running.load(std::sync::atomic::Ordering::Relaxed) {
             match listener.accept() {
-                Ok((stream, _addr)) => {
+                Ok((stream, peer_addr)) => {
                     // TODO replace with connected event.
                     let session_setting = &self.session_settings[0];
-                    let stream = StreamFactory::configure_stream(
+                    let log = self.log_factory.create(session_setting.session_id());
+                    let stream = match StreamFactory::configure_stream(
                         stream,
                         session_setting.socket_settings(),
                         true,
-                    )?;
-                    let stream = stream;
+                        &log,
+                    ) {
+                        Ok(stream) => stream,
+                        // A failed handshake only affects this connection.
+                        Err(e @ ConnectionError::Tls(_)) => {
+                            log.on_event(&format!("TLS handshake with {peer_addr} failed: {e}"));
+                            continue;
+                        }
+                        Err(e) => return Err(e.into()),
+                    };
                     let session_settings = self.session_settings.clone();
                     let app = self.app.clone();
                     let store_factory = self.store_factory.clone();
                     let data_dictionary_provider = self.data_dictionary_provider.clone();
                     let log_factory = self.log_factory.clone();
                     let message_factory = self.message_factory.clone();
+                    let interceptors = self.interceptors.clone();
 
                     let t = thread::Builder::new()
                         .name(format!("socket-acceptor-connection-{n}"))
@@ -250,7 +289,8 @@ where
                                 data_dictionary_provider,
                                 log_factory,
                                 message_factory,
-                            );
+                            )
+                            .with_interceptors(interceptors);
                             reactor.start()
                         })
                         .unwrap();
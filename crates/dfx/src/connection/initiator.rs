@@ -8,7 +8,10 @@ use crate::{
     connection::StreamFactory,
     logging::{LogFactory, Logger},
     message_store::MessageStoreFactory,
-    session::{Application, SessionSetting, SessionSettings},
+    session::{
+        Application, Interceptor, Interceptors, SessionSetting, SessionSettings,
+        SessionSettingsError,
+    },
 };
 use chrono::Utc;
 use dfx_base::data_dictionary_provider::DataDictionaryProvider;
@@ -28,6 +31,7 @@ pub struct SocketInitiator<App, StoreFactory, DataDictionaryProvider, LogFactory
     log_factory: LogFactory,
     message_factory: MessageFactory,
     session_settings: SessionSettings,
+    interceptors: Interceptors,
     thread: Vec<JoinHandle<()>>,
     running: Arc<AtomicBool>,
 }
@@ -57,11 +61,19 @@ where
             log_factory,
             message_factory,
             session_settings,
+            interceptors: Interceptors::default(),
             thread: Vec::new(),
             running: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Adds an interceptor to every session, call before `start`.
+    #[must_use]
+    pub fn with_interceptor<I: Interceptor + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
     pub fn start(&mut self) {
         self.running
             .store(true, std::sync::atomic::Ordering::SeqCst);
@@ -73,7 +85,8 @@ where
                 self.log_factory.clone(),
                 self.message_factory.clone(),
                 session_settings.clone(),
-            );
+            )
+            .with_interceptors(self.interceptors.clone());
             let thread = ac.start(&self.running);
             self.thread.push(thread);
         }
@@ -81,6 +94,14 @@ where
     pub fn join(&mut self) {
         while self.thread.iter().any(|t| !t.is_finished()) {}
     }
+    /// Reloads the certificates of all sessions, see `SSLReloadCertificates`.
+    /// Established connections keep using the certificates they were opened with.
+    pub fn reload_certificates(&self) -> Result<(), SessionSettingsError> {
+        self.session_settings
+            .sessions()
+            .iter()
+            .try_for_each(SessionSetting::reload_certificates)
+    }
     pub fn stop(mut self) {
         self.running
             .store(false, std::sync::atomic::Ordering::Relaxed);
@@ -101,6 +122,7 @@ pub(crate) struct SocketInitiatorThread<
     log_factory: LogFactory,
     message_factory: MessageFactory,
     session_settings: SessionSetting,
+    interceptors: Interceptors,
 }
 
 #[derive(Debug)]
@@ -150,9 +172,15 @@ where
             log_factory,
             message_factory,
             session_settings,
+            interceptors: Interceptors::default(),
         }
     }
 
+    fn with_interceptors(mut self, interceptors: Interceptors) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
     pub(crate) fn start(mut self, running: &Arc<AtomicBool>) -> JoinHandle<()> {
         let running = running.clone();
         thread::Builder::new()
@@ -161,11 +189,7 @@ where
                 let timeout = u64::from(self.session_settings.reconnect_interval().unwrap_or(30));
                 // loop here for session reconnect!
                 while running.load(std::sync::atomic::Ordering::Relaxed) {
-                    if self
-                        .session_settings
-                        .schedule()
-                        .is_session_time(&Utc::now())
-                    {
+                    if self.session_settings.schedule().is_logon_time(&Utc::now()) {
                         if let Err(e) = self.event_loop() {
                             match e {
                                 e => println!(
@@ -183,7 +207,9 @@ where
     }
 
     fn event_loop(&mut self) -> Result<(), InitiatorError> {
-        let stream = StreamFactory::create_client_stream(self.session_settings.socket_settings())?;
+        let log = self.log_factory.create(self.session_settings.session_id());
+        let stream =
+            StreamFactory::create_client_stream(self.session_settings.socket_settings(), &log)?;
         let app = self.app.clone();
         let store_factory = self.store_factory.clone();
         let data_dictionary_provider = self.data_dictionary_provider.clone();
@@ -197,7 +223,8 @@ where
             data_dictionary_provider,
             log_factory,
             message_factory,
-        );
+        )
+        .with_interceptors(self.interceptors.clone());
         reactor.start();
 
         Ok(())
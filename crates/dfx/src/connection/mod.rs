@@ -7,36 +7,43 @@ mod acceptor;
 pub use acceptor::*;
 mod reactor;
 pub(crate) use reactor::*;
+mod proxy;
 mod socket_settings;
 pub(crate) use socket_settings::*;
 mod stream_factory;
 pub(crate) use stream_factory::*;
+mod tls;
+pub(crate) use tls::{PeerIdentity, TlsAcceptor, TlsConfig, TlsConnector};
 
 #[derive(Debug)]
 pub(crate) enum ConnectionError {
-    IOError(std::io::Error),
-    AddrParseError(AddrParseError),
+    Io(std::io::Error),
+    AddrParse(AddrParseError),
+    Proxy(String),
+    Tls(String),
 }
 impl From<std::io::Error> for ConnectionError {
     fn from(e: std::io::Error) -> ConnectionError {
-        ConnectionError::IOError(e)
+        ConnectionError::Io(e)
     }
 }
 impl From<AddrParseError> for ConnectionError {
     fn from(e: AddrParseError) -> ConnectionError {
-        ConnectionError::AddrParseError(e)
+        ConnectionError::AddrParse(e)
     }
 }
 
 impl Display for ConnectionError {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ConnectionError::IOError(err) => {
-                fmt.write_fmt(format_args!("Connection failed: {err}"))
-            }
-            ConnectionError::AddrParseError(err) => {
+            ConnectionError::Io(err) => fmt.write_fmt(format_args!("Connection failed: {err}")),
+            ConnectionError::AddrParse(err) => {
                 fmt.write_fmt(format_args!("Failed to parse address: {err}"))
             }
+            ConnectionError::Proxy(err) => {
+                fmt.write_fmt(format_args!("Proxy tunnel failed: {err}"))
+            }
+            ConnectionError::Tls(err) => fmt.write_fmt(format_args!("TLS handshake failed: {err}")),
         }
     }
 }
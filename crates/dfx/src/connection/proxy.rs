@@ -0,0 +1,292 @@
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+
+use base64::Engine;
+
+use crate::session::{ProxyOptions, ProxyType};
+
+use super::ConnectionError;
+
+const SOCKS5_VERSION: u8 = 0x05;
+const SOCKS5_AUTH_NONE: u8 = 0x00;
+const SOCKS5_AUTH_USER_PASSWORD: u8 = 0x02;
+const SOCKS5_AUTH_NO_ACCEPTABLE: u8 = 0xFF;
+const SOCKS5_CMD_CONNECT: u8 = 0x01;
+const SOCKS5_ATYP_IPV4: u8 = 0x01;
+const SOCKS5_ATYP_DOMAIN: u8 = 0x03;
+const SOCKS5_ATYP_IPV6: u8 = 0x04;
+
+/// Maximum size of the HTTP CONNECT response headers we are willing to read.
+const HTTP_MAX_RESPONSE: usize = 8192;
+
+/// Opens a tcp connection to the proxy and tunnels it through to the target endpoint.
+pub(crate) fn connect(options: &ProxyOptions) -> Result<TcpStream, ConnectionError> {
+    let addr = (options.host(), options.port())
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| {
+            ConnectionError::Proxy(format!(
+                "Could not resolve proxy {}:{}",
+                options.host(),
+                options.port()
+            ))
+        })?;
+    let mut stream = TcpStream::connect(addr)?;
+    match options.proxy_type() {
+        ProxyType::Http => http_connect(&mut stream, options)?,
+        ProxyType::Socks5 => socks5_connect(&mut stream, options)?,
+    }
+    Ok(stream)
+}
+
+fn http_connect<S: Read + Write>(
+    stream: &mut S,
+    options: &ProxyOptions,
+) -> Result<(), ConnectionError> {
+    let target = format!("{}:{}", options.target_host(), options.target_port());
+    let mut request = format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n");
+    if let Some((user, password)) = options.credentials() {
+        let token = base64::engine::general_purpose::STANDARD.encode(format!("{user}:{password}"));
+        request.push_str(&format!("Proxy-Authorization: Basic {token}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+
+    // Read byte by byte so no data belonging to the tunnel is consumed.
+    let mut response = Vec::new();
+    let mut byte = [0; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= HTTP_MAX_RESPONSE {
+            return Err(ConnectionError::Proxy(
+                "HTTP proxy response too large".into(),
+            ));
+        }
+        if stream.read(&mut byte)? == 0 {
+            return Err(ConnectionError::Proxy(
+                "HTTP proxy closed the connection".into(),
+            ));
+        }
+        response.push(byte[0]);
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    let status = status_line.split_whitespace().nth(1);
+    match status {
+        Some(code) if code.starts_with('2') => Ok(()),
+        _ => Err(ConnectionError::Proxy(format!(
+            "HTTP proxy refused CONNECT {target}: {status_line}"
+        ))),
+    }
+}
+
+fn socks5_connect<S: Read + Write>(
+    stream: &mut S,
+    options: &ProxyOptions,
+) -> Result<(), ConnectionError> {
+    let credentials = options.credentials();
+    let greeting = match credentials {
+        Some(_) => vec![
+            SOCKS5_VERSION,
+            2,
+            SOCKS5_AUTH_NONE,
+            SOCKS5_AUTH_USER_PASSWORD,
+        ],
+        None => vec![SOCKS5_VERSION, 1, SOCKS5_AUTH_NONE],
+    };
+    stream.write_all(&greeting)?;
+
+    let mut reply = [0; 2];
+    stream.read_exact(&mut reply)?;
+    if reply[0] != SOCKS5_VERSION {
+        return Err(ConnectionError::Proxy(format!(
+            "Unsupported SOCKS version {}",
+            reply[0]
+        )));
+    }
+    match (reply[1], credentials) {
+        (SOCKS5_AUTH_NONE, _) => {}
+        (SOCKS5_AUTH_USER_PASSWORD, Some((user, password))) => {
+            if user.len() > 255 || password.len() > 255 {
+                return Err(ConnectionError::Proxy(
+                    "SOCKS5 username and password must be at most 255 bytes".into(),
+                ));
+            }
+            let mut auth = vec![0x01, user.len() as u8];
+            auth.extend_from_slice(user.as_bytes());
+            auth.push(password.len() as u8);
+            auth.extend_from_slice(password.as_bytes());
+            stream.write_all(&auth)?;
+
+            let mut status = [0; 2];
+            stream.read_exact(&mut status)?;
+            if status[1] != 0x00 {
+                return Err(ConnectionError::Proxy(
+                    "SOCKS5 proxy rejected the credentials".into(),
+                ));
+            }
+        }
+        (SOCKS5_AUTH_NO_ACCEPTABLE, _) => {
+            return Err(ConnectionError::Proxy(
+                "SOCKS5 proxy has no acceptable authentication method".into(),
+            ));
+        }
+        (method, _) => {
+            return Err(ConnectionError::Proxy(format!(
+                "SOCKS5 proxy selected unsupported authentication method {method}"
+            )));
+        }
+    }
+
+    let mut request = vec![SOCKS5_VERSION, SOCKS5_CMD_CONNECT, 0x00];
+    match options.target_host().parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(SOCKS5_ATYP_IPV4);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(SOCKS5_ATYP_IPV6);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            let host = options.target_host().as_bytes();
+            if host.len() > 255 {
+                return Err(ConnectionError::Proxy(
+                    "SOCKS5 target host must be at most 255 bytes".into(),
+                ));
+            }
+            request.push(SOCKS5_ATYP_DOMAIN);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host);
+        }
+    }
+    request.extend_from_slice(&options.target_port().to_be_bytes());
+    stream.write_all(&request)?;
+
+    let mut header = [0; 4];
+    stream.read_exact(&mut header)?;
+    if header[1] != 0x00 {
+        return Err(ConnectionError::Proxy(format!(
+            "SOCKS5 proxy refused connection to {}:{} (reply {})",
+            options.target_host(),
+            options.target_port(),
+            header[1]
+        )));
+    }
+    let bound_len = match header[3] {
+        SOCKS5_ATYP_IPV4 => 4,
+        SOCKS5_ATYP_IPV6 => 16,
+        SOCKS5_ATYP_DOMAIN => {
+            let mut len = [0; 1];
+            stream.read_exact(&mut len)?;
+            usize::from(len[0])
+        }
+        atyp => {
+            return Err(ConnectionError::Proxy(format!(
+                "SOCKS5 proxy replied with unknown address type {atyp}"
+            )))
+        }
+    };
+    // bound address + port, unused.
+    let mut bound = vec![0; bound_len + 2];
+    stream.read_exact(&mut bound)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use crate::connection::ConnectionError;
+    use crate::session::{ProxyOptions, ProxyType};
+
+    fn options(proxy_type: ProxyType, port: u16, credentials: bool) -> ProxyOptions {
+        ProxyOptions::builder()
+            .proxy_type(proxy_type)
+            .host("127.0.0.1".into())
+            .port(port)
+            .credentials(credentials.then(|| ("user".into(), "secret".into())))
+            .target_host("fix.example.com".into())
+            .target_port(9876)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn http_connect_with_credentials() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let proxy = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut byte = [0; 1];
+            while !request.ends_with(b"\r\n\r\n") {
+                stream.read_exact(&mut byte).unwrap();
+                request.push(byte[0]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n8=FIX")
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let mut stream = super::connect(&options(ProxyType::Http, port, true)).unwrap();
+        let request = proxy.join().unwrap();
+        assert!(request.starts_with("CONNECT fix.example.com:9876 HTTP/1.1\r\n"));
+        assert!(request.contains("Proxy-Authorization: Basic dXNlcjpzZWNyZXQ=\r\n"));
+
+        let mut tunnel = [0; 5];
+        stream.read_exact(&mut tunnel).unwrap();
+        assert_eq!(&tunnel, b"8=FIX");
+    }
+
+    #[test]
+    fn http_connect_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 512];
+            let _ = stream.read(&mut buf).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n")
+                .unwrap();
+        });
+
+        let result = super::connect(&options(ProxyType::Http, port, false));
+        assert!(matches!(result, Err(ConnectionError::Proxy(_))));
+    }
+
+    #[test]
+    fn socks5_connect_with_credentials() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let proxy = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut greeting = [0; 4];
+            stream.read_exact(&mut greeting).unwrap();
+            assert_eq!(greeting, [5, 2, 0, 2]);
+            stream.write_all(&[5, 2]).unwrap();
+
+            let mut auth = [0; 13];
+            stream.read_exact(&mut auth).unwrap();
+            assert_eq!(&auth, b"\x01\x04user\x06secret");
+            stream.write_all(&[1, 0]).unwrap();
+
+            let mut request = [0; 22];
+            stream.read_exact(&mut request).unwrap();
+            assert_eq!(&request[..5], &[5, 1, 0, 3, 15]);
+            assert_eq!(&request[5..20], b"fix.example.com");
+            assert_eq!(u16::from_be_bytes([request[20], request[21]]), 9876);
+            stream
+                .write_all(&[5, 0, 0, 1, 127, 0, 0, 1, 0x26, 0x94])
+                .unwrap();
+        });
+
+        super::connect(&options(ProxyType::Socks5, port, true)).unwrap();
+        proxy.join().unwrap();
+    }
+}
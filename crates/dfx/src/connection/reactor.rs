@@ -1,10 +1,11 @@
 use std::{
     io::{Read, Write},
-    sync::mpsc::{Receiver, Sender},
+    sync::mpsc::Receiver,
     time::Duration,
 };
 
 use dfx_base::data_dictionary_provider::DataDictionaryProvider;
+use dfx_base::encoding::encode_latin1;
 use dfx_base::message::{Message, MessageParseError};
 use dfx_base::message_factory::MessageFactory;
 use dfx_base::session_id::SessionId;
@@ -14,7 +15,7 @@ use crate::{
     message_store::MessageStoreFactory,
     parser::{Parser, ParserError},
     session::{
-        Application, ChannelResponder, ISession, ResponderEvent, ResponderResponse, SessionSetting,
+        Application, ChannelResponder, ISession, Interceptors, ResponderEvent, SessionSetting,
     },
 };
 
@@ -34,13 +35,13 @@ pub(crate) struct SocketReactor<
     stream: Option<Stream>,
     buffer: [u8; BUF_SIZE],
     rx: Option<Receiver<ResponderEvent>>,
-    tx: Option<Sender<ResponderResponse>>,
     settings: Vec<SessionSetting>,
     app: App,
     store_factory: StoreFactory,
     data_dictionary_provider: DataDictionaryProvider,
     log_factory: LogFactory,
     message_factory: MessageFactory,
+    interceptors: Interceptors,
 }
 
 #[derive(Debug)]
@@ -105,12 +106,12 @@ where
             stream: Some(connection),
             buffer: [0; BUF_SIZE],
             rx: None,
-            tx: None,
             app,
             store_factory,
             data_dictionary_provider,
             log_factory,
             message_factory,
+            interceptors: Interceptors::default(),
         };
         if reactor.settings.len() == 1 {
             let session_setting = &reactor.settings[0];
@@ -129,12 +130,19 @@ where
         reactor
     }
 
+    pub(crate) fn with_interceptors(mut self, interceptors: Interceptors) -> Self {
+        if let Some(session) = self.session.as_mut() {
+            session.set_interceptors(interceptors.clone());
+        }
+        self.interceptors = interceptors;
+        self
+    }
+
     fn create_responder(&mut self) {
         if let Some(s) = self.session.as_mut() {
-            let (responder, rx1, tx1) = ChannelResponder::new();
+            let (responder, rx1) = ChannelResponder::new();
             s.set_responder(Box::new(responder));
             self.rx = Some(rx1);
-            self.tx = Some(tx1);
         }
     }
 
@@ -154,6 +162,8 @@ where
                         // TODO
                     }
                 }
+                // Rejected before the session was connected, there is nothing to disconnect.
+                ReactorError::ConnectionError(ConnectionError::Tls(_)) => {}
                 e => todo!("SocketReactor::start: Error {:?}", e),
             }
         }
@@ -161,6 +171,9 @@ where
     }
 
     fn event_loop(&mut self) -> Result<(), ReactorError> {
+        if let Some(session) = self.session.as_ref() {
+            self.verify_client_identity(session.session_id(), &self.settings[0])?;
+        }
         while self.session.is_none() {
             self.read()?;
         }
@@ -257,6 +270,7 @@ where
                 match session_settings {
                     Some(settings) => {
                         if settings.accepts(&session_id) {
+                            self.verify_client_identity(&session_id, settings)?;
                             let session = self.create_session(session_id.clone(), settings);
                             self.session = Some(session);
                             self.create_responder();
@@ -276,13 +290,41 @@ where
         Ok(())
     }
 
+    /// Rejects the connection if the client certificate does not present the identity
+    /// expected for `session_id`, see `SSLRequireClientCertificate`.
+    fn verify_client_identity(
+        &self,
+        session_id: &SessionId,
+        settings: &SessionSetting,
+    ) -> Result<(), ReactorError> {
+        let Some(expected) = settings.expected_client_identity(session_id) else {
+            return Ok(());
+        };
+        let identity = self.stream.as_ref().and_then(Stream::peer_identity);
+        match identity {
+            Some(identity) if identity.matches(expected) => Ok(()),
+            identity => {
+                let reason = match identity {
+                    Some(identity) => {
+                        format!("client certificate {identity} does not match {expected}")
+                    }
+                    None => "no client certificate".into(),
+                };
+                self.log_factory
+                    .create(session_id)
+                    .on_event(&format!("Disconnecting {session_id}; {reason}"));
+                Err(ConnectionError::Tls(reason).into())
+            }
+        }
+    }
+
     fn create_session(
         &self,
         session_id: SessionId,
         settings: &SessionSetting,
     ) -> ISession<App, DDP, Log, MF> {
         let log = self.log_factory.create(&session_id);
-        ISession::from_settings(
+        let mut session = ISession::from_settings(
             session_id,
             self.app.clone(),
             Box::new(self.store_factory.clone()),
@@ -290,19 +332,22 @@ where
             log,
             self.message_factory.clone(),
             settings.clone(),
-        )
+        );
+        session.set_interceptors(self.interceptors.clone());
+        session
     }
 
     fn process_responder(&mut self) -> Result<(), ReactorError> {
-        match (self.tx.as_mut(), self.rx.as_mut()) {
-            (Some(tx), Some(rx)) => match rx.recv_timeout(Duration::from_millis(1)) {
+        match self.rx.as_mut() {
+            Some(rx) => match rx.recv_timeout(Duration::from_millis(1)) {
                 Ok(event) => match event {
-                    ResponderEvent::Send(message) => {
+                    ResponderEvent::Send(message, confirmation) => {
                         let stream = &mut self.stream.as_mut().unwrap();
-                        match stream.write_all(message.as_bytes()) {
-                            Ok(()) => tx.send(ResponderResponse::Sent(true)).unwrap_or(()),
-                            Err(_) => tx.send(ResponderResponse::Sent(false)).unwrap_or(()),
-                        };
+                        match (stream.write_all(&encode_latin1(&message)), confirmation) {
+                            (Ok(()), Some(confirmation)) => confirmation.written(),
+                            (Err(e), Some(confirmation)) => confirmation.io_error(e),
+                            (_, None) => {}
+                        }
                         Ok(stream.flush()?)
                     }
                     ResponderEvent::Disconnect => Err(ReactorError::Disconnect),
@@ -1,6 +1,6 @@
 use std::net::SocketAddr;
 
-use crate::session::{SocketOptions, SslOptions};
+use crate::session::{ProxyOptions, SocketOptions, SslOptions};
 
 use super::ConnectionError;
 
@@ -12,6 +12,7 @@ pub(crate) struct SocketSettings {
     // receive_buffer_size: usize,
     send_timeout: u64,
     receive_timeout: u64,
+    proxy_options: Option<ProxyOptions>,
     ssl_options: Option<SslOptions>,
 }
 
@@ -20,6 +21,7 @@ impl SocketSettings {
     pub(crate) fn new(
         socket_addr: SocketAddr,
         socket_options: SocketOptions,
+        proxy_options: Option<ProxyOptions>,
         ssl_options: Option<SslOptions>,
     ) -> Self {
         Self {
@@ -29,6 +31,7 @@ impl SocketSettings {
             // receive_buffer_size: socket_options.receive_buffer_size(),
             send_timeout: socket_options.send_timeout(),
             receive_timeout: socket_options.receive_timeout(),
+            proxy_options,
             ssl_options,
         }
     }
@@ -57,6 +60,10 @@ impl SocketSettings {
         self.receive_timeout
     }
 
+    pub(crate) fn proxy_options(&self) -> Option<&ProxyOptions> {
+        self.proxy_options.as_ref()
+    }
+
     pub(crate) fn ssl_options(&self) -> &Option<SslOptions> {
         &self.ssl_options
     }
@@ -1,5 +1,9 @@
+use crate::connection::proxy;
+use crate::connection::tls::TlsStream;
 use crate::connection::ConnectionError;
+use crate::connection::PeerIdentity;
 use crate::connection::SocketSettings;
+use crate::logging::Logger;
 use crate::session::SslOptions;
 use std::io::Read;
 use std::io::Write;
@@ -9,7 +13,7 @@ use std::time::Duration;
 
 pub(crate) enum Stream {
     Tcp(TcpStream),
-    Ssl(native_tls::TlsStream<TcpStream>),
+    Ssl(TlsStream),
 }
 
 #[derive(Debug)]
@@ -38,6 +42,15 @@ impl Stream {
             Stream::Ssl(ssl) => ssl.get_ref().peer_addr().map(Some),
         }
     }
+    /// Identity presented by the peer certificate, `None` for plain tcp
+    /// or if the peer did not present a certificate.
+    pub(crate) fn peer_identity(&self) -> Option<PeerIdentity> {
+        match self {
+            Stream::Tcp(_) => None,
+            Stream::Ssl(ssl) => PeerIdentity::from_der(&ssl.peer_certificate()?),
+        }
+    }
+
     pub(crate) fn shutdown(&mut self, how: std::net::Shutdown) -> std::io::Result<()> {
         match self {
             Stream::Tcp(tcp) => tcp.shutdown(how),
@@ -86,25 +99,32 @@ pub(crate) struct StreamFactory;
 impl StreamFactory {
     pub(crate) fn create_client_stream(
         settings: SocketSettings,
+        log: &dyn Logger,
     ) -> Result<Stream, ConnectionError> {
-        let endpoint: SocketAddr = settings.get_endpoint()?;
-        let stream = TcpStream::connect(endpoint)?;
-        let stream = StreamFactory::configure_stream(stream, settings, false)?;
+        let stream = match settings.proxy_options() {
+            Some(proxy_options) => proxy::connect(proxy_options)?,
+            None => {
+                let endpoint: SocketAddr = settings.get_endpoint()?;
+                TcpStream::connect(endpoint)?
+            }
+        };
+        let stream = StreamFactory::configure_stream(stream, settings, false, log)?;
         Ok(stream)
     }
     pub(crate) fn configure_stream(
         mut stream: TcpStream,
         settings: SocketSettings,
         _acceptor: bool,
+        log: &dyn Logger,
     ) -> Result<Stream, ConnectionError> {
         match settings.ssl_options() {
-            Some(SslOptions::Acceptor { acceptor }) => {
-                let mut stream = acceptor.accept(stream).unwrap();
+            Some(SslOptions::Acceptor { acceptor, .. }) => {
+                let mut stream = acceptor.accept(stream, log)?;
                 StreamFactory::configure_stream_mut(stream.get_mut(), &settings)?;
                 Ok(Stream::Ssl(stream))
             }
             Some(SslOptions::Initiator { initiator, domain }) => {
-                let mut stream = initiator.connect(domain, stream).unwrap();
+                let mut stream = initiator.connect(domain, stream, log)?;
                 StreamFactory::configure_stream_mut(stream.get_mut(), &settings)?;
                 Ok(Stream::Ssl(stream))
             }
@@ -0,0 +1,75 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+use crate::connection::ConnectionError;
+use crate::session::SessionSettingsError;
+
+use super::TlsConfig;
+
+const NO_BACKEND: &str = "SSLEnable=Y requires the native-tls or rustls feature";
+
+#[derive(Clone, Debug)]
+pub(crate) enum TlsConnector {}
+
+#[derive(Clone, Debug)]
+pub(crate) enum TlsAcceptor {}
+
+pub(crate) enum TlsStream {}
+
+impl TlsConnector {
+    pub(crate) fn new(_config: &TlsConfig) -> Result<Self, SessionSettingsError> {
+        Err(SessionSettingsError::TlsError(NO_BACKEND.into()))
+    }
+
+    pub(crate) fn connect(
+        &self,
+        _domain: &str,
+        _stream: TcpStream,
+    ) -> Result<TlsStream, ConnectionError> {
+        match *self {}
+    }
+}
+
+impl TlsAcceptor {
+    pub(crate) fn new(_config: &TlsConfig) -> Result<Self, SessionSettingsError> {
+        Err(SessionSettingsError::TlsError(NO_BACKEND.into()))
+    }
+
+    pub(crate) fn accept(&self, _stream: TcpStream) -> Result<TlsStream, ConnectionError> {
+        match *self {}
+    }
+}
+
+impl TlsStream {
+    pub(crate) fn get_ref(&self) -> &TcpStream {
+        match *self {}
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut TcpStream {
+        match *self {}
+    }
+
+    pub(crate) fn shutdown(&mut self) -> std::io::Result<()> {
+        match *self {}
+    }
+
+    pub(crate) fn peer_certificate(&self) -> Option<Vec<u8>> {
+        match *self {}
+    }
+}
+
+impl Read for TlsStream {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        match *self {}
+    }
+}
+
+impl Write for TlsStream {
+    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+        match *self {}
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match *self {}
+    }
+}
@@ -0,0 +1,351 @@
+//! TLS backends.
+//!
+//! `native-tls` is used by default, enabling the `rustls` feature switches the engine to `rustls`.
+//! When both features are enabled `rustls` takes precedence.
+use std::path::PathBuf;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::SystemTime;
+
+use x509_parser::extensions::GeneralName;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use crate::connection::ConnectionError;
+use crate::logging::Logger;
+use crate::session::SessionSettingsError;
+
+#[cfg(feature = "rustls")]
+mod rustls;
+#[cfg(feature = "rustls")]
+use self::rustls as backend;
+
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+mod native;
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+use self::native as backend;
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+mod disabled;
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+use self::disabled as backend;
+
+pub(crate) use backend::TlsStream;
+
+/// Backend independent TLS configuration, built from the `SSL*` settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TlsConfig {
+    pub(crate) certificate: Option<PathBuf>,
+    pub(crate) certificate_password: Option<String>,
+    pub(crate) ca_certificate: Option<PathBuf>,
+    pub(crate) min_protocol: Option<String>,
+    pub(crate) max_protocol: Option<String>,
+    pub(crate) use_sni: bool,
+    pub(crate) accept_invalid_certs: bool,
+    pub(crate) accept_invalid_hostnames: bool,
+    pub(crate) disable_built_in_roots: bool,
+    pub(crate) require_client_certificate: bool,
+    /// Reload when the certificate files changed, checked for every new connection.
+    pub(crate) reload_on_change: bool,
+}
+
+/// Accepts TLS connections, the certificates can be reloaded without affecting established streams.
+#[derive(Clone, Debug)]
+pub(crate) struct TlsAcceptor(Reloadable<backend::TlsAcceptor>);
+
+/// Opens TLS connections, the certificates can be reloaded without affecting established streams.
+#[derive(Clone, Debug)]
+pub(crate) struct TlsConnector(Reloadable<backend::TlsConnector>);
+
+impl TlsAcceptor {
+    pub(crate) fn new(config: TlsConfig) -> Result<Self, SessionSettingsError> {
+        Ok(TlsAcceptor(Reloadable::new(
+            config,
+            backend::TlsAcceptor::new,
+        )?))
+    }
+
+    pub(crate) fn accept(
+        &self,
+        stream: std::net::TcpStream,
+        log: &dyn Logger,
+    ) -> Result<TlsStream, ConnectionError> {
+        self.0.current(log).accept(stream)
+    }
+
+    pub(crate) fn reload(&self) -> Result<(), SessionSettingsError> {
+        self.0.reload()
+    }
+}
+
+impl TlsConnector {
+    pub(crate) fn new(config: TlsConfig) -> Result<Self, SessionSettingsError> {
+        Ok(TlsConnector(Reloadable::new(
+            config,
+            backend::TlsConnector::new,
+        )?))
+    }
+
+    pub(crate) fn connect(
+        &self,
+        domain: &str,
+        stream: std::net::TcpStream,
+        log: &dyn Logger,
+    ) -> Result<TlsStream, ConnectionError> {
+        self.0.current(log).connect(domain, stream)
+    }
+
+    pub(crate) fn reload(&self) -> Result<(), SessionSettingsError> {
+        self.0.reload()
+    }
+}
+
+/// A value built from a [`TlsConfig`], shared between clones and rebuilt on [`Reloadable::reload`].
+#[derive(Clone, Debug)]
+struct Reloadable<T> {
+    config: Arc<TlsConfig>,
+    build: fn(&TlsConfig) -> Result<T, SessionSettingsError>,
+    state: Arc<RwLock<Loaded<T>>>,
+}
+
+#[derive(Debug)]
+struct Loaded<T> {
+    value: T,
+    modified: Vec<Option<SystemTime>>,
+}
+
+impl<T: Clone> Reloadable<T> {
+    fn new(
+        config: TlsConfig,
+        build: fn(&TlsConfig) -> Result<T, SessionSettingsError>,
+    ) -> Result<Self, SessionSettingsError> {
+        let loaded = Loaded {
+            modified: modified(&config),
+            value: build(&config)?,
+        };
+        Ok(Reloadable {
+            config: Arc::new(config),
+            build,
+            state: Arc::new(RwLock::new(loaded)),
+        })
+    }
+
+    /// Returns the loaded value, reloaded first if the files changed. A failed reload is logged
+    /// to `log`.
+    fn current(&self, log: &dyn Logger) -> T {
+        if self.config.reload_on_change {
+            let modified = modified(&self.config);
+            let changed = self
+                .state
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .modified
+                != modified;
+            if changed {
+                if let Err(e) = self.reload() {
+                    // Keep using the previous certificates, e.g. while the files are being replaced.
+                    // The files are only retried once they change again.
+                    log.on_event(&format!(
+                        "Failed to reload certificates, keeping the previous ones: {e:?}"
+                    ));
+                    self.state
+                        .write()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .modified = modified;
+                }
+            }
+        }
+        self.state
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .value
+            .clone()
+    }
+
+    fn reload(&self) -> Result<(), SessionSettingsError> {
+        let modified = modified(&self.config);
+        let value = (self.build)(&self.config)?;
+        *self.state.write().unwrap_or_else(PoisonError::into_inner) = Loaded { value, modified };
+        Ok(())
+    }
+}
+
+fn modified(config: &TlsConfig) -> Vec<Option<SystemTime>> {
+    [config.certificate.as_ref(), config.ca_certificate.as_ref()]
+        .into_iter()
+        .flatten()
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Names presented by the peer certificate: the subject common names and subject alternative names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PeerIdentity {
+    common_names: Vec<String>,
+    alt_names: Vec<String>,
+}
+
+impl PeerIdentity {
+    pub(crate) fn from_der(der: &[u8]) -> Option<PeerIdentity> {
+        let (_, certificate) = X509Certificate::from_der(der).ok()?;
+        let common_names = certificate
+            .subject()
+            .iter_common_name()
+            .filter_map(|cn| cn.as_str().ok())
+            .map(String::from)
+            .collect();
+        let alt_names = certificate
+            .subject_alternative_name()
+            .ok()
+            .flatten()
+            .map(|san| {
+                san.value
+                    .general_names
+                    .iter()
+                    .filter_map(|name| match name {
+                        GeneralName::DNSName(name)
+                        | GeneralName::RFC822Name(name)
+                        | GeneralName::URI(name) => Some(name.to_string()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(PeerIdentity {
+            common_names,
+            alt_names,
+        })
+    }
+
+    /// Returns true if `expected` is one of the subject common names or subject alternative names.
+    pub(crate) fn matches(&self, expected: &str) -> bool {
+        self.common_names
+            .iter()
+            .chain(self.alt_names.iter())
+            .any(|name| name == expected)
+    }
+}
+
+impl std::fmt::Display for PeerIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CN={} SAN={}",
+            self.common_names.join(","),
+            self.alt_names.join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Write;
+    use std::time::{Duration, SystemTime};
+
+    use crate::logging::tests::TestLogger;
+    use crate::logging::NoLogger;
+    use crate::session::SessionSettingsError;
+
+    use super::{PeerIdentity, Reloadable, TlsConfig};
+
+    // Certificate with CN=CLIENT1 and SAN DNS:client1.example.com, email:ops@example.com
+    const CLIENT_CERT: &str = include_str!("../../../tests/tls/client1.pem");
+
+    fn der(pem: &str) -> Vec<u8> {
+        use base64::Engine;
+        let body: String = pem
+            .lines()
+            .skip_while(|line| *line != "-----BEGIN CERTIFICATE-----")
+            .skip(1)
+            .take_while(|line| *line != "-----END CERTIFICATE-----")
+            .collect();
+        base64::engine::general_purpose::STANDARD
+            .decode(body)
+            .unwrap()
+    }
+
+    #[test]
+    fn peer_identity_from_certificate() {
+        let identity = PeerIdentity::from_der(&der(CLIENT_CERT)).unwrap();
+        assert_eq!(identity.common_names, ["CLIENT1"]);
+        assert_eq!(
+            identity.alt_names,
+            ["client1.example.com", "ops@example.com"]
+        );
+        assert!(identity.matches("CLIENT1"));
+        assert!(identity.matches("client1.example.com"));
+        assert!(!identity.matches("CLIENT2"));
+        assert!(!identity.matches("client1"));
+    }
+
+    #[test]
+    fn peer_identity_invalid_der() {
+        assert_eq!(PeerIdentity::from_der(b"not a certificate"), None);
+    }
+
+    fn read_certificate(config: &TlsConfig) -> Result<String, SessionSettingsError> {
+        let content = std::fs::read_to_string(config.certificate.as_ref().unwrap())?;
+        match content.as_str() {
+            "" => Err(SessionSettingsError::TlsError("empty".into())),
+            _ => Ok(content),
+        }
+    }
+
+    fn write(path: &std::path::Path, content: &str, modified: SystemTime) {
+        let mut file = File::create(path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file.set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn reload_on_change() {
+        let path = std::env::temp_dir().join(format!("dfx-reload-{}.pem", std::process::id()));
+        let start = SystemTime::now() - Duration::from_secs(60);
+        write(&path, "first", start);
+        let config = TlsConfig {
+            certificate: Some(path.clone()),
+            reload_on_change: true,
+            ..Default::default()
+        };
+        let reloadable = Reloadable::new(config, read_certificate).unwrap();
+        let shared = reloadable.clone();
+        let log = TestLogger::default();
+        assert_eq!(reloadable.current(&log), "first");
+
+        write(&path, "second", start + Duration::from_secs(1));
+        assert_eq!(shared.current(&log), "second");
+        assert_eq!(reloadable.current(&log), "second");
+
+        // A broken file keeps the previous value
+        write(&path, "", start + Duration::from_secs(2));
+        assert_eq!(reloadable.current(&log), "second");
+        assert!(reloadable.reload().is_err());
+        assert_eq!(reloadable.current(&log), "second");
+        assert_eq!(
+            log.events(),
+            ["Failed to reload certificates, keeping the previous ones: TlsError(\"empty\")"]
+        );
+
+        write(&path, "third", start + Duration::from_secs(3));
+        assert_eq!(reloadable.current(&log), "third");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reload_explicit() {
+        let path =
+            std::env::temp_dir().join(format!("dfx-reload-explicit-{}.pem", std::process::id()));
+        let start = SystemTime::now() - Duration::from_secs(60);
+        write(&path, "first", start);
+        let config = TlsConfig {
+            certificate: Some(path.clone()),
+            ..Default::default()
+        };
+        let reloadable = Reloadable::new(config, read_certificate).unwrap();
+
+        write(&path, "second", start + Duration::from_secs(1));
+        assert_eq!(reloadable.current(&NoLogger), "first");
+        reloadable.reload().unwrap();
+        assert_eq!(reloadable.current(&NoLogger), "second");
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,174 @@
+use std::fs::File;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+use native_tls::{Certificate, HandshakeError, Identity, Protocol};
+
+use crate::connection::ConnectionError;
+use crate::session::SessionSettingsError;
+
+use super::TlsConfig;
+
+#[derive(Clone)]
+pub(crate) struct TlsConnector(native_tls::TlsConnector);
+
+#[derive(Clone)]
+pub(crate) struct TlsAcceptor(native_tls::TlsAcceptor);
+
+pub(crate) struct TlsStream(native_tls::TlsStream<TcpStream>);
+
+impl std::fmt::Debug for TlsConnector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::fmt::Debug for TlsAcceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsAcceptor").finish()
+    }
+}
+
+impl TlsConnector {
+    pub(crate) fn new(config: &TlsConfig) -> Result<Self, SessionSettingsError> {
+        let mut builder = native_tls::TlsConnector::builder();
+        if config.certificate.is_some() {
+            builder.identity(identity(config)?);
+        }
+        builder.min_protocol_version(protocol(config.min_protocol.as_deref()));
+        builder.max_protocol_version(protocol(config.max_protocol.as_deref()));
+        builder.use_sni(config.use_sni);
+        builder.danger_accept_invalid_certs(config.accept_invalid_certs);
+        builder.danger_accept_invalid_hostnames(config.accept_invalid_hostnames);
+        builder.disable_built_in_roots(config.disable_built_in_roots);
+        if let Some(ca_certificate) = config.ca_certificate.as_ref() {
+            let buf = read_file(ca_certificate, "SSLCACertificate")?;
+            let certificate = Certificate::from_pem(&buf)
+                .map_err(|e| SessionSettingsError::TlsError(format!("SSLCACertificate: {e}")))?;
+            builder.add_root_certificate(certificate);
+        }
+        let connector = builder
+            .build()
+            .map_err(|e| SessionSettingsError::TlsError(e.to_string()))?;
+        Ok(TlsConnector(connector))
+    }
+
+    pub(crate) fn connect(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> Result<TlsStream, ConnectionError> {
+        self.0
+            .connect(domain, stream)
+            .map(TlsStream)
+            .map_err(handshake_error)
+    }
+}
+
+impl TlsAcceptor {
+    pub(crate) fn new(config: &TlsConfig) -> Result<Self, SessionSettingsError> {
+        if config.require_client_certificate {
+            return Err(SessionSettingsError::TlsError(
+                "SSLRequireClientCertificate is not supported by the native-tls backend, enable the rustls feature".into(),
+            ));
+        }
+        let mut builder = native_tls::TlsAcceptor::builder(identity(config)?);
+        builder.min_protocol_version(protocol(config.min_protocol.as_deref()));
+        builder.max_protocol_version(protocol(config.max_protocol.as_deref()));
+        let acceptor = builder
+            .build()
+            .map_err(|e| SessionSettingsError::TlsError(e.to_string()))?;
+        Ok(TlsAcceptor(acceptor))
+    }
+
+    pub(crate) fn accept(&self, stream: TcpStream) -> Result<TlsStream, ConnectionError> {
+        self.0
+            .accept(stream)
+            .map(TlsStream)
+            .map_err(handshake_error)
+    }
+}
+
+impl TlsStream {
+    pub(crate) fn get_ref(&self) -> &TcpStream {
+        self.0.get_ref()
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut TcpStream {
+        self.0.get_mut()
+    }
+
+    pub(crate) fn shutdown(&mut self) -> std::io::Result<()> {
+        self.0.shutdown()
+    }
+
+    /// DER encoded certificate presented by the peer.
+    pub(crate) fn peer_certificate(&self) -> Option<Vec<u8>> {
+        self.0
+            .peer_certificate()
+            .ok()
+            .flatten()
+            .and_then(|certificate| certificate.to_der().ok())
+    }
+}
+
+impl Read for TlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for TlsStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+fn handshake_error(err: HandshakeError<TcpStream>) -> ConnectionError {
+    match err {
+        HandshakeError::Failure(e) => ConnectionError::Tls(e.to_string()),
+        HandshakeError::WouldBlock(_) => {
+            ConnectionError::Tls("handshake interrupted, socket would block".into())
+        }
+    }
+}
+
+fn identity(config: &TlsConfig) -> Result<Identity, SessionSettingsError> {
+    let path = config
+        .certificate
+        .as_ref()
+        .ok_or_else(|| SessionSettingsError::TlsError("SSLCertificate must be set".into()))?;
+    let buf = read_file(path, "SSLCertificate")?;
+    let certificate = Certificate::from_pem(&buf)
+        .and_then(|certificate| certificate.to_der())
+        .map_err(|e| SessionSettingsError::TlsError(format!("SSLCertificate: {e}")))?;
+    Identity::from_pkcs12(
+        &certificate,
+        config.certificate_password.as_deref().unwrap_or_default(),
+    )
+    .map_err(|e| SessionSettingsError::TlsError(format!("SSLCertificate: {e}")))
+}
+
+fn read_file(path: &std::path::Path, setting: &str) -> Result<Vec<u8>, SessionSettingsError> {
+    let mut buf = vec![];
+    File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut buf))
+        .map_err(|e| {
+            SessionSettingsError::TlsError(format!("{setting} {}: {e}", path.display()))
+        })?;
+    Ok(buf)
+}
+
+fn protocol(protocol: Option<&str>) -> Option<Protocol> {
+    match protocol? {
+        "TLSv1.0" => Some(Protocol::Tlsv10),
+        "TLSv1.1" => Some(Protocol::Tlsv11),
+        "TLSv1.2" => Some(Protocol::Tlsv12),
+        "SSLv3" => Some(Protocol::Sslv3),
+        _ => None,
+    }
+}
@@ -0,0 +1,454 @@
+use std::fs::File;
+use std::io::{BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::Arc;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{
+    CertificateError, ClientConfig, ClientConnection, DigitallySignedStruct, RootCertStore,
+    ServerConfig, ServerConnection, SignatureScheme, StreamOwned, SupportedProtocolVersion,
+};
+
+use crate::connection::ConnectionError;
+use crate::session::SessionSettingsError;
+
+use super::TlsConfig;
+
+#[derive(Clone)]
+pub(crate) struct TlsConnector(Arc<ClientConfig>);
+
+#[derive(Clone)]
+pub(crate) struct TlsAcceptor(Arc<ServerConfig>);
+
+pub(crate) enum TlsStream {
+    Client(Box<StreamOwned<ClientConnection, TcpStream>>),
+    Server(Box<StreamOwned<ServerConnection, TcpStream>>),
+}
+
+impl std::fmt::Debug for TlsConnector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsConnector").finish()
+    }
+}
+
+impl std::fmt::Debug for TlsAcceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsAcceptor").finish()
+    }
+}
+
+impl TlsConnector {
+    pub(crate) fn new(config: &TlsConfig) -> Result<Self, SessionSettingsError> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(provider.clone())
+            .with_protocol_versions(&protocol_versions(config)?)
+            .map_err(|e| SessionSettingsError::TlsError(e.to_string()))?;
+
+        let mut roots = RootCertStore::empty();
+        if !config.disable_built_in_roots {
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+        if let Some(ca_certificate) = config.ca_certificate.as_ref() {
+            for certificate in load_certificates(ca_certificate, "SSLCACertificate")? {
+                roots.add(certificate).map_err(|e| {
+                    SessionSettingsError::TlsError(format!("SSLCACertificate: {e}"))
+                })?;
+            }
+        }
+
+        let builder = if config.accept_invalid_certs {
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(AcceptInvalid {
+                    inner: None,
+                    provider,
+                }))
+        } else if config.accept_invalid_hostnames {
+            let inner =
+                WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+                    .build()
+                    .map_err(|e| SessionSettingsError::TlsError(e.to_string()))?;
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(AcceptInvalid {
+                    inner: Some(inner),
+                    provider,
+                }))
+        } else {
+            builder.with_root_certificates(roots)
+        };
+
+        let mut client_config = match config.certificate.as_ref() {
+            Some(certificate) => {
+                let chain = load_certificates(certificate, "SSLCertificate")?;
+                let key = load_private_key(certificate)?;
+                builder
+                    .with_client_auth_cert(chain, key)
+                    .map_err(|e| SessionSettingsError::TlsError(format!("SSLCertificate: {e}")))?
+            }
+            None => builder.with_no_client_auth(),
+        };
+        client_config.enable_sni = config.use_sni;
+        Ok(TlsConnector(Arc::new(client_config)))
+    }
+
+    pub(crate) fn connect(
+        &self,
+        domain: &str,
+        mut stream: TcpStream,
+    ) -> Result<TlsStream, ConnectionError> {
+        let server_name = ServerName::try_from(domain.to_string())
+            .map_err(|e| ConnectionError::Tls(format!("{domain}: {e}")))?;
+        let mut connection = ClientConnection::new(self.0.clone(), server_name)
+            .map_err(|e| ConnectionError::Tls(e.to_string()))?;
+        while connection.is_handshaking() {
+            connection
+                .complete_io(&mut stream)
+                .map_err(|e| ConnectionError::Tls(e.to_string()))?;
+        }
+        Ok(TlsStream::Client(Box::new(StreamOwned::new(
+            connection, stream,
+        ))))
+    }
+}
+
+impl TlsAcceptor {
+    pub(crate) fn new(config: &TlsConfig) -> Result<Self, SessionSettingsError> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_protocol_versions(&protocol_versions(config)?)
+            .map_err(|e| SessionSettingsError::TlsError(e.to_string()))?;
+
+        let builder = if config.require_client_certificate {
+            let ca_certificate = config.ca_certificate.as_ref().ok_or_else(|| {
+                SessionSettingsError::TlsError(
+                    "SSLCACertificate must be set when SSLRequireClientCertificate=Y".into(),
+                )
+            })?;
+            let mut roots = RootCertStore::empty();
+            for certificate in load_certificates(ca_certificate, "SSLCACertificate")? {
+                roots.add(certificate).map_err(|e| {
+                    SessionSettingsError::TlsError(format!("SSLCACertificate: {e}"))
+                })?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .map_err(|e| SessionSettingsError::TlsError(e.to_string()))?;
+            builder.with_client_cert_verifier(verifier)
+        } else {
+            builder.with_no_client_auth()
+        };
+
+        let certificate = config
+            .certificate
+            .as_ref()
+            .ok_or_else(|| SessionSettingsError::TlsError("SSLCertificate must be set".into()))?;
+        let chain = load_certificates(certificate, "SSLCertificate")?;
+        let key = load_private_key(certificate)?;
+        let server_config = builder
+            .with_single_cert(chain, key)
+            .map_err(|e| SessionSettingsError::TlsError(format!("SSLCertificate: {e}")))?;
+        Ok(TlsAcceptor(Arc::new(server_config)))
+    }
+
+    pub(crate) fn accept(&self, mut stream: TcpStream) -> Result<TlsStream, ConnectionError> {
+        let mut connection = ServerConnection::new(self.0.clone())
+            .map_err(|e| ConnectionError::Tls(e.to_string()))?;
+        while connection.is_handshaking() {
+            connection
+                .complete_io(&mut stream)
+                .map_err(|e| ConnectionError::Tls(e.to_string()))?;
+        }
+        Ok(TlsStream::Server(Box::new(StreamOwned::new(
+            connection, stream,
+        ))))
+    }
+}
+
+impl TlsStream {
+    pub(crate) fn get_ref(&self) -> &TcpStream {
+        match self {
+            TlsStream::Client(stream) => stream.get_ref(),
+            TlsStream::Server(stream) => stream.get_ref(),
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut TcpStream {
+        match self {
+            TlsStream::Client(stream) => stream.get_mut(),
+            TlsStream::Server(stream) => stream.get_mut(),
+        }
+    }
+
+    pub(crate) fn shutdown(&mut self) -> std::io::Result<()> {
+        match self {
+            TlsStream::Client(stream) => {
+                stream.conn.send_close_notify();
+                stream.flush()
+            }
+            TlsStream::Server(stream) => {
+                stream.conn.send_close_notify();
+                stream.flush()
+            }
+        }
+    }
+
+    /// DER encoded certificate presented by the peer.
+    pub(crate) fn peer_certificate(&self) -> Option<Vec<u8>> {
+        let certificates = match self {
+            TlsStream::Client(stream) => stream.conn.peer_certificates(),
+            TlsStream::Server(stream) => stream.conn.peer_certificates(),
+        };
+        certificates
+            .and_then(|certificates| certificates.first())
+            .map(|certificate| certificate.to_vec())
+    }
+}
+
+impl Read for TlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = match self {
+            TlsStream::Client(stream) => stream.read(buf),
+            TlsStream::Server(stream) => stream.read(buf),
+        };
+        match read {
+            // Match the plain tcp behaviour when the peer goes away without a close_notify.
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(0),
+            read => read,
+        }
+    }
+}
+
+impl Write for TlsStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            TlsStream::Client(stream) => stream.write(buf),
+            TlsStream::Server(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            TlsStream::Client(stream) => stream.flush(),
+            TlsStream::Server(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Verifier used for `SSLAcceptInvalidCerts` and `SSLAcceptInvalidHostnames`.
+///
+/// Without an inner verifier every certificate is accepted,
+/// otherwise only certificates that are not valid for the server name are accepted.
+#[derive(Debug)]
+struct AcceptInvalid {
+    inner: Option<Arc<WebPkiServerVerifier>>,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for AcceptInvalid {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let Some(inner) = self.inner.as_ref() else {
+            return Ok(ServerCertVerified::assertion());
+        };
+        match inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now) {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. },
+            )) => Ok(ServerCertVerified::assertion()),
+            result => result,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+fn protocol_versions(
+    config: &TlsConfig,
+) -> Result<Vec<&'static SupportedProtocolVersion>, SessionSettingsError> {
+    fn version(setting: &str, value: Option<&str>) -> Result<Option<u8>, SessionSettingsError> {
+        match value {
+            None => Ok(None),
+            Some("TLSv1.2") => Ok(Some(2)),
+            Some("TLSv1.3") => Ok(Some(3)),
+            Some(value) => Err(SessionSettingsError::InvalidValue {
+                setting: setting.into(),
+                value: value.into(),
+            }),
+        }
+    }
+    let min = version("SSLMinProtocol", config.min_protocol.as_deref())?.unwrap_or(2);
+    let max = version("SSLMaxProtocol", config.max_protocol.as_deref())?.unwrap_or(3);
+    let versions: Vec<_> = [(2, &rustls::version::TLS12), (3, &rustls::version::TLS13)]
+        .into_iter()
+        .filter(|(v, _)| (min..=max).contains(v))
+        .map(|(_, version)| version)
+        .collect();
+    if versions.is_empty() {
+        return Err(SessionSettingsError::TlsError(
+            "SSLMinProtocol must not be greater than SSLMaxProtocol".into(),
+        ));
+    }
+    Ok(versions)
+}
+
+fn open(path: &Path, setting: &str) -> Result<BufReader<File>, SessionSettingsError> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| SessionSettingsError::TlsError(format!("{setting} {}: {e}", path.display())))
+}
+
+fn load_certificates(
+    path: &Path,
+    setting: &str,
+) -> Result<Vec<CertificateDer<'static>>, SessionSettingsError> {
+    let certificates = rustls_pemfile::certs(&mut open(path, setting)?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            SessionSettingsError::TlsError(format!("{setting} {}: {e}", path.display()))
+        })?;
+    if certificates.is_empty() {
+        return Err(SessionSettingsError::TlsError(format!(
+            "{setting} {}: no certificates found",
+            path.display()
+        )));
+    }
+    Ok(certificates)
+}
+
+/// The private key is expected in the same pem file as the certificate chain.
+fn load_private_key(path: &Path) -> Result<PrivateKeyDer<'static>, SessionSettingsError> {
+    rustls_pemfile::private_key(&mut open(path, "SSLCertificate")?)
+        .map_err(|e| {
+            SessionSettingsError::TlsError(format!("SSLCertificate {}: {e}", path.display()))
+        })?
+        .ok_or_else(|| {
+            SessionSettingsError::TlsError(format!(
+                "SSLCertificate {}: no private key found",
+                path.display()
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    use crate::connection::{ConnectionError, PeerIdentity, TlsConfig};
+
+    use super::{TlsAcceptor, TlsConnector};
+
+    const TLS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/tls");
+
+    fn acceptor() -> TlsAcceptor {
+        TlsAcceptor::new(&TlsConfig {
+            certificate: Some(format!("{TLS_DIR}/server.pem").into()),
+            ca_certificate: Some(format!("{TLS_DIR}/ca.pem").into()),
+            require_client_certificate: true,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn connector(certificate: Option<&str>) -> TlsConnector {
+        TlsConnector::new(&TlsConfig {
+            certificate: certificate.map(|c| format!("{TLS_DIR}/{c}").into()),
+            ca_certificate: Some(format!("{TLS_DIR}/ca.pem").into()),
+            disable_built_in_roots: true,
+            use_sni: true,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn mutual_tls_handshake() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let acceptor = acceptor();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut stream = acceptor.accept(stream).unwrap();
+            stream.write_all(b"8=FIX").unwrap();
+            stream.flush().unwrap();
+            PeerIdentity::from_der(&stream.peer_certificate().unwrap()).unwrap()
+        });
+
+        let stream = TcpStream::connect(addr).unwrap();
+        let mut stream = connector(Some("client1.pem"))
+            .connect("localhost", stream)
+            .unwrap();
+        let mut buf = [0; 5];
+        stream.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"8=FIX");
+
+        let identity = server.join().unwrap();
+        assert!(identity.matches("CLIENT1"));
+    }
+
+    #[test]
+    fn handshake_without_client_certificate() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let acceptor = acceptor();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            acceptor.accept(stream).map(|_| ())
+        });
+
+        let stream = TcpStream::connect(addr).unwrap();
+        // With TLS1.3 the client only learns about the rejection on its first read.
+        if let Ok(mut stream) = connector(None).connect("localhost", stream) {
+            let mut buf = [0; 1];
+            assert!(!matches!(stream.read(&mut buf), Ok(n) if n > 0));
+        }
+        assert!(matches!(
+            server.join().unwrap(),
+            Err(ConnectionError::Tls(_))
+        ));
+    }
+}
@@ -5,6 +5,8 @@
 // #![allow(clippy::too_many_arguments)]
 // TODO remove above once closer to completed
 
+#[cfg(all(feature = "admin", unix))]
+pub mod admin;
 pub mod connection;
 pub(crate) mod fields;
 pub mod logging;
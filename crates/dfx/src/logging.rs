@@ -220,3 +220,37 @@ impl LogFactory for MacroLogFactory {
         MacroLogger::new(session_id, path)
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::Logger;
+
+    /// Keeps everything logged, shared between clones.
+    #[derive(Debug, Clone, Default)]
+    pub(crate) struct TestLogger {
+        events: Arc<Mutex<Vec<String>>>,
+        outgoing: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl TestLogger {
+        pub(crate) fn events(&self) -> Vec<String> {
+            self.events.lock().unwrap().clone()
+        }
+
+        pub(crate) fn outgoing(&self) -> Vec<String> {
+            self.outgoing.lock().unwrap().clone()
+        }
+    }
+
+    impl Logger for TestLogger {
+        fn on_incoming(&self, _incoming: &str) {}
+        fn on_outgoing(&self, outgoing: &str) {
+            self.outgoing.lock().unwrap().push(outgoing.to_string());
+        }
+        fn on_event(&self, event: &str) {
+            self.events.lock().unwrap().push(event.to_string());
+        }
+    }
+}
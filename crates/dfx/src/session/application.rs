@@ -1,7 +1,13 @@
+use chrono::{DateTime, Utc};
+use dfx_base::data_dictionary::Violation;
 use dfx_base::field_map::FieldMapError;
 use dfx_base::message::Message;
 use dfx_base::session_id::SessionId;
 
+use crate::session::MessageSigner;
+use crate::session::ResendProgress;
+use crate::session::ThrottleAction;
+
 #[derive(Debug, Clone)]
 pub enum ApplicationError {
     // DoNotAccept,
@@ -12,7 +18,10 @@ pub enum ApplicationError {
 
 #[derive(Debug, Clone)]
 pub enum FromAppError {
-    UnknownMessageType { message: Message, msg_type: String },
+    UnknownMessageType {
+        message: Box<Message>,
+        msg_type: String,
+    },
     FieldMapError(FieldMapError),
 }
 
@@ -23,6 +32,18 @@ pub struct LogonReject {
 #[derive(Debug, Clone)]
 pub struct DoNotAccept;
 
+/// What to do with a stored application message when the counterparty requests a resend.
+#[derive(Debug, Clone)]
+pub enum ResendAction {
+    /// Resend the stored message with PossDupFlag set.
+    Resend,
+    /// Resend this message instead, the session keeps the original MsgSeqNum and sets the resend
+    /// header fields.
+    ResendModified(Box<Message>),
+    /// Do not resend, the sequence number is covered by a SequenceReset-GapFill.
+    GapFill,
+}
+
 impl From<FieldMapError> for ApplicationError {
     fn from(e: FieldMapError) -> Self {
         ApplicationError::FieldMapError(e)
@@ -55,6 +76,54 @@ pub trait Application: Send {
         session_id: &SessionId,
     ) -> Result<(), ApplicationError>;
     fn from_app(&mut self, message: &Message, session_id: &SessionId) -> Result<(), FromAppError>;
+    /// Called with every outgoing Logon after `to_admin`, once MsgSeqNum and SendingTime are
+    /// final. Use this to add a signature, e.g. in RawData, that has to cover the header.
+    fn sign_logon(
+        &mut self,
+        _logon: &mut Message,
+        _session_id: &SessionId,
+    ) -> Result<(), FieldMapError> {
+        Ok(())
+    }
+    /// Signs outgoing messages and, if the signer is set up for it, authenticates received
+    /// Logons, see
+    /// [`HmacSigner`](crate::session::HmacSigner) with the `hmac` feature.
+    fn message_signer(&self) -> Option<&dyn MessageSigner> {
+        None
+    }
+    /// Called for every stored application message that is about to be resent, `sending_time` is
+    /// the SendingTime of the original message. See [`ResendPolicy`](crate::session::ResendPolicy)
+    /// for common policies.
+    fn on_resend(
+        &mut self,
+        _message: &Message,
+        _sending_time: DateTime<Utc>,
+        _session_id: &SessionId,
+    ) -> ResendAction {
+        ResendAction::Resend
+    }
+    /// Called when a ResendRequest or the next chunk of one is sent, and when the gap is filled.
+    fn on_resend_progress(&mut self, _progress: &ResendProgress, _session_id: &SessionId) {}
+    /// Called when an outgoing application message exceeds the configured throttle, `action`
+    /// is what happens to it.
+    fn on_throttle(
+        &mut self,
+        _message: &Message,
+        _action: ThrottleAction,
+        _session_id: &SessionId,
+    ) {
+    }
+    /// Called with `UseDataDictionary=Report` when a received message has validation errors,
+    /// `errors` has all of them with their group paths. The message is processed as if it were
+    /// valid. [Advisory](Violation::is_advisory) errors are missing required fields in group
+    /// entries, which `UseDataDictionary=Y` does not reject.
+    fn on_validation_report(
+        &mut self,
+        _message: &Message,
+        _errors: &[Violation],
+        _session_id: &SessionId,
+    ) {
+    }
 }
 
 pub trait ApplicationExt: Application {
@@ -0,0 +1,172 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use dfx_base::message::Message;
+use dfx_base::session_id::SessionId;
+
+/// Whether a message continues through the session after an [`Interceptor`] has seen it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intercept {
+    Continue,
+    /// Inbound: the message is ignored as if it was never received.
+    /// Outbound before send: the message is not sent and no MsgSeqNum is used.
+    /// Outbound after persist: the message is stored but not written to the socket.
+    Drop,
+}
+
+/// Hooks into the inbound and outbound paths of every session, independent of the
+/// [`Application`](crate::session::Application). Registered with
+/// `SocketAcceptor::with_interceptor` or `SocketInitiator::with_interceptor`, interceptors are
+/// called in the order they were registered.
+///
+/// Inbound messages pass `inbound_raw`, `before_validation` and `after_validation`, outbound
+/// messages pass `before_send` and `after_persist`. All methods default to passing the message
+/// through unchanged.
+///
+/// Resent messages pass `before_send` and `after_persist` again with PossDupFlag and
+/// OrigSendingTime set, they are not persisted again. Dropping a resent message in `before_send`
+/// replaces it with a gap fill.
+pub trait Interceptor: Send + Sync {
+    /// The bytes as received, before they are parsed.
+    fn inbound_raw(&self, _bytes: &mut Vec<u8>, _session_id: &SessionId) -> Intercept {
+        Intercept::Continue
+    }
+
+    /// The parsed message, before it is validated against the data dictionary.
+    fn before_validation(&self, _message: &mut Message, _session_id: &SessionId) -> Intercept {
+        Intercept::Continue
+    }
+
+    /// The validated message, before the session and application handle it.
+    fn after_validation(&self, _message: &Message, _session_id: &SessionId) {}
+
+    /// An outbound message after `to_admin` / `to_app`, before it is signed and persisted.
+    /// The header, including MsgSeqNum, is already initialized.
+    fn before_send(&self, _message: &mut Message, _session_id: &SessionId) -> Intercept {
+        Intercept::Continue
+    }
+
+    /// An outbound message after it was persisted, `raw` is written to the socket as is.
+    fn after_persist(
+        &self,
+        _message: &Message,
+        _raw: &mut String,
+        _session_id: &SessionId,
+    ) -> Intercept {
+        Intercept::Continue
+    }
+}
+
+/// The interceptors of a session, stops at the first interceptor that drops a message.
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(Vec<Arc<dyn Interceptor>>);
+
+impl Debug for Interceptors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Interceptors").field(&self.0.len()).finish()
+    }
+}
+
+impl Interceptors {
+    pub(crate) fn push(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.0.push(interceptor);
+    }
+
+    pub(crate) fn inbound_raw(&self, bytes: &mut Vec<u8>, session_id: &SessionId) -> Intercept {
+        self.chain(|interceptor| interceptor.inbound_raw(bytes, session_id))
+    }
+
+    pub(crate) fn before_validation(
+        &self,
+        message: &mut Message,
+        session_id: &SessionId,
+    ) -> Intercept {
+        self.chain(|interceptor| interceptor.before_validation(message, session_id))
+    }
+
+    pub(crate) fn after_validation(&self, message: &Message, session_id: &SessionId) {
+        for interceptor in &self.0 {
+            interceptor.after_validation(message, session_id);
+        }
+    }
+
+    pub(crate) fn before_send(&self, message: &mut Message, session_id: &SessionId) -> Intercept {
+        self.chain(|interceptor| interceptor.before_send(message, session_id))
+    }
+
+    pub(crate) fn after_persist(
+        &self,
+        message: &Message,
+        raw: &mut String,
+        session_id: &SessionId,
+    ) -> Intercept {
+        self.chain(|interceptor| interceptor.after_persist(message, raw, session_id))
+    }
+
+    fn chain<F: FnMut(&dyn Interceptor) -> Intercept>(&self, mut f: F) -> Intercept {
+        for interceptor in &self.0 {
+            if f(interceptor.as_ref()) == Intercept::Drop {
+                return Intercept::Drop;
+            }
+        }
+        Intercept::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use dfx_base::message::Message;
+    use dfx_base::session_id::SessionId;
+    use dfx_base::tags;
+
+    use super::{Intercept, Interceptor, Interceptors};
+
+    struct Enrich;
+    impl Interceptor for Enrich {
+        fn before_send(&self, message: &mut Message, _session_id: &SessionId) -> Intercept {
+            message.set_tag_value(tags::Account, "ACC");
+            Intercept::Continue
+        }
+    }
+
+    #[derive(Default)]
+    struct DropHeartbeats(AtomicUsize);
+    impl Interceptor for DropHeartbeats {
+        fn before_send(&self, message: &mut Message, _session_id: &SessionId) -> Intercept {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            match message.header().get_string(tags::MsgType) {
+                Ok(msg_type) if msg_type == "0" => Intercept::Drop,
+                _ => Intercept::Continue,
+            }
+        }
+    }
+
+    #[test]
+    fn interceptor_chain() {
+        let session_id = SessionId::new("FIX.4.4", "SENDER", "", "", "TARGET", "", "");
+        let drop_heartbeats = Arc::new(DropHeartbeats::default());
+        let mut interceptors = Interceptors::default();
+        interceptors.push(drop_heartbeats.clone());
+        interceptors.push(Arc::new(Enrich));
+
+        let mut order = Message::default();
+        order.header_mut().set_tag_value(tags::MsgType, "D");
+        assert_eq!(
+            interceptors.before_send(&mut order, &session_id),
+            Intercept::Continue
+        );
+        assert_eq!(order.get_string(tags::Account).unwrap(), "ACC");
+
+        let mut heartbeat = Message::default();
+        heartbeat.header_mut().set_tag_value(tags::MsgType, "0");
+        assert_eq!(
+            interceptors.before_send(&mut heartbeat, &session_id),
+            Intercept::Drop
+        );
+        assert!(!heartbeat.is_field_set(tags::Account));
+        assert_eq!(drop_heartbeats.0.load(Ordering::Relaxed), 2);
+    }
+}
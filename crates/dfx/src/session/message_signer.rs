@@ -0,0 +1,259 @@
+use dfx_base::field_map::{FieldMapError, Tag};
+use dfx_base::message::Message;
+use dfx_base::session_id::SessionId;
+
+use crate::fields::MsgType;
+
+/// Signs outgoing messages and optionally authenticates the counterparty's Logon, returned from
+/// [`Application::message_signer`](crate::session::Application::message_signer).
+///
+/// `sign` is called after `to_admin` / `to_app` and `sign_logon`, once the header is fully
+/// initialized, so the signature covers the MsgSeqNum and SendingTime that are sent.
+/// Resent messages are signed again after PossDupFlag and OrigSendingTime are set.
+pub trait MessageSigner {
+    /// Whether messages of `msg_type` are signed, only the Logon by default.
+    fn signs(&self, msg_type: &str) -> bool {
+        msg_type == MsgType::LOGON
+    }
+
+    fn sign(&self, message: &mut Message, session_id: &SessionId) -> Result<(), FieldMapError>;
+
+    /// Checks the signature of a received Logon, the session logs out when this returns `false`.
+    /// Accepts every Logon by default, many venues do not sign theirs.
+    fn authenticate(&self, _logon: &Message, _session_id: &SessionId) -> bool {
+        true
+    }
+}
+
+/// Part of the string that is signed by [`HmacSigner`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrehashPart {
+    /// The value of a header or body field, empty when the field is not set.
+    Field(Tag),
+    Literal(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureEncoding {
+    Base64,
+    Hex,
+}
+
+/// HMAC-SHA256 over a list of fields joined by a separator.
+///
+/// The default signs the Logon over SendingTime, MsgType, MsgSeqNum, SenderCompID and
+/// TargetCompID joined by SOH, and puts the base64 signature in RawData / RawDataLength.
+/// Received Logons are only checked with [`HmacSigner::with_authentication`].
+///
+/// ```ignore
+/// // Coinbase also includes the Password in the signature
+/// let signer = HmacSigner::new(secret).with_field(tags::Password);
+/// ```
+#[cfg(feature = "hmac")]
+#[derive(Clone)]
+pub struct HmacSigner {
+    key: Vec<u8>,
+    prehash: Vec<PrehashPart>,
+    separator: String,
+    encoding: SignatureEncoding,
+    signature_tag: Tag,
+    signature_length_tag: Option<Tag>,
+    msg_types: Vec<String>,
+    authenticate: bool,
+}
+
+#[cfg(feature = "hmac")]
+impl std::fmt::Debug for HmacSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HmacSigner")
+            .field("prehash", &self.prehash)
+            .field("separator", &self.separator)
+            .field("encoding", &self.encoding)
+            .field("signature_tag", &self.signature_tag)
+            .field("signature_length_tag", &self.signature_length_tag)
+            .field("msg_types", &self.msg_types)
+            .field("authenticate", &self.authenticate)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "hmac")]
+impl HmacSigner {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        use dfx_base::tags;
+        HmacSigner {
+            key: key.into(),
+            prehash: vec![
+                PrehashPart::Field(tags::SendingTime),
+                PrehashPart::Field(tags::MsgType),
+                PrehashPart::Field(tags::MsgSeqNum),
+                PrehashPart::Field(tags::SenderCompID),
+                PrehashPart::Field(tags::TargetCompID),
+            ],
+            separator: "\x01".into(),
+            encoding: SignatureEncoding::Base64,
+            signature_tag: tags::RawData,
+            signature_length_tag: Some(tags::RawDataLength),
+            msg_types: vec![MsgType::LOGON.into()],
+            authenticate: false,
+        }
+    }
+
+    /// Also requires the counterparty's Logon to carry a valid signature made with the same key.
+    #[must_use]
+    pub fn with_authentication(mut self) -> Self {
+        self.authenticate = true;
+        self
+    }
+
+    /// Replaces the signed parts.
+    #[must_use]
+    pub fn prehash(mut self, prehash: Vec<PrehashPart>) -> Self {
+        self.prehash = prehash;
+        self
+    }
+
+    /// Appends a field to the signed parts.
+    #[must_use]
+    pub fn with_field(mut self, tag: Tag) -> Self {
+        self.prehash.push(PrehashPart::Field(tag));
+        self
+    }
+
+    #[must_use]
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    #[must_use]
+    pub fn encoding(mut self, encoding: SignatureEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// The field the signature is written to, and optionally its length field.
+    #[must_use]
+    pub fn signature_tag(mut self, tag: Tag, length_tag: Option<Tag>) -> Self {
+        self.signature_tag = tag;
+        self.signature_length_tag = length_tag;
+        self
+    }
+
+    /// The message types that are signed, e.g. `&["A", "D"]`.
+    #[must_use]
+    pub fn msg_types(mut self, msg_types: &[&str]) -> Self {
+        self.msg_types = msg_types.iter().map(|&msg_type| msg_type.into()).collect();
+        self
+    }
+
+    pub fn signature(&self, message: &Message) -> String {
+        let prehash = self
+            .prehash
+            .iter()
+            .map(|part| match part {
+                PrehashPart::Field(tag) => message
+                    .header()
+                    .get_string(*tag)
+                    .or_else(|_| message.get_string(*tag))
+                    .unwrap_or_default(),
+                PrehashPart::Literal(value) => value.clone(),
+            })
+            .collect::<Vec<String>>()
+            .join(&self.separator);
+
+        let mut hmac = hmac_sha256::HMAC::new(&self.key);
+        hmac.update(prehash.as_bytes());
+        let bytes = hmac.finalize();
+        match self.encoding {
+            SignatureEncoding::Base64 => {
+                use base64::Engine;
+                base64::engine::general_purpose::STANDARD.encode(bytes)
+            }
+            SignatureEncoding::Hex => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "hmac")]
+impl MessageSigner for HmacSigner {
+    fn signs(&self, msg_type: &str) -> bool {
+        self.msg_types.iter().any(|signed| signed == msg_type)
+    }
+
+    fn sign(&self, message: &mut Message, _session_id: &SessionId) -> Result<(), FieldMapError> {
+        let signature = self.signature(message);
+        if let Some(length_tag) = self.signature_length_tag {
+            message.set_tag_value(length_tag, signature.len());
+        }
+        message.set_tag_value(self.signature_tag, signature);
+        Ok(())
+    }
+
+    fn authenticate(&self, logon: &Message, _session_id: &SessionId) -> bool {
+        if !self.authenticate {
+            return true;
+        }
+        let expected = self.signature(logon);
+        let received = logon.get_string(self.signature_tag).unwrap_or_default();
+        // Compare every byte so the time taken does not leak the signature
+        expected.len() == received.len()
+            && expected
+                .bytes()
+                .zip(received.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+#[cfg(all(test, feature = "hmac"))]
+mod tests {
+    use dfx_base::message::Message;
+    use dfx_base::session_id::SessionId;
+    use dfx_base::tags;
+
+    use super::{HmacSigner, MessageSigner, SignatureEncoding};
+
+    fn logon() -> Message {
+        let mut message = Message::default();
+        message.header_mut().set_tag_value(tags::MsgType, "A");
+        message.header_mut().set_tag_value(tags::MsgSeqNum, "1");
+        message
+            .header_mut()
+            .set_tag_value(tags::SenderCompID, "SENDER");
+        message
+            .header_mut()
+            .set_tag_value(tags::TargetCompID, "TARGET");
+        message
+            .header_mut()
+            .set_tag_value(tags::SendingTime, "20230605-12:00:00.000");
+        message.set_tag_value(tags::Password, "secret");
+        message
+    }
+
+    #[test]
+    fn hmac_signer() {
+        let session_id = SessionId::new("FIX.4.2", "SENDER", "", "", "TARGET", "", "");
+        let signer = HmacSigner::new("key")
+            .with_field(tags::Password)
+            .encoding(SignatureEncoding::Hex);
+        assert!(signer.signs("A"));
+        assert!(!signer.signs("D"));
+
+        let mut message = logon();
+        signer.sign(&mut message, &session_id).unwrap();
+        assert_eq!(
+            message.get_string(tags::RawData).unwrap(),
+            "d13bbd022769ff01f3f2df9c3ff1314696d9d4d0dfd6ddf720b2be8fc8a4e327"
+        );
+        assert_eq!(message.get_int(tags::RawDataLength).unwrap(), 64);
+        assert!(signer.authenticate(&message, &session_id));
+        assert!(signer.authenticate(&logon(), &session_id));
+
+        let signer = signer.with_authentication();
+        assert!(signer.authenticate(&message, &session_id));
+        assert!(!signer.authenticate(&logon(), &session_id));
+        message.header_mut().set_tag_value(tags::MsgSeqNum, "2");
+        assert!(!signer.authenticate(&message, &session_id));
+    }
+}
@@ -2,13 +2,27 @@ mod session;
 pub use session::*;
 mod settings;
 pub use settings::*;
+mod session_handle;
+pub use session_handle::*;
+mod send_ticket;
+pub use send_ticket::*;
 mod session_state;
 pub(crate) use session_state::*;
 mod session_schedule;
-pub(crate) use session_schedule::*;
+pub use session_schedule::*;
 mod application;
 pub use application::*;
 mod responder;
 pub(crate) use responder::*;
+mod interceptor;
+pub use interceptor::*;
+mod message_signer;
+pub use message_signer::*;
+mod resend_policy;
+pub use resend_policy::*;
 mod reset_range;
-pub(crate) use reset_range::*;
+pub use reset_range::*;
+mod throttle;
+pub use throttle::*;
+#[cfg(test)]
+pub(crate) mod test_session;
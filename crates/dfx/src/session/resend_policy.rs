@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use dfx_base::message::Message;
+use dfx_base::tags;
+
+use crate::session::ResendAction;
+
+/// Common resend decisions, to be called from [`Application::on_resend`](crate::session::Application::on_resend).
+///
+/// ```ignore
+/// fn on_resend(&mut self, message: &Message, sending_time: DateTime<Utc>, _: &SessionId) -> ResendAction {
+///     self.resend_policy.decide(message, sending_time, Utc::now())
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResendPolicy {
+    max_age: Option<Duration>,
+    never_resend: Vec<String>,
+}
+
+impl ResendPolicy {
+    /// Resends everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gap fill messages that were originally sent more than `max_age` ago.
+    pub fn gap_fill_older_than(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Gap fill all messages of `msg_type`, e.g. `"D"` to never resend a NewOrderSingle.
+    pub fn never_resend(mut self, msg_type: &str) -> Self {
+        self.never_resend.push(msg_type.into());
+        self
+    }
+
+    pub fn decide(
+        &self,
+        message: &Message,
+        sending_time: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> ResendAction {
+        let never_resend = message
+            .header()
+            .get_string(tags::MsgType)
+            .is_ok_and(|msg_type| self.never_resend.contains(&msg_type));
+        let too_old = self
+            .max_age
+            .is_some_and(|max_age| (now - sending_time).to_std().is_ok_and(|age| age > max_age));
+        if never_resend || too_old {
+            ResendAction::GapFill
+        } else {
+            ResendAction::Resend
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{TimeZone, Utc};
+    use dfx_base::message::Message;
+    use dfx_base::tags;
+
+    use super::ResendPolicy;
+    use crate::session::ResendAction;
+
+    fn message(msg_type: &str) -> Message {
+        let mut message = Message::default();
+        message.header_mut().set_tag_value(tags::MsgType, msg_type);
+        message
+    }
+
+    #[test]
+    fn resend_policy() {
+        let now = Utc.with_ymd_and_hms(2023, 6, 5, 12, 0, 0).unwrap();
+        let sent = Utc.with_ymd_and_hms(2023, 6, 5, 11, 59, 0).unwrap();
+        let policy = ResendPolicy::new()
+            .gap_fill_older_than(Duration::from_secs(30))
+            .never_resend("D");
+
+        let recent = now - chrono::Duration::seconds(10);
+        assert!(matches!(
+            policy.decide(&message("8"), recent, now),
+            ResendAction::Resend
+        ));
+        assert!(matches!(
+            policy.decide(&message("8"), sent, now),
+            ResendAction::GapFill
+        ));
+        assert!(matches!(
+            policy.decide(&message("D"), recent, now),
+            ResendAction::GapFill
+        ));
+        assert!(matches!(
+            ResendPolicy::new().decide(&message("D"), sent, now),
+            ResendAction::Resend
+        ));
+    }
+}
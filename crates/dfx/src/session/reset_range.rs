@@ -2,5 +2,23 @@
 pub(crate) struct ResetRange {
     pub(crate) begin_seq_num: u32,
     pub(crate) end_seq_num: u32,
+    pub(crate) chunk_begin_seq_num: u32,
     pub(crate) chunk_end_seq_num: Option<u32>,
 }
+
+/// Progress of an outstanding ResendRequest, see [`Application::on_resend_progress`](crate::session::Application::on_resend_progress).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResendProgress {
+    /// First sequence number of the gap.
+    pub begin_seq_num: u32,
+    /// Last sequence number of the gap, 0 or 999999 when requested up to infinity.
+    pub end_seq_num: u32,
+    /// First sequence number of the outstanding chunk.
+    pub chunk_begin_seq_num: u32,
+    /// Last sequence number of the outstanding chunk.
+    pub chunk_end_seq_num: u32,
+    /// Last sequence number received or gap filled by the counterparty.
+    pub last_received_seq_num: u32,
+    /// True once the whole gap has been received.
+    pub complete: bool,
+}
@@ -1,43 +1,46 @@
 use std::sync::mpsc;
 use std::sync::mpsc::{Receiver, Sender};
-use std::time::Duration;
+
+use crate::session::SendConfirmation;
 
 pub(crate) trait Responder: Send {
-    fn send(&mut self, message: String) -> bool;
+    /// Hands `message` to the connection, returns false when the connection is gone.
+    ///
+    /// True only means the connection took the message, it is written later on the
+    /// connection's thread. `confirmation` is resolved once the message is written or writing
+    /// it failed.
+    fn send(&mut self, message: String, confirmation: Option<SendConfirmation>) -> bool;
     fn disconnect(&mut self);
 }
 
 pub(crate) enum ResponderEvent {
-    Send(String),
+    Send(String, Option<SendConfirmation>),
     Disconnect,
 }
-pub(crate) enum ResponderResponse {
-    Sent(bool),
-}
 
+/// Queues messages for the [`SocketReactor`](crate::connection::SocketReactor), `send` returns
+/// true as long as the reactor is running.
 pub(crate) struct ChannelResponder {
     tx: Sender<ResponderEvent>,
-    rx: Receiver<ResponderResponse>,
 }
 
 impl ChannelResponder {
-    pub fn new() -> (Self, Receiver<ResponderEvent>, Sender<ResponderResponse>) {
+    pub fn new() -> (Self, Receiver<ResponderEvent>) {
         let (tx, out_rx) = mpsc::channel();
-        let (out_tx, rx) = mpsc::channel();
-        (ChannelResponder { tx, rx }, out_rx, out_tx)
+        (ChannelResponder { tx }, out_rx)
     }
 }
 
 impl Responder for ChannelResponder {
-    fn send(&mut self, message: String) -> bool {
-        match self.tx.send(ResponderEvent::Send(message)) {
-            Ok(()) => match self.rx.recv_timeout(Duration::from_millis(10)) {
-                Ok(response) => match response {
-                    ResponderResponse::Sent(sent) => sent,
-                },
-                Err(_e) => false,
-            },
-            Err(_) => false,
+    fn send(&mut self, message: String, confirmation: Option<SendConfirmation>) -> bool {
+        match self.tx.send(ResponderEvent::Send(message, confirmation)) {
+            Ok(()) => true,
+            Err(mpsc::SendError(event)) => {
+                if let ResponderEvent::Send(_, Some(confirmation)) = event {
+                    confirmation.not_written();
+                }
+                false
+            }
         }
     }
 
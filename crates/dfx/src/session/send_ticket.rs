@@ -0,0 +1,166 @@
+use std::io;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::time::Duration;
+
+use dfx_base::field_map::FieldMapError;
+use dfx_base::session_id::SessionId;
+
+/// A message that was sent through a [`SessionHandle`](crate::session::SessionHandle).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sent {
+    pub msg_seq_num: u32,
+    /// False when the message was persisted but not written to the socket, e.g. an
+    /// [`Interceptor`](crate::session::Interceptor) dropped it after persist or the connection
+    /// was lost. The counterparty receives it with its next ResendRequest.
+    pub written: bool,
+}
+
+/// Why a message sent through a [`SessionHandle`](crate::session::SessionHandle) was not sent.
+#[derive(Debug)]
+pub enum SendError {
+    NotLoggedOn(Box<SessionId>),
+    /// [`Application::to_app`](crate::session::Application::to_app) returned `DoNotSend`.
+    DoNotSend,
+    /// An [`Interceptor`](crate::session::Interceptor) dropped the message before it was sent.
+    Dropped,
+    /// The message was rejected with `ThrottleAction=Reject`.
+    Throttled,
+    InvalidMessage(FieldMapError),
+    /// The message was persisted as `msg_seq_num`, writing it to the socket failed.
+    Io {
+        msg_seq_num: u32,
+        error: io::Error,
+    },
+    /// The session disconnected before the message was sent.
+    Discarded,
+    /// [`SendTicket::wait`] timed out, the message may still be sent.
+    Timeout,
+}
+
+/// Resolves once the session has sent a message, returned by
+/// [`SessionHandle::try_send`](crate::session::SessionHandle::try_send).
+///
+/// Dropping the ticket does not cancel the message.
+///
+/// ```ignore
+/// let ticket = Session::send_to_session(&session_id, order)?;
+/// let sent = ticket.wait(Duration::from_secs(1))?;
+/// println!("Order sent as {}", sent.msg_seq_num);
+/// ```
+#[derive(Debug)]
+pub struct SendTicket {
+    receiver: Receiver<Result<Sent, SendError>>,
+}
+
+impl SendTicket {
+    /// Waits up to `timeout` for the message to be sent. Must not be called from the session's
+    /// own thread, e.g. from an [`Application`](crate::session::Application) callback.
+    pub fn wait(&self, timeout: Duration) -> Result<Sent, SendError> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => Err(SendError::Timeout),
+            Err(RecvTimeoutError::Disconnected) => Err(SendError::Discarded),
+        }
+    }
+
+    /// Returns the result without waiting, `None` while the message is not sent yet.
+    pub fn try_result(&self) -> Option<Result<Sent, SendError>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(SendError::Discarded)),
+        }
+    }
+}
+
+/// The sending side of a [`SendTicket`], travels with the message through the session and the
+/// connection. Dropping it resolves the ticket with [`SendError::Discarded`].
+#[derive(Debug)]
+pub(crate) struct SendConfirmation {
+    sender: Sender<Result<Sent, SendError>>,
+    msg_seq_num: u32,
+}
+
+impl SendConfirmation {
+    pub(crate) fn new() -> (Self, SendTicket) {
+        let (sender, receiver) = channel();
+        let confirmation = SendConfirmation {
+            sender,
+            msg_seq_num: 0,
+        };
+        (confirmation, SendTicket { receiver })
+    }
+
+    pub(crate) fn set_msg_seq_num(&mut self, msg_seq_num: u32) {
+        self.msg_seq_num = msg_seq_num;
+    }
+
+    pub(crate) fn written(self) {
+        self.sent(true);
+    }
+
+    pub(crate) fn not_written(self) {
+        self.sent(false);
+    }
+
+    pub(crate) fn io_error(self, error: io::Error) {
+        let msg_seq_num = self.msg_seq_num;
+        self.failed(SendError::Io { msg_seq_num, error });
+    }
+
+    pub(crate) fn failed(self, error: SendError) {
+        let _ = self.sender.send(Err(error));
+    }
+
+    fn sent(self, written: bool) {
+        let _ = self.sender.send(Ok(Sent {
+            msg_seq_num: self.msg_seq_num,
+            written,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::time::Duration;
+
+    use super::{SendConfirmation, SendError, Sent};
+
+    #[test]
+    fn send_ticket() {
+        let (mut confirmation, ticket) = SendConfirmation::new();
+        assert!(ticket.try_result().is_none());
+        assert!(matches!(
+            ticket.wait(Duration::from_millis(1)),
+            Err(SendError::Timeout)
+        ));
+        confirmation.set_msg_seq_num(12);
+        confirmation.written();
+        assert_eq!(
+            ticket.wait(Duration::from_millis(1)).unwrap(),
+            Sent {
+                msg_seq_num: 12,
+                written: true
+            }
+        );
+
+        let (mut confirmation, ticket) = SendConfirmation::new();
+        confirmation.set_msg_seq_num(13);
+        confirmation.io_error(io::ErrorKind::BrokenPipe.into());
+        assert!(matches!(
+            ticket.try_result(),
+            Some(Err(SendError::Io {
+                msg_seq_num: 13,
+                ..
+            }))
+        ));
+
+        let (confirmation, ticket) = SendConfirmation::new();
+        drop(confirmation);
+        assert!(matches!(
+            ticket.try_result(),
+            Some(Err(SendError::Discarded))
+        ));
+    }
+}
@@ -1,12 +1,14 @@
 use std::cmp;
 use std::cmp::min;
+use std::collections::VecDeque;
 use std::sync::mpsc::channel;
+use std::sync::mpsc::sync_channel;
 use std::sync::mpsc::Receiver;
-use std::sync::mpsc::Sender;
 use std::time::Duration;
 use std::time::Instant;
 
 use chashmap::CHashMap;
+use chrono::DateTime;
 use chrono::NaiveDateTime;
 use chrono::Utc;
 use dfx_base::data_dictionary::TagException;
@@ -21,7 +23,10 @@ use crate::logging::LogFactory;
 use crate::logging::Logger;
 use dfx_base::data_dictionary::DataDictionary;
 use dfx_base::data_dictionary::MessageValidationError;
+use dfx_base::data_dictionary::Violation;
 use dfx_base::data_dictionary_provider::DataDictionaryProvider;
+use dfx_base::encoding::encode_latin1;
+use dfx_base::encoding::MessageEncoding;
 use dfx_base::field_map::Field;
 use dfx_base::field_map::FieldMapError;
 use dfx_base::field_map::Tag;
@@ -32,48 +37,92 @@ use dfx_base::fix_values::BeginString;
 use crate::message_store::MessageStoreFactory;
 use crate::session::Application;
 use crate::session::ApplicationError;
+use crate::session::Intercept;
+use crate::session::Interceptors;
+use crate::session::QueuedMessage;
+use crate::session::ResendAction;
+use crate::session::ResendProgress;
+use crate::session::ResetRange;
 use crate::session::Responder;
+use crate::session::SendConfirmation;
+use crate::session::SendError;
+use crate::session::SendTicket;
+use crate::session::SessionCommand;
+use crate::session::SessionHandle;
 use crate::session::SessionSchedule;
 use crate::session::SessionState;
+use crate::session::SessionStatus;
+use crate::session::Throttle;
+use crate::session::ThrottleAction;
 use dfx_base::message::Message;
 use dfx_base::message::MessageParseError;
 use dfx_base::message_factory::MessageFactory;
 use dfx_base::message_factory::MessageFactoryError;
 use dfx_base::session_id::SessionId;
 use dfx_base::tags;
+use dfx_base::validation_rules::ValidationRules;
 
 use super::FromAppError;
+use super::LogonOptions;
 use super::LogonReject;
+use super::OutboundQueueOptions;
 use super::Persistence;
 use super::SessionSetting;
 
 const _BUF_SIZE: usize = 4096;
 
 lazy_static! {
-    static ref SESSION_MAP: CHashMap<SessionId, Sender<Message>> = CHashMap::new();
+    static ref SESSION_MAP: CHashMap<SessionId, SessionHandle> = CHashMap::new();
+    /// Messages kept for the next connection with `PersistOutboundQueue=Y`.
+    static ref PENDING_OUTBOUND: CHashMap<SessionId, Vec<(Message, SendConfirmation)>> =
+        CHashMap::new();
 }
 
 #[allow(non_snake_case)]
 pub mod Session {
 
-    use super::{SessionError, SESSION_MAP};
+    use super::{SendTicket, SessionError, SessionHandle, SESSION_MAP};
     use dfx_base::{message::Message, session_id::SessionId};
 
-    pub fn send_to_session(session_id: &SessionId, message: Message) -> Result<(), SessionError> {
+    pub fn send_to_session(
+        session_id: &SessionId,
+        message: Message,
+    ) -> Result<SendTicket, SessionError> {
+        lookup(session_id)?.try_send(message)
+    }
+
+    /// Returns a handle to administer a connected session.
+    pub fn lookup(session_id: &SessionId) -> Result<SessionHandle, SessionError> {
         match SESSION_MAP.get(session_id) {
-            Some(session) => session.send(message).unwrap(),
-            None => return Err(SessionError::SessionNotFound),
+            Some(handle) => Ok(handle.clone()),
+            None => Err(SessionError::SessionNotFound),
         }
-        Ok(())
+    }
+
+    /// Returns handles to all connected sessions.
+    pub fn sessions() -> Vec<SessionHandle> {
+        SESSION_MAP
+            .clone()
+            .into_iter()
+            .map(|(_, handle)| handle)
+            .collect()
     }
 }
-fn connect(session_id: &SessionId) -> Result<Receiver<Message>, InternalSessionError> {
+/// Registers the session, returns the receivers for its message and command queues.
+fn connect(
+    session_id: &SessionId,
+    capacity: usize,
+) -> Result<(Receiver<QueuedMessage>, Receiver<SessionCommand>), InternalSessionError> {
     if SESSION_MAP.contains_key(session_id) {
         return Err(InternalSessionError::AlreadyConnected);
     }
-    let (tx, rx) = channel();
-    SESSION_MAP.insert_new(session_id.clone(), tx);
-    Ok(rx)
+    let (tx, rx) = sync_channel(capacity);
+    let (control_tx, control_rx) = channel();
+    SESSION_MAP.insert_new(
+        session_id.clone(),
+        SessionHandle::new(session_id.clone(), tx, control_tx),
+    );
+    Ok((rx, control_rx))
 }
 fn disconnect_session(session_id: &SessionId) {
     SESSION_MAP.remove(session_id);
@@ -91,6 +140,7 @@ pub(crate) struct ISession<App, DDP, Log, MF> {
     target_default_appl_ver_id: Option<u32>,
     session_data_dictionary: DataDictionary,     //Option?
     application_data_dictionary: DataDictionary, //Option?
+    validation_rules: ValidationRules,
     log: Log,
     state: SessionState<Log>,
     persist_messages: bool,
@@ -98,9 +148,11 @@ pub(crate) struct ISession<App, DDP, Log, MF> {
     send_redundant_resend_requests: bool,
     _resend_session_level_rejects: bool,
     validate_length_and_checksum: bool,
+    validation_report: bool,
     check_comp_id: bool,
     time_stamp_precision: DateTimeFormat,
     enable_last_msg_seq_num_processed: bool,
+    enable_next_expected_msg_seq_num: bool,
     max_messages_in_resend_request: u32,
     send_logout_before_timeout_disconnect: bool,
     _ignore_poss_dup_resend_requests: bool,
@@ -111,80 +163,43 @@ pub(crate) struct ISession<App, DDP, Log, MF> {
     refresh_on_logon: bool,
     reset_on_logon: bool,
     reset_on_logout: bool,
-    outbound: Option<Receiver<Message>>,
+    logon_options: LogonOptions,
+    interceptors: Interceptors,
+    throttle: Option<Throttle>,
+    message_encoding: Option<MessageEncoding>,
+    outbound_queue_options: OutboundQueueOptions,
+    held_outbound: VecDeque<(Message, SendConfirmation)>,
+    outbound: Option<Receiver<QueuedMessage>>,
+    control: Option<Receiver<SessionCommand>>,
 }
 
 fn add_data_dictionaries<D: DataDictionaryProvider>(provider: &mut D, settings: &SessionSetting) {
     let options = settings.validation_options();
-    if options.use_data_dictionary() {
-        if settings.session_id().is_fixt() {
-            // https://github.com/connamara/quickfixn/blob/c4e8171e9a702be29078eab3b6dc26b713002de2/QuickFIXn/SessionFactory.cs#L193
-            if let Some(appl_ver_id) = settings.default_appl_ver_id() {
-                let path = match options.app_data_dictionary() {
-                    Some(path) => path,
-                    None => settings.session_id().begin_string(), // TODO error?
-                };
-                let mut dd = DataDictionary::from_file(path).unwrap();
-                dd.set_allow_unknown_message_fields(
-                    settings.validation_options().allow_unknown_msg_fields(),
-                );
-                dd.set_check_fields_have_values(
-                    settings.validation_options().validate_fields_have_values(),
-                );
-                dd.set_check_fields_out_of_order(
-                    settings.validation_options().validate_fields_out_of_order(),
-                );
-                dd.set_check_user_defined_fields(
-                    settings.validation_options().validate_user_defined_fields(),
-                );
-
-                provider.add_application_data_dictionary(appl_ver_id, dd);
-
-                let path = match options.transport_data_dictionary() {
-                    Some(path) => path,
-                    None => settings.session_id().begin_string(), // TODO error?
-                };
-                let mut dd = DataDictionary::from_file(path).unwrap();
-                dd.set_allow_unknown_message_fields(
-                    settings.validation_options().allow_unknown_msg_fields(),
-                );
-                dd.set_check_fields_have_values(
-                    settings.validation_options().validate_fields_have_values(),
-                );
-                dd.set_check_fields_out_of_order(
-                    settings.validation_options().validate_fields_out_of_order(),
-                );
-                dd.set_check_user_defined_fields(
-                    settings.validation_options().validate_user_defined_fields(),
-                );
-
-                provider.add_session_data_dictionary(settings.session_id().begin_string(), dd);
-            }
-        } else {
-            let path = match options.data_dictionary() {
-                Some(path) => path,
-                None => settings.session_id().begin_string(),
-            };
-
-            let mut dd = DataDictionary::from_file(path).unwrap();
-            dd.set_allow_unknown_message_fields(
-                settings.validation_options().allow_unknown_msg_fields(),
-            );
-            dd.set_check_fields_have_values(
-                settings.validation_options().validate_fields_have_values(),
-            );
-            dd.set_check_fields_out_of_order(
-                settings.validation_options().validate_fields_out_of_order(),
-            );
-            dd.set_check_user_defined_fields(
-                settings.validation_options().validate_user_defined_fields(),
-            );
-            provider.add_session_data_dictionary(settings.session_id().begin_string(), dd.clone());
-            provider.add_application_data_dictionary(
-                ApplVerID::from_begin_string(settings.session_id().begin_string()),
-                dd,
-            );
+    let configure = |dd: &DataDictionary| {
+        let mut dd = dd.clone();
+        dd.set_allow_unknown_message_fields(options.allow_unknown_msg_fields());
+        dd.set_check_fields_have_values(options.validate_fields_have_values());
+        dd.set_check_fields_out_of_order(options.validate_fields_out_of_order());
+        dd.set_check_user_defined_fields(options.validate_user_defined_fields());
+        dd
+    };
+    // loaded by the settings when UseDataDictionary is set
+    let dictionaries = settings.data_dictionaries();
+    let begin_string = settings.session_id().begin_string();
+    if settings.session_id().is_fixt() {
+        // https://github.com/connamara/quickfixn/blob/c4e8171e9a702be29078eab3b6dc26b713002de2/QuickFIXn/SessionFactory.cs#L193
+        if let (Some(appl_ver_id), Some(dd)) =
+            (settings.default_appl_ver_id(), dictionaries.application())
+        {
+            provider.add_application_data_dictionary(appl_ver_id, configure(dd));
+        }
+        if let Some(dd) = dictionaries.session() {
+            provider.add_session_data_dictionary(begin_string, configure(dd));
         }
+    } else if let Some(dd) = dictionaries.session() {
+        let dd = configure(dd);
+        provider.add_session_data_dictionary(begin_string, dd.clone());
+        provider.add_application_data_dictionary(ApplVerID::from_begin_string(begin_string), dd);
     }
 }
 
@@ -216,6 +231,7 @@ where
         } else {
             session_data_dictionary.clone()
         };
+        let validation_rules = settings.data_dictionaries().validation_rules().clone();
 
         let msg_store = store_factory.create(&session_id);
         let mut state = SessionState::new(
@@ -263,6 +279,7 @@ where
             target_default_appl_ver_id: None,
             session_data_dictionary,
             application_data_dictionary,
+            validation_rules,
             log,
             state,
             persist_messages: !matches!(settings.persistence(), Persistence::None),
@@ -276,11 +293,15 @@ where
             validate_length_and_checksum: settings
                 .validation_options()
                 .validate_length_and_checksum(),
+            validation_report: settings.validation_options().validation_report(),
             check_comp_id: true,
             time_stamp_precision: settings.validation_options().time_stamp_precision().clone(),
             enable_last_msg_seq_num_processed: settings
                 .validation_options()
                 .enable_last_msg_seq_num_processed(),
+            enable_next_expected_msg_seq_num: settings
+                .validation_options()
+                .enable_next_expected_msg_seq_num(),
             max_messages_in_resend_request: settings
                 .validation_options()
                 .max_messages_in_resend_request(),
@@ -297,10 +318,21 @@ where
             refresh_on_logon: settings.validation_options().refresh_on_logon(),
             reset_on_logon: settings.validation_options().reset_on_logon(),
             reset_on_logout: settings.validation_options().reset_on_logout(),
+            logon_options: settings.logon_options().clone(),
+            interceptors: Interceptors::default(),
+            throttle: Throttle::new(settings.throttle_options(), Instant::now()),
+            message_encoding: settings.message_encoding(),
+            outbound_queue_options: settings.outbound_queue_options().clone(),
+            held_outbound: VecDeque::new(),
             outbound: None,
+            control: None,
         }
     }
 
+    pub(crate) fn set_interceptors(&mut self, interceptors: Interceptors) {
+        self.interceptors = interceptors;
+    }
+
     pub(crate) fn set_responder(&mut self, responder: Box<dyn Responder>) {
         self.responder = Some(responder);
     }
@@ -309,27 +341,181 @@ where
         &mut self,
         session_id: &SessionId,
     ) -> Result<(), InternalSessionError> {
-        let receiver = connect(session_id);
-        self.outbound = Some(receiver?);
+        let (outbound, control) = connect(session_id, self.outbound_queue_options.capacity())?;
+        self.outbound = Some(outbound);
+        self.control = Some(control);
+        if let Some(pending) = PENDING_OUTBOUND.remove(session_id) {
+            self.held_outbound.extend(pending);
+        }
         Ok(())
     }
 
     pub(crate) fn set_disconnected(&mut self, session_id: &SessionId) {
         disconnect_session(session_id);
-        self.outbound = None;
+        self.control = None;
+        if let Some(receiver) = self.outbound.take() {
+            if self.outbound_queue_options.persist() {
+                // Anything beyond the capacity is dropped with the receiver, resolving its
+                // ticket with `SendError::Discarded`.
+                let room = self
+                    .outbound_queue_options
+                    .capacity()
+                    .saturating_sub(self.held_outbound.len());
+                self.held_outbound.extend(receiver.try_iter().take(room));
+            }
+        }
+        if !self.held_outbound.is_empty() {
+            self.log.on_event(
+                format!(
+                    "Keeping {} queued messages until the next logon",
+                    self.held_outbound.len()
+                )
+                .as_str(),
+            );
+            PENDING_OUTBOUND.insert(session_id.clone(), self.held_outbound.drain(..).collect());
+        }
     }
 
     fn process_outbound(&mut self) {
+        while let Some(command) = self
+            .control
+            .as_ref()
+            .and_then(|control| control.try_recv().ok())
+        {
+            if let Err(err) = self.handle_command(command) {
+                self.log
+                    .on_event(format!("Error handling session command: {err:?}").as_str());
+            }
+        }
+        if self.is_logged_on() {
+            while let Some((message, confirmation)) = self.held_outbound.pop_front() {
+                if let Err(err) = self.send_outbound(message, confirmation) {
+                    self.log
+                        .on_event(format!("Error sending queued message: {err:?}").as_str());
+                }
+            }
+        }
+        if let Err(err) = self.send_throttled() {
+            self.log
+                .on_event(format!("Error sending throttled message: {err:?}").as_str());
+        }
+        if self.throttle.as_ref().is_some_and(|throttle| {
+            throttle.action() == ThrottleAction::Block && throttle.has_pending()
+        }) || self.outbound_backlog_full()
+        {
+            return;
+        }
         if let Some(receiver) = self.outbound.as_mut() {
             match receiver.recv_timeout(Duration::from_millis(1)) {
-                Ok(mut msg) => {
-                    self.initialize_header(&mut msg, None);
-                    self.send_raw(msg, 0).unwrap()
+                Ok((message, confirmation)) => {
+                    if let Err(err) = self.handle_queued(message, confirmation) {
+                        self.log
+                            .on_event(format!("Error sending queued message: {err:?}").as_str());
+                    }
                 }
                 Err(_) => return,
             };
         }
     }
+
+    /// True while the messages held until logon or queued by the throttle fill
+    /// `OutboundQueueCapacity`. Messages then stay in the channel, so once it is full as well
+    /// the handle returns `SessionError::QueueFull`.
+    fn outbound_backlog_full(&self) -> bool {
+        let capacity = self.outbound_queue_options.capacity();
+        self.held_outbound.len() >= capacity
+            || self
+                .throttle
+                .as_ref()
+                .is_some_and(|throttle| throttle.pending_len() >= capacity)
+    }
+
+    fn handle_queued(
+        &mut self,
+        message: Message,
+        confirmation: SendConfirmation,
+    ) -> Result<(), SessionHandleMessageError> {
+        if self.outbound_queue_options.persist() && !self.is_logged_on() {
+            self.held_outbound.push_back((message, confirmation));
+            Ok(())
+        } else {
+            self.send_outbound(message, confirmation)
+        }
+    }
+
+    fn handle_command(&mut self, command: SessionCommand) -> Result<(), SessionHandleMessageError> {
+        match command {
+            SessionCommand::SetNextSenderMsgSeqNum(seq_num) => {
+                let previous = self.state.next_sender_msg_seq_num();
+                self.state.set_next_sender_msg_seq_num(seq_num);
+                self.log.on_event(
+                    format!("Next sender MsgSeqNum set to {seq_num} (was {previous})").as_str(),
+                );
+            }
+            SessionCommand::SetNextTargetMsgSeqNum(seq_num) => {
+                let previous = self.state.next_target_msg_seq_num();
+                self.state.set_next_target_msg_seq_num(seq_num);
+                self.log.on_event(
+                    format!("Next target MsgSeqNum set to {seq_num} (was {previous})").as_str(),
+                );
+            }
+            SessionCommand::SequenceReset(new_seq_no) => {
+                if !self.is_logged_on() {
+                    self.log
+                        .on_event("SequenceReset-Reset not sent, session is not logged on");
+                    return Ok(());
+                }
+                let mut sequence_reset = self
+                    .msg_factory
+                    .create(self.session_id.begin_string(), MsgType::SEQUENCE_RESET)?;
+                self.initialize_header(&mut sequence_reset, None);
+                sequence_reset.set_tag_value(tags::NewSeqNo, i64::from(new_seq_no));
+                sequence_reset.set_tag_value(tags::GapFillFlag, false);
+                self.send_raw(sequence_reset, 0)?;
+                self.state.set_next_sender_msg_seq_num(new_seq_no);
+                self.log
+                    .on_event(format!("Sent SequenceReset-Reset NewSeqNo: {new_seq_no}").as_str());
+            }
+            SessionCommand::ResendRequest {
+                begin_seq_num,
+                end_seq_num,
+            } => {
+                if !self.is_logged_on() {
+                    self.log
+                        .on_event("ResendRequest not sent, session is not logged on");
+                    return Ok(());
+                }
+                let begin_string = self.session_id.begin_string().to_string();
+                if self.generate_resend_request_range(begin_string, begin_seq_num, end_seq_num)? {
+                    self.state
+                        .set_resend_range_begin_end(begin_seq_num, end_seq_num, None);
+                }
+            }
+            SessionCommand::Reset => self.state.reset(Some("Reset by administrator")),
+            SessionCommand::Logon => {
+                self.state.set_is_enabled(true);
+                self.log.on_event("Session enabled by administrator");
+            }
+            SessionCommand::Logout(reason) => {
+                self.state.set_logout_reason(reason);
+                self.state.set_is_enabled(false);
+                self.log.on_event("Session disabled by administrator");
+            }
+            SessionCommand::Disconnect => self.disconnect("Disconnected by administrator"),
+            SessionCommand::Status(sender) => {
+                let status = SessionStatus {
+                    session_id: self.session_id.clone(),
+                    enabled: self.state.is_enabled(),
+                    logged_on: self.is_logged_on(),
+                    next_sender_msg_seq_num: self.state.next_sender_msg_seq_num(),
+                    next_target_msg_seq_num: self.state.next_target_msg_seq_num(),
+                };
+                // The caller may have timed out already
+                let _ = sender.send(status);
+            }
+        }
+        Ok(())
+    }
     pub(crate) fn next(&mut self) {
         if self.responder.is_none() {
             return;
@@ -353,6 +539,17 @@ where
             self.state.reset(Some("New session (detected in Next())"));
         }
 
+        if !self.is_logon_time() {
+            if self.is_logged_on() && !self.state.sent_logout() {
+                self.log
+                    .on_event("Out of logon time, initiated logout request");
+                self.generate_logout(Some("Out of logon time".into()), None);
+            } else if !self.is_logged_on() || self.state.logout_timed_out() {
+                self.disconnect("Out of logon time");
+            }
+            return;
+        }
+
         if !self.state.is_enabled() {
             if !self.is_logged_on() {
                 return;
@@ -410,6 +607,10 @@ where
         is_session_time(&self.schedule)
     }
 
+    fn is_logon_time(&self) -> bool {
+        self.schedule.is_logon_time(&Utc::now())
+    }
+
     fn is_new_session(&self) -> bool {
         self.state
             .creation_time()
@@ -478,6 +679,22 @@ where
         if self.should_send_reset() {
             logon.set_field(ResetSeqNumFlag::new(true));
         }
+        if let Some(raw_data) = self.logon_options.raw_data() {
+            logon.set_tag_value(tags::RawDataLength, raw_data.len());
+            logon.set_tag_value(tags::RawData, raw_data);
+        }
+        if let Some(username) = self.logon_options.username() {
+            logon.set_tag_value(tags::Username, username);
+        }
+        if let Some(password) = self.logon_options.password() {
+            logon.set_tag_value(tags::Password, password);
+        }
+        if self.enable_next_expected_msg_seq_num {
+            logon.set_tag_value(
+                tags::NextExpectedMsgSeqNum,
+                i64::from(self.state.next_target_msg_seq_num()),
+            );
+        }
 
         self.initialize_header(&mut logon, None);
         self.state.set_last_received_time_dt(Instant::now());
@@ -499,6 +716,16 @@ where
         }
         logon.set_field(other.get_field(tags::HeartBtInt).unwrap().clone());
 
+        if self.enable_next_expected_msg_seq_num {
+            // The Logon being answered counts as received unless it leaves a gap
+            let next_target = self.state.next_target_msg_seq_num();
+            let next_expected = match other.header().get_int(tags::MsgSeqNum) {
+                Ok(msg_seq_num) if self.is_target_too_high(msg_seq_num) => next_target,
+                _ => next_target + 1,
+            };
+            logon.set_tag_value(tags::NextExpectedMsgSeqNum, i64::from(next_expected));
+        }
+
         if self.enable_last_msg_seq_num_processed {
             if let Some(seq) = other.header().get_field(tags::MsgSeqNum) {
                 let value: &FieldValue = seq.value();
@@ -634,7 +861,20 @@ where
         self.state.set_resend_range_begin_end(0, 0, None);
     }
 
-    fn send_raw(&mut self, mut message: Message, seq_num: u32) -> Result<bool, FieldMapError> {
+    /// Returns whether the message was handed to the connection, see [`Responder::send`]. A
+    /// failed write is not reported here.
+    fn send_raw(&mut self, message: Message, seq_num: u32) -> Result<bool, FieldMapError> {
+        self.send_raw_confirmed(message, seq_num, &mut None)
+    }
+
+    /// Like `send_raw`, resolves `confirmation` once the message is handed to the connection or
+    /// was not sent. An error leaves `confirmation` to the caller.
+    fn send_raw_confirmed(
+        &mut self,
+        mut message: Message,
+        seq_num: u32,
+        confirmation: &mut Option<SendConfirmation>,
+    ) -> Result<bool, FieldMapError> {
         let msg_type = message.header().get_string(tags::MsgType)?;
         self.initialize_header(&mut message, Some(seq_num));
         let message = if Message::is_admin_msg_type(msg_type.as_bytes()) {
@@ -651,9 +891,19 @@ where
                         tags::MsgSeqNum,
                         format!("{}", self.state.next_sender_msg_seq_num()),
                     ));
+                    if message.is_field_set(tags::NextExpectedMsgSeqNum) {
+                        message.set_tag_value(
+                            tags::NextExpectedMsgSeqNum,
+                            i64::from(self.state.next_target_msg_seq_num()),
+                        );
+                    }
                 }
                 self.state.set_sent_reset(reset);
             }
+            if MsgType::LOGON == msg_type {
+                self.application
+                    .sign_logon(&mut message, &self.session_id)?;
+            }
             Ok(message)
         } else {
             self.application
@@ -663,24 +913,165 @@ where
 
         match message {
             Ok(mut message) => {
-                let message_string = message.to_string_mut();
+                self.encode(&mut message)?;
+                if self
+                    .interceptors
+                    .before_send(&mut message, &self.session_id)
+                    == Intercept::Drop
+                {
+                    if let Some(confirmation) = confirmation.take() {
+                        confirmation.failed(SendError::Dropped);
+                    }
+                    return Ok(false);
+                }
+                self.sign(&mut message)?;
+                if let Some(confirmation) = confirmation.as_mut() {
+                    confirmation.set_msg_seq_num(message.header().get_int(tags::MsgSeqNum)?);
+                }
+                let mut message_string = message.to_string_mut();
                 if 0 == seq_num {
                     self.persist(&message, &message_string);
                 }
-                Ok(self.send(message_string))
+                if self
+                    .interceptors
+                    .after_persist(&message, &mut message_string, &self.session_id)
+                    == Intercept::Drop
+                {
+                    if let Some(confirmation) = confirmation.take() {
+                        confirmation.not_written();
+                    }
+                    return Ok(true);
+                }
+                Ok(self.send(message_string, confirmation.take()))
             }
             Err(e) => match e {
-                ApplicationError::DoNotSend => Ok(false),
+                ApplicationError::DoNotSend => {
+                    if let Some(confirmation) = confirmation.take() {
+                        confirmation.failed(SendError::DoNotSend);
+                    }
+                    Ok(false)
+                }
                 ApplicationError::FieldMapError(e) => Err(e),
             },
         }
     }
-    fn send(&mut self, message: String) -> bool {
+
+    /// Sends a message queued through a [`SessionHandle`](super::SessionHandle).
+    fn send_outbound(
+        &mut self,
+        message: Message,
+        confirmation: SendConfirmation,
+    ) -> Result<(), SessionHandleMessageError> {
+        if let Some((message, confirmation)) = self.throttle(message, confirmation)? {
+            self.send_confirmed(message, confirmation)?;
+        }
+        Ok(())
+    }
+
+    fn send_confirmed(
+        &mut self,
+        mut message: Message,
+        confirmation: SendConfirmation,
+    ) -> Result<(), FieldMapError> {
+        if !self.is_logged_on() {
+            confirmation.failed(SendError::NotLoggedOn(Box::new(self.session_id.clone())));
+            return Ok(());
+        }
+        self.initialize_header(&mut message, None);
+        let mut confirmation = Some(confirmation);
+        let result = self.send_raw_confirmed(message, 0, &mut confirmation);
+        if let (Err(e), Some(confirmation)) = (&result, confirmation) {
+            confirmation.failed(SendError::InvalidMessage(e.clone()));
+        }
+        result.map(|_| ())
+    }
+
+    /// Returns the message when it may be sent now, otherwise queues or rejects it according to
+    /// `ThrottleAction`.
+    fn throttle(
+        &mut self,
+        message: Message,
+        confirmation: SendConfirmation,
+    ) -> Result<Option<(Message, SendConfirmation)>, SessionHandleMessageError> {
+        let Some(throttle) = self.throttle.as_mut() else {
+            return Ok(Some((message, confirmation)));
+        };
+        let msg_type = match message.header().get_string(tags::MsgType) {
+            Ok(msg_type) => msg_type,
+            Err(e) => {
+                confirmation.failed(SendError::InvalidMessage(e.clone()));
+                return Err(e.into());
+            }
+        };
+        if Message::is_admin_msg_type(msg_type.as_bytes())
+            || (!throttle.has_pending() && throttle.try_acquire(&msg_type, Instant::now()))
+        {
+            return Ok(Some((message, confirmation)));
+        }
+
+        let action = throttle.action();
+        self.application
+            .on_throttle(&message, action, &self.session_id);
+        match action {
+            ThrottleAction::Reject => {
+                self.log
+                    .on_event(format!("Throttled message of type {msg_type} rejected").as_str());
+                confirmation.failed(SendError::Throttled);
+            }
+            ThrottleAction::Queue | ThrottleAction::Block => {
+                self.log
+                    .on_event(format!("Throttled message of type {msg_type} queued").as_str());
+                throttle.push(message, confirmation);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Sends queued throttled messages as far as the rate allows.
+    fn send_throttled(&mut self) -> Result<(), SessionHandleMessageError> {
+        while let Some((message, confirmation)) = self
+            .throttle
+            .as_mut()
+            .and_then(|throttle| throttle.pop_ready(Instant::now()))
+        {
+            self.send_confirmed(message, confirmation)?;
+        }
+        Ok(())
+    }
+
+    /// Encodes the Encoded* fields, set as UTF-8 by the application, with the `MessageEncoding`
+    /// of the session unless the message sets its own.
+    fn encode(&self, message: &mut Message) -> Result<(), FieldMapError> {
+        if let Some(encoding) = self.message_encoding {
+            if message.encoding()?.is_none() {
+                message.transcode_encoded_fields(MessageEncoding::default(), encoding)?;
+                message.set_encoding(encoding);
+            }
+        }
+        Ok(())
+    }
+
+    /// Signs `message` if the application's `MessageSigner` signs its MsgType.
+    fn sign(&self, message: &mut Message) -> Result<(), FieldMapError> {
+        if let Some(signer) = self.application.message_signer() {
+            let msg_type = message.header().get_string(tags::MsgType)?;
+            if signer.signs(&msg_type) {
+                signer.sign(message, &self.session_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn send(&mut self, message: String, confirmation: Option<SendConfirmation>) -> bool {
         self.state.set_last_sent_time_dt(Instant::now());
         if let Some(responder) = self.responder.as_mut() {
-            self.log.on_outgoing(message.as_str());
-            responder.send(message)
+            self.log
+                .on_outgoing(&String::from_utf8_lossy(&encode_latin1(&message)));
+            responder.send(message, confirmation)
         } else {
+            if let Some(confirmation) = confirmation {
+                confirmation.not_written();
+            }
             false
         }
     }
@@ -834,7 +1225,7 @@ where
                     if let Some(msg) = e.inner() {
                         self.log.on_event(msg.as_str());
                     }
-                    self.generate_reject(msg, e.session_reject_reason().clone(), Some(e.field()))
+                    self.generate_reject(*msg, e.session_reject_reason().clone(), Some(e.field()))
                         .unwrap();
                 }
                 SessionHandleMessageError::UnsupportedVersion {
@@ -844,7 +1235,7 @@ where
                 } => {
                     let result = message.header().get_string(tags::MsgType);
                     if matches!(result, Ok(v) if MsgType::LOGOUT == v) {
-                        self.next_logout(message).unwrap();
+                        self.next_logout(*message).unwrap();
                     } else {
                         self.log.on_event(
                             format!("Received version {actual} but expected {expected}").as_str(),
@@ -867,11 +1258,22 @@ where
                     self.generate_logout(reason, None);
                     self.disconnect(&disconnect_msg);
                 }
+                SessionHandleMessageError::BusinessReject {
+                    message,
+                    reason,
+                    tag,
+                } => {
+                    self.log.on_event(
+                        format!("Business reject for tag {tag}: {}", reason.reason()).as_str(),
+                    );
+                    self.generate_business_message_reject(*message, reason)
+                        .unwrap();
+                }
                 SessionHandleMessageError::UnknownMessageType { message, msg_type } => {
                     self.log
                         .on_event(format!("Unsupported message type: {msg_type}").as_str());
                     self.generate_business_message_reject(
-                        message,
+                        *message,
                         BusinessRejectReason::UNKNOWN_MESSAGE_TYPE(),
                     )
                     .unwrap();
@@ -882,7 +1284,10 @@ where
         self.next();
     }
 
-    fn next_msg_handler(&mut self, msg: Vec<u8>) -> Result<(), SessionHandleMessageError> {
+    fn next_msg_handler(&mut self, mut msg: Vec<u8>) -> Result<(), SessionHandleMessageError> {
+        if self.interceptors.inbound_raw(&mut msg, &self.session_id) == Intercept::Drop {
+            return Ok(());
+        }
         let msg_type = Message::identify_type(&msg).map_err(|mp| {
             SessionHandleMessageError::MessageParseError {
                 message: msg.clone(),
@@ -914,7 +1319,7 @@ where
 
     fn handle_msg(
         &mut self,
-        message: Message,
+        mut message: Message,
         begin_string: &str,
         msg_type: &str,
     ) -> Result<(), SessionHandleMessageError> {
@@ -922,11 +1327,19 @@ where
             todo!("Do early intercept")
         }
 
+        if self
+            .interceptors
+            .before_validation(&mut message, &self.session_id)
+            == Intercept::Drop
+        {
+            return Ok(());
+        }
+
         let _header = message.header();
 
         if begin_string != self.session_id.begin_string() {
             return Err(SessionHandleMessageError::UnsupportedVersion {
-                message,
+                message: Box::new(message),
                 actual: begin_string.into(),
                 expected: self.session_id.begin_string().to_string(),
             });
@@ -940,36 +1353,19 @@ where
             }
         }
 
-        let validation_result =
-            if self.session_id.is_fixt() && !Message::is_admin_msg_type(msg_type.as_bytes()) {
-                DataDictionary::validate(
-                    &message,
-                    Some(&self.session_data_dictionary),
-                    &self.application_data_dictionary,
-                    begin_string,
-                    msg_type,
-                )
-            } else {
-                DataDictionary::validate(
-                    &message,
-                    Some(&self.session_data_dictionary),
-                    &self.session_data_dictionary,
-                    begin_string,
-                    msg_type,
-                )
-            };
+        let validation_result = self.validate(&message, begin_string, msg_type);
 
         if let Err(e) = validation_result {
             return Err(match e {
                 MessageValidationError::UnsupportedVersion { expected, actual } => {
                     SessionHandleMessageError::UnsupportedVersion {
-                        message,
+                        message: Box::new(message),
                         expected,
                         actual,
                     }
                 }
                 MessageValidationError::TagException(tag_exception) => {
-                    SessionHandleMessageError::TagException(message, tag_exception)
+                    SessionHandleMessageError::TagException(Box::new(message), tag_exception)
                 }
                 MessageValidationError::FieldMapError(fm) => {
                     SessionHandleMessageError::FieldMapError(fm)
@@ -977,8 +1373,17 @@ where
                 MessageValidationError::ConversionError(ce) => {
                     SessionHandleMessageError::ConversionError(ce)
                 }
+                MessageValidationError::BusinessReject { reason, tag } => {
+                    SessionHandleMessageError::BusinessReject {
+                        message: Box::new(message),
+                        reason,
+                        tag,
+                    }
+                }
             });
         }
+        self.interceptors
+            .after_validation(&message, &self.session_id);
 
         if MsgType::LOGON == msg_type {
             self.next_logon(message)
@@ -1003,6 +1408,56 @@ where
         }
     }
 
+    /// Validates a received message against the data dictionaries and validation rules. With
+    /// `UseDataDictionary=Report` every error is logged and passed to
+    /// [`Application::on_validation_report`], the message is processed anyway.
+    fn validate(
+        &mut self,
+        message: &Message,
+        begin_string: &str,
+        msg_type: &str,
+    ) -> Result<(), MessageValidationError> {
+        let app_data_dictionary =
+            if self.session_id.is_fixt() && !Message::is_admin_msg_type(msg_type.as_bytes()) {
+                &self.application_data_dictionary
+            } else {
+                &self.session_data_dictionary
+            };
+        if !self.validation_report {
+            return DataDictionary::validate(
+                message,
+                Some(&self.session_data_dictionary),
+                app_data_dictionary,
+                begin_string,
+                msg_type,
+            )
+            .and_then(|()| self.validation_rules.validate(message, msg_type));
+        }
+
+        let mut errors = DataDictionary::validate_all(
+            message,
+            Some(&self.session_data_dictionary),
+            app_data_dictionary,
+            begin_string,
+            msg_type,
+        );
+        errors.extend(
+            self.validation_rules
+                .validate_all(message, msg_type)
+                .into_iter()
+                .map(Violation::from),
+        );
+        if !errors.is_empty() {
+            for error in &errors {
+                self.log
+                    .on_event(format!("Validation error in {msg_type}: {error}").as_str());
+            }
+            self.application
+                .on_validation_report(message, &errors, &self.session_id);
+        }
+        Ok(())
+    }
+
     fn next_queued(&mut self) {
         while let Some(msg) = self
             .state
@@ -1041,6 +1496,13 @@ where
     }
 
     fn next_logon(&mut self, logon: Message) -> Result<(), SessionHandleMessageError> {
+        if !self.is_logon_time() {
+            self.log().on_event("Received logon outside of logon time");
+            self.generate_logout(Some("Logon attempt not within logon time".into()), None);
+            self.disconnect("Logon attempt not within logon time");
+            return Ok(());
+        }
+
         let received_reset =
             logon.is_field_set(tags::ResetSeqNumFlag) && logon.get_bool(tags::ResetSeqNumFlag);
         self.state.set_received_reset(received_reset);
@@ -1072,6 +1534,36 @@ where
             return Ok(());
         }
 
+        let authenticated = match self.application.message_signer() {
+            Some(signer) => signer.authenticate(&logon, &self.session_id),
+            None => true,
+        };
+        if !authenticated {
+            self.log().on_event("Logon authentication failed");
+            self.generate_logout(Some("Logon authentication failed".into()), None);
+            self.disconnect("Logon authentication failed");
+            return Ok(());
+        }
+
+        let next_expected = if self.enable_next_expected_msg_seq_num
+            && logon.is_field_set(tags::NextExpectedMsgSeqNum)
+        {
+            Some(logon.get_int(tags::NextExpectedMsgSeqNum)?)
+        } else {
+            None
+        };
+        // Our Logon has, or for an acceptor is about to get, MsgSeqNum next_sender - 1 or next_sender
+        let next_sender = self.state.next_sender_msg_seq_num();
+        if let Some(next_expected) = next_expected.filter(|&n| n > next_sender) {
+            let err = format!(
+                "NextExpectedMsgSeqNum too high, expecting at most {next_sender} but received {next_expected}"
+            );
+            self.log().on_event(&err);
+            self.generate_logout(Some(err.clone()), None);
+            self.disconnect(&err);
+            return Ok(());
+        }
+
         self.state.set_received_logon(true);
         self.log().on_event("Received logon");
 
@@ -1082,11 +1574,37 @@ where
             self.log().on_event("Responding to logon request");
         }
 
+        if let Some(next_expected) = next_expected {
+            let logon_seq_num = self.state.next_sender_msg_seq_num() - 1;
+            if next_expected < logon_seq_num {
+                self.log().on_event(
+                    format!(
+                        "Counterparty expects {next_expected}, resending up to {}",
+                        logon_seq_num - 1
+                    )
+                    .as_str(),
+                );
+                self.resend_messages(&logon, next_expected, logon_seq_num - 1)?;
+            }
+        }
+
         self.state.set_sent_reset(false);
         self.state.set_received_reset(false);
 
         let msg_seq_num = logon.header().get_int(tags::MsgSeqNum)?;
-        if self.is_target_too_high(msg_seq_num) && !received_reset {
+        if self.is_target_too_high(msg_seq_num) && !received_reset && next_expected.is_some() {
+            // The counterparty resends the gap because of the NextExpectedMsgSeqNum we sent
+            let begin_seq_num = self.state.next_target_msg_seq_num();
+            self.log.on_event(
+                format!(
+                    "MsgSeqNum too high, expecting {begin_seq_num} but received {msg_seq_num}, waiting for resend"
+                )
+                .as_str(),
+            );
+            self.state.queue(msg_seq_num, logon);
+            self.state
+                .set_resend_range_begin_end(begin_seq_num, msg_seq_num - 1, None);
+        } else if self.is_target_too_high(msg_seq_num) && !received_reset {
             self.do_target_too_high(logon, msg_seq_num)?;
         } else {
             self.state.incr_next_target_msg_seq_num();
@@ -1164,6 +1682,10 @@ where
             );
             if new_seq_no > self.state.next_target_msg_seq_num() {
                 self.state.set_next_target_msg_seq_num(new_seq_no);
+                if self.state.resend_requested() {
+                    // A gap fill can skip past the end of the outstanding chunk
+                    self.check_resend_progress(new_seq_no - 1)?;
+                }
             } else if new_seq_no < self.state.next_target_msg_seq_num() {
                 self.generate_reject(message, SessionRejectReason::VALUE_IS_INCORRECT(), None)?;
             }
@@ -1181,7 +1703,6 @@ where
             if !(self._ignore_poss_dup_resend_requests
                 && resend_request.header().is_field_set(tags::PossDupFlag))
             {
-                let mut msg_seq_num;
                 let beg_seq_no = resend_request.get_int(tags::BeginSeqNo)?;
                 let mut end_seq_no = resend_request.get_int(tags::EndSeqNo)?;
                 self.log.on_event(
@@ -1191,91 +1712,125 @@ where
                 if end_seq_no == 999999 || end_seq_no == 0 {
                     end_seq_no = self.state.next_sender_msg_seq_num() - 1;
                 }
+                self.resend_messages(&resend_request, beg_seq_no, end_seq_no)?;
+            }
+            let msg_seq_num = resend_request.header().get_int(tags::MsgSeqNum)?;
+            if !self.is_target_too_high(msg_seq_num) && !self.is_target_too_low(msg_seq_num) {
+                self.state.incr_next_target_msg_seq_num();
+            }
+            Ok(())
+        } else {
+            Ok(())
+        }
+    }
 
-                if !self.persist_messages {
-                    end_seq_no += 1;
-                    let next = self.state.next_sender_msg_seq_num();
-                    if end_seq_no > next {
-                        end_seq_no = next;
-                    }
-                    self.generate_sequence_reset(&resend_request, beg_seq_no, end_seq_no)?;
-                    msg_seq_num = resend_request.header().get_int(tags::MsgSeqNum)?;
-                    if !self.is_target_too_high(msg_seq_num) && !self.is_target_too_low(msg_seq_num)
-                    {
-                        self.state.incr_next_target_msg_seq_num();
-                    }
-                    return Ok(());
-                }
+    /// Resends the stored messages from `beg_seq_no` to `end_seq_no`, admin messages and messages
+    /// the application does not want resent are replaced by SequenceReset-GapFill.
+    fn resend_messages(
+        &mut self,
+        received_message: &Message,
+        beg_seq_no: u32,
+        mut end_seq_no: u32,
+    ) -> Result<(), SessionHandleMessageError> {
+        if !self.persist_messages {
+            end_seq_no += 1;
+            let next = self.state.next_sender_msg_seq_num();
+            if end_seq_no > next {
+                end_seq_no = next;
+            }
+            self.generate_sequence_reset(received_message, beg_seq_no, end_seq_no)?;
+            return Ok(());
+        }
 
-                let mut current = beg_seq_no;
-                let mut begin = 0;
-                for msg_str in self.state.get_messages(beg_seq_no, end_seq_no) {
-                    let mut msg = Message::default();
-                    msg.from_string(
-                        msg_str.as_bytes(),
-                        true,
-                        Some(&self.session_data_dictionary),
-                        Some(&self.application_data_dictionary),
-                        Some(&self.msg_factory),
-                        false,
-                    )
-                    .map_err(|mp| {
-                        SessionHandleMessageError::MessageParseError {
-                            message: msg_str.into_bytes(),
-                            parse_error: mp,
-                        }
-                    })?;
-                    msg_seq_num = msg.header().get_int(tags::MsgSeqNum)?;
+        let mut current = beg_seq_no;
+        let mut begin = 0;
+        for msg_str in self.state.get_messages(beg_seq_no, end_seq_no) {
+            let mut msg = Message::default();
+            let msg_bytes = encode_latin1(&msg_str);
+            msg.from_string(
+                &msg_bytes,
+                true,
+                Some(&self.session_data_dictionary),
+                Some(&self.application_data_dictionary),
+                Some(&self.msg_factory),
+                false,
+            )
+            .map_err(|mp| SessionHandleMessageError::MessageParseError {
+                message: msg_bytes,
+                parse_error: mp,
+            })?;
+            let msg_seq_num = msg.header().get_int(tags::MsgSeqNum)?;
 
-                    if current != msg_seq_num && begin == 0 {
-                        begin = current;
+            if current != msg_seq_num && begin == 0 {
+                begin = current;
+            }
+
+            if msg.is_admin()
+                && !(self._resend_session_level_rejects
+                    && msg.header().get_string(tags::MsgType)? == MsgType::REJECT)
+            {
+                if begin == 0 {
+                    begin = msg_seq_num;
+                }
+            } else {
+                let sending_time = msg.header().get_datetime(tags::SendingTime)?;
+                let action = self
+                    .application
+                    .on_resend(&msg, sending_time, &self.session_id);
+                let msg = match action {
+                    ResendAction::Resend => Some(msg),
+                    ResendAction::ResendModified(mut modified) => {
+                        modified
+                            .header_mut()
+                            .set_tag_value(tags::MsgSeqNum, i64::from(msg_seq_num));
+                        Some(*modified)
+                    }
+                    ResendAction::GapFill => None,
+                };
+                let approved = msg
+                    .and_then(|mut msg| {
+                        self.initialize_resend_fields(&mut msg, sending_time);
+                        self.resend_approved(msg)
+                    })
+                    .and_then(|mut msg| {
+                        let intercept = self.interceptors.before_send(&mut msg, &self.session_id);
+                        (intercept == Intercept::Continue).then_some(msg)
+                    });
+                if let Some(mut msg) = approved {
+                    if begin != 0 {
+                        self.generate_sequence_reset(received_message, begin, msg_seq_num)?;
                     }
 
-                    if msg.is_admin()
-                        && !(self._resend_session_level_rejects
-                            && msg.header().get_string(tags::MsgType)? == MsgType::REJECT)
+                    self.sign(&mut msg)?;
+                    let mut message_string = msg.to_string_mut();
+                    if self
+                        .interceptors
+                        .after_persist(&msg, &mut message_string, &self.session_id)
+                        == Intercept::Continue
                     {
-                        if begin == 0 {
-                            begin = msg_seq_num;
-                        }
-                    } else {
-                        self.initialize_resend_fields(&mut msg);
-                        let approved = self.resend_approved(msg);
-                        if let Some(mut msg) = approved {
-                            if begin != 0 {
-                                self.generate_sequence_reset(&resend_request, begin, msg_seq_num)?;
-                            }
-
-                            self.send(msg.to_string_mut());
-                            begin = 0;
-                        } else {
-                            continue;
-                        }
+                        self.send(message_string, None);
                     }
-                    current = msg_seq_num + 1;
+                    begin = 0;
+                } else {
+                    continue;
                 }
+            }
+            current = msg_seq_num + 1;
+        }
 
-                let next_seq_num = self.state.next_sender_msg_seq_num();
-                end_seq_no += 1;
-                if end_seq_no > next_seq_num {
-                    end_seq_no = next_seq_num;
-                }
-                if begin == 0 {
-                    begin = current;
-                }
+        let next_seq_num = self.state.next_sender_msg_seq_num();
+        end_seq_no += 1;
+        if end_seq_no > next_seq_num {
+            end_seq_no = next_seq_num;
+        }
+        if begin == 0 {
+            begin = current;
+        }
 
-                if end_seq_no > begin {
-                    self.generate_sequence_reset(&resend_request, begin, end_seq_no)?;
-                }
-            }
-            let msg_seq_num = resend_request.header().get_int(tags::MsgSeqNum)?;
-            if !self.is_target_too_high(msg_seq_num) && !self.is_target_too_low(msg_seq_num) {
-                self.state.incr_next_target_msg_seq_num();
-            }
-            Ok(())
-        } else {
-            Ok(())
+        if end_seq_no > begin {
+            self.generate_sequence_reset(received_message, begin, end_seq_no)?;
         }
+        Ok(())
     }
 
     /// This will pass the message into the `from_admin` / `from_app` methods from the Application
@@ -1319,36 +1874,7 @@ where
         }
 
         if (check_too_high || check_too_low) && self.state.resend_requested() {
-            if let Some(range) = self.state.resend_range() {
-                if msg_seq_num >= range.end_seq_num {
-                    self.log.on_event(
-                        format!(
-                            "ResendRequest for messages FROM: {} TO: {} has been satisfied.",
-                            range.begin_seq_num, range.end_seq_num
-                        )
-                        .as_str(),
-                    );
-                    self.state.set_resend_range(None);
-                } else if let Some(chunk) = range.chunk_end_seq_num {
-                    if msg_seq_num >= chunk {
-                        self.log.on_event(format!("Chunked ResendRequest for messages FROM: {} TO: {} has been satisfied.", range.begin_seq_num, chunk).as_str());
-                        let new_chunk_end_seq_no = cmp::min(
-                            range.end_seq_num,
-                            chunk + self.max_messages_in_resend_request,
-                        );
-                        self.generate_resend_request_range(
-                            message.header().get_string(tags::BeginString)?,
-                            chunk + 1,
-                            new_chunk_end_seq_no,
-                        )?;
-                        self.state
-                            .resend_range_mut()
-                            .as_mut()
-                            .unwrap()
-                            .chunk_end_seq_num = Some(new_chunk_end_seq_no);
-                    }
-                }
-            }
+            self.check_resend_progress(msg_seq_num)?;
         }
 
         if !self.is_good_time(&message) {
@@ -1373,6 +1899,68 @@ where
         Ok(Some(message))
     }
 
+    /// Updates the outstanding ResendRequest once `last_seq_num` has been received or gap filled,
+    /// the next chunk is requested when the current one is satisfied.
+    fn check_resend_progress(
+        &mut self,
+        last_seq_num: u32,
+    ) -> Result<(), SessionHandleMessageError> {
+        let Some(range) = self.state.resend_range().cloned() else {
+            return Ok(());
+        };
+        let chunk_end = range.chunk_end_seq_num.unwrap_or(range.end_seq_num);
+        if last_seq_num >= range.end_seq_num {
+            self.log.on_event(
+                format!(
+                    "ResendRequest for messages FROM: {} TO: {} has been satisfied.",
+                    range.begin_seq_num, range.end_seq_num
+                )
+                .as_str(),
+            );
+            self.state.set_resend_range(None);
+            self.report_resend_progress(&range, last_seq_num, true);
+        } else if last_seq_num >= chunk_end {
+            self.log.on_event(
+                format!(
+                    "Chunked ResendRequest for messages FROM: {} TO: {} has been satisfied.",
+                    range.chunk_begin_seq_num, chunk_end
+                )
+                .as_str(),
+            );
+            let begin_seq_num = last_seq_num + 1;
+            let end_seq_num = cmp::min(
+                range.end_seq_num,
+                last_seq_num + self.max_messages_in_resend_request,
+            );
+            self.generate_resend_request_range(
+                self.session_id.begin_string().to_string(),
+                begin_seq_num,
+                end_seq_num,
+            )?;
+            if let Some(range) = self.state.resend_range_mut().as_mut() {
+                range.chunk_begin_seq_num = begin_seq_num;
+                range.chunk_end_seq_num = Some(end_seq_num);
+            }
+            if let Some(range) = self.state.resend_range().cloned() {
+                self.report_resend_progress(&range, last_seq_num, false);
+            }
+        }
+        Ok(())
+    }
+
+    fn report_resend_progress(&mut self, range: &ResetRange, last_seq_num: u32, complete: bool) {
+        let progress = ResendProgress {
+            begin_seq_num: range.begin_seq_num,
+            end_seq_num: range.end_seq_num,
+            chunk_begin_seq_num: range.chunk_begin_seq_num,
+            chunk_end_seq_num: range.chunk_end_seq_num.unwrap_or(range.end_seq_num),
+            last_received_seq_num: last_seq_num,
+            complete,
+        };
+        self.application
+            .on_resend_progress(&progress, &self.session_id);
+    }
+
     fn is_correct_comp_id(&self, sender_comp_id: String, target_comp_id: String) -> bool {
         !self.check_comp_id
             || (self.session_id.sender_comp_id() == sender_comp_id
@@ -1622,6 +2210,9 @@ where
             end_range_seq_num,
             Some(end_chunk_seq_num),
         );
+        if let Some(range) = self.state.resend_range().cloned() {
+            self.report_resend_progress(&range, begin_seq_num - 1, false);
+        }
         Ok(true)
     }
 
@@ -1728,8 +2319,7 @@ where
         Ok(())
     }
 
-    fn initialize_resend_fields(&self, msg: &mut Message) {
-        let sending_time = msg.header().get_datetime(tags::SendingTime).unwrap();
+    fn initialize_resend_fields(&self, msg: &mut Message, sending_time: DateTime<Utc>) {
         self.insert_orig_sending_time(msg, sending_time.naive_utc());
         msg.header_mut().set_tag_value(tags::PossDupFlag, true);
         self.insert_sending_time(msg);
@@ -1790,9 +2380,11 @@ fn is_session_time(session_schedule: &SessionSchedule) -> bool {
 
 #[derive(Debug, Clone)]
 pub enum SessionError {
-    NotConnected(SessionId),
-    NotLoggedOn(SessionId),
+    NotConnected(Box<SessionId>),
+    NotLoggedOn(Box<SessionId>),
     SessionNotFound,
+    Timeout(Box<SessionId>),
+    QueueFull(Box<SessionId>),
 }
 
 #[derive(Debug, Clone)]
@@ -1806,14 +2398,20 @@ pub(crate) enum InternalSessionError {
 #[derive(Debug, Clone)]
 pub(crate) enum SessionHandleMessageError {
     UnsupportedVersion {
-        message: Message,
+        message: Box<Message>,
         expected: String,
         actual: String,
     },
     UnknownMessageType {
-        message: Message,
+        message: Box<Message>,
         msg_type: String,
     },
+    // ValidationRules
+    BusinessReject {
+        message: Box<Message>,
+        reason: BusinessRejectReason,
+        tag: Tag,
+    },
     // MessageFactory::create
     InvalidMessageError(MessageFactoryError),
     // Message::from_string
@@ -1822,7 +2420,7 @@ pub(crate) enum SessionHandleMessageError {
         parse_error: MessageParseError,
     },
     // DataDictionaryError
-    TagException(Message, TagException),
+    TagException(Box<Message>, TagException),
     //TODO?
     String(String),
     LogonReject {
@@ -1878,3 +2476,307 @@ impl From<FromAppError> for SessionHandleMessageError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use dfx_base::message::Message;
+    use dfx_base::session_id::SessionId;
+    use dfx_base::tags;
+
+    use super::{Session, SessionError};
+    use crate::session::test_session::TestSession;
+    use crate::session::{Intercept, Interceptor, Interceptors};
+    use crate::session::{SendError, Sent};
+
+    fn order(cl_ord_id: &str) -> Message {
+        let mut order = Message::default();
+        order.header_mut().set_tag_value(tags::MsgType, "D");
+        order.set_tag_value(tags::ClOrdID, cl_ord_id);
+        order.set_tag_value(tags::Symbol, "EUR/USD");
+        order.set_tag_value(tags::Side, "1");
+        order.set_tag_value(tags::TransactTime, "20240229-12:00:00");
+        order.set_tag_value(tags::OrdType, "1");
+        order
+    }
+
+    #[test]
+    fn validation_report() {
+        let order = [
+            (tags::ClOrdID, "1"),
+            (tags::Symbol, "EUR/USD"),
+            (tags::Side, "Z"),
+            (tags::TransactTime, "20240229-12:00:00"),
+            (tags::OrdType, "1"),
+        ];
+        let settings = "UseDataDictionary=Report\nDataDictionary=../../spec/FIX44.xml";
+        let mut session = TestSession::logged_on("REPORT", settings);
+        session.receive(2, "D", &order);
+        assert_eq!(
+            session.app.events(),
+            [
+                "report 54: Value is incorrect (out of range) for this tag",
+                "from_app D",
+            ]
+        );
+        assert!(session.log.events().contains(
+            &"Validation error in D: 54: Value is incorrect (out of range) for this tag".into()
+        ));
+        assert!(session.sent_msg_types().is_empty());
+        drop(session);
+
+        let settings = "UseDataDictionary=Y\nDataDictionary=../../spec/FIX44.xml";
+        let mut session = TestSession::logged_on("REJECT", settings);
+        session.receive(2, "D", &order);
+        assert!(session.app.events().is_empty());
+        assert_eq!(session.sent_msg_types(), ["3"]);
+    }
+
+    #[cfg(feature = "hmac")]
+    #[test]
+    fn logon_authentication() {
+        use crate::session::test_session::RecordingApplication;
+        use crate::session::HmacSigner;
+
+        let app = RecordingApplication::default().with_signer(HmacSigner::new("key"));
+        let mut test = TestSession::connected_with("UNSIGNED", "", app);
+        test.logon();
+        assert!(test.session.is_logged_on());
+        drop(test);
+
+        let signer = HmacSigner::new("key").with_authentication();
+        let app = RecordingApplication::default().with_signer(signer);
+        let mut test = TestSession::connected_with("AUTHENTICATED", "", app);
+        test.receive(
+            1,
+            "A",
+            &[(tags::EncryptMethod, "0"), (tags::HeartBtInt, "30")],
+        );
+        assert!(!test.session.is_logged_on());
+        assert_eq!(test.sent_msg_types(), ["5"]);
+        assert!(test
+            .log
+            .events()
+            .contains(&"Logon authentication failed".into()));
+    }
+
+    #[test]
+    fn session_commands() {
+        let mut test = TestSession::logged_on("COMMANDS", "");
+        let handle = Session::lookup(&test.session_id()).unwrap();
+        let session = &mut test.session;
+        assert_eq!(session.state.next_sender_msg_seq_num(), 2);
+        assert_eq!(session.state.next_target_msg_seq_num(), 2);
+
+        handle.set_next_sender_msg_seq_num(10).unwrap();
+        session.next();
+        assert_eq!(session.state.next_sender_msg_seq_num(), 10);
+
+        handle.send_sequence_reset(20).unwrap();
+        test.session.next();
+        let messages = test.sent();
+        let [sequence_reset] = messages.as_slice() else {
+            panic!("{messages:?}");
+        };
+        let header = sequence_reset.header();
+        assert_eq!(header.get_string(tags::MsgType).unwrap(), "4");
+        assert_eq!(header.get_int(tags::MsgSeqNum).unwrap(), 10);
+        assert_eq!(sequence_reset.get_int(tags::NewSeqNo).unwrap(), 20);
+        assert_eq!(sequence_reset.get_string(tags::GapFillFlag).unwrap(), "N");
+        let session = &mut test.session;
+        assert_eq!(session.state.next_sender_msg_seq_num(), 20);
+
+        handle.reset().unwrap();
+        session.next();
+        assert_eq!(session.state.next_sender_msg_seq_num(), 1);
+        assert_eq!(session.state.next_target_msg_seq_num(), 1);
+    }
+
+    #[test]
+    fn persisted_queue_full() {
+        let settings = "PersistOutboundQueue=Y\nOutboundQueueCapacity=2";
+        let mut test = TestSession::connected("PERSIST", settings);
+        let session_id = test.session_id();
+        let handle = Session::lookup(&session_id).unwrap();
+        handle.try_send(order("1")).unwrap();
+        handle.try_send(order("2")).unwrap();
+        test.session.next();
+        test.session.next();
+        assert_eq!(test.session.held_outbound.len(), 2);
+
+        handle.try_send(order("3")).unwrap();
+        handle.try_send(order("4")).unwrap();
+        test.session.next();
+        assert_eq!(test.session.held_outbound.len(), 2);
+        assert!(matches!(
+            handle.try_send(order("5")),
+            Err(SessionError::QueueFull(id)) if *id == session_id
+        ));
+
+        test.receive(
+            1,
+            "A",
+            &[(tags::EncryptMethod, "0"), (tags::HeartBtInt, "30")],
+        );
+        test.session.next();
+        let sent: Vec<String> = test
+            .sent()
+            .iter()
+            .map(|message| message.get_string(tags::ClOrdID).unwrap_or_default())
+            .collect();
+        assert_eq!(sent, ["", "1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn commands_with_full_queue() {
+        let settings = "PersistOutboundQueue=Y\nOutboundQueueCapacity=1";
+        let mut test = TestSession::connected("CONTROL", settings);
+        let handle = Session::lookup(&test.session_id()).unwrap();
+        handle.try_send(order("1")).unwrap();
+        test.session.next();
+        handle.try_send(order("2")).unwrap();
+        assert!(matches!(
+            handle.try_send(order("3")),
+            Err(SessionError::QueueFull(_))
+        ));
+
+        let status = thread::scope(|scope| {
+            let status = scope.spawn(|| handle.status(Duration::from_secs(1)));
+            while !status.is_finished() {
+                test.session.next();
+            }
+            status.join().unwrap()
+        })
+        .unwrap();
+        assert!(!status.logged_on);
+
+        handle.disconnect().unwrap();
+        test.session.next();
+        let session_id = test.session_id();
+        assert!(test.log.events().contains(&format!(
+            "Session {session_id} disconnecting: Disconnected by administrator"
+        )));
+    }
+
+    #[test]
+    fn throttled_queue_full() {
+        let settings = "ThrottleRate=1\nThrottleAction=Queue\nOutboundQueueCapacity=2";
+        let mut test = TestSession::logged_on("THROTTLE", settings);
+        let session_id = test.session_id();
+        let handle = Session::lookup(&session_id).unwrap();
+        handle.try_send(order("1")).unwrap();
+        test.session.next();
+        assert_eq!(test.sent_msg_types(), ["D"]);
+
+        handle.try_send(order("2")).unwrap();
+        handle.try_send(order("3")).unwrap();
+        test.session.next();
+        test.session.next();
+        assert_eq!(test.session.throttle.as_ref().unwrap().pending_len(), 2);
+
+        handle.try_send(order("4")).unwrap();
+        handle.try_send(order("5")).unwrap();
+        test.session.next();
+        assert!(matches!(
+            handle.try_send(order("6")),
+            Err(SessionError::QueueFull(id)) if *id == session_id
+        ));
+        assert!(test.sent().is_empty());
+    }
+
+    #[test]
+    fn send_tickets() {
+        let mut test = TestSession::logged_on("TICKETS", "");
+        let handle = Session::lookup(&test.session_id()).unwrap();
+        let ticket = handle.try_send(order("1")).unwrap();
+        test.session.next();
+        assert_eq!(test.sent_msg_types(), ["D"]);
+        assert_eq!(
+            ticket.try_result().unwrap().unwrap(),
+            Sent {
+                msg_seq_num: 2,
+                written: true
+            }
+        );
+
+        test.app.set_do_not_send(true);
+        let ticket = handle.try_send(order("2")).unwrap();
+        test.session.next();
+        assert!(test.sent().is_empty());
+        assert!(matches!(
+            ticket.try_result(),
+            Some(Err(SendError::DoNotSend))
+        ));
+    }
+
+    #[test]
+    fn log_outgoing_utf8() {
+        let mut test = TestSession::logged_on("UTF8", "");
+        let handle = Session::lookup(&test.session_id()).unwrap();
+        let mut news = Message::default();
+        news.header_mut().set_tag_value(tags::MsgType, "B");
+        news.set_tag_value(tags::Headline, "olé");
+        handle.try_send(news).unwrap();
+        test.session.next();
+        assert_eq!(test.sent_msg_types(), ["B"]);
+        let outgoing = test.log.outgoing();
+        assert!(outgoing.last().unwrap().contains("\u{1}148=olé\u{1}"));
+    }
+
+    /// Records the outbound messages and drops resends of ClOrdID 2.
+    #[derive(Default)]
+    struct ResendInterceptor {
+        seen: Mutex<Vec<String>>,
+    }
+
+    impl Interceptor for ResendInterceptor {
+        fn before_send(&self, message: &mut Message, _session_id: &SessionId) -> Intercept {
+            let header = message.header();
+            let poss_dup = header.get_string(tags::PossDupFlag).unwrap_or_default();
+            let cl_ord_id = message.get_string(tags::ClOrdID).unwrap_or_default();
+            self.seen.lock().unwrap().push(format!(
+                "{} {cl_ord_id} {poss_dup}",
+                header.get_string(tags::MsgType).unwrap()
+            ));
+            match (poss_dup.as_str(), cl_ord_id.as_str()) {
+                ("Y", "2") => Intercept::Drop,
+                _ => Intercept::Continue,
+            }
+        }
+    }
+
+    #[test]
+    fn resend_interceptors() {
+        let mut test = TestSession::logged_on("RESEND", "");
+        let interceptor = Arc::new(ResendInterceptor::default());
+        let mut interceptors = Interceptors::default();
+        interceptors.push(interceptor.clone());
+        test.session.set_interceptors(interceptors);
+
+        let handle = Session::lookup(&test.session_id()).unwrap();
+        handle.try_send(order("1")).unwrap();
+        handle.try_send(order("2")).unwrap();
+        test.session.next();
+        test.session.next();
+        assert_eq!(test.sent_msg_types(), ["D", "D"]);
+
+        test.receive(2, "2", &[(tags::BeginSeqNo, "2"), (tags::EndSeqNo, "0")]);
+        let messages = test.sent();
+        let [resent, gap_fill] = messages.as_slice() else {
+            panic!("{messages:?}");
+        };
+        assert_eq!(resent.header().get_int(tags::MsgSeqNum).unwrap(), 2);
+        assert_eq!(resent.header().get_string(tags::PossDupFlag).unwrap(), "Y");
+        assert_eq!(resent.get_string(tags::ClOrdID).unwrap(), "1");
+        assert_eq!(gap_fill.header().get_string(tags::MsgType).unwrap(), "4");
+        assert_eq!(gap_fill.header().get_int(tags::MsgSeqNum).unwrap(), 3);
+        assert_eq!(gap_fill.get_int(tags::NewSeqNo).unwrap(), 4);
+        assert_eq!(
+            *interceptor.seen.lock().unwrap(),
+            ["D 1 ", "D 2 ", "D 1 Y", "D 2 Y", "4  Y"]
+        );
+    }
+}
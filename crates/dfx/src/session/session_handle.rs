@@ -0,0 +1,260 @@
+use std::sync::mpsc::{channel, Sender, SyncSender, TrySendError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use dfx_base::message::Message;
+use dfx_base::session_id::SessionId;
+
+use crate::session::{SendConfirmation, SendTicket, SessionError};
+
+/// A message queued for a connected session, sent on its own thread.
+pub(crate) type QueuedMessage = (Message, SendConfirmation);
+
+/// Administrative commands for a connected session, applied on its own thread before any queued
+/// message.
+#[derive(Debug)]
+pub(crate) enum SessionCommand {
+    SetNextSenderMsgSeqNum(u32),
+    SetNextTargetMsgSeqNum(u32),
+    SequenceReset(u32),
+    ResendRequest {
+        begin_seq_num: u32,
+        end_seq_num: u32,
+    },
+    Reset,
+    Logon,
+    Logout(Option<String>),
+    Disconnect,
+    Status(Sender<SessionStatus>),
+}
+
+/// Snapshot of a connected session, see [`SessionHandle::status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionStatus {
+    pub session_id: SessionId,
+    /// False after [`SessionHandle::logout`], until [`SessionHandle::logon`].
+    pub enabled: bool,
+    pub logged_on: bool,
+    pub next_sender_msg_seq_num: u32,
+    pub next_target_msg_seq_num: u32,
+}
+
+/// Handle to a connected session, returned by [`Session::lookup`](crate::session::Session::lookup).
+///
+/// Messages and commands are queued and applied by the session on its next tick, every applied
+/// command is logged as an event.
+///
+/// The message queue holds at most `OutboundQueueCapacity` messages, a full queue returns
+/// [`SessionError::QueueFull`]. Messages held until logon with `PersistOutboundQueue=Y`, or
+/// queued by `ThrottleAction=Queue`, are limited to the same capacity, while that is reached the
+/// session leaves further messages in the queue.
+///
+/// Commands such as [`SessionHandle::disconnect`] or [`SessionHandle::status`] have a queue of
+/// their own without a limit, and are applied before any queued message, so they also work when
+/// the message queue is full.
+///
+/// A handle returns [`SessionError::NotConnected`] once the session it was looked up for has
+/// disconnected.
+///
+/// ```ignore
+/// let session = Session::lookup(&session_id)?;
+/// session.set_next_target_msg_seq_num(1200)?;
+/// session.send_sequence_reset(3400)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SessionHandle {
+    session_id: SessionId,
+    sender: SyncSender<QueuedMessage>,
+    control: Sender<SessionCommand>,
+}
+
+impl SessionHandle {
+    pub(crate) fn new(
+        session_id: SessionId,
+        sender: SyncSender<QueuedMessage>,
+        control: Sender<SessionCommand>,
+    ) -> Self {
+        SessionHandle {
+            session_id,
+            sender,
+            control,
+        }
+    }
+
+    pub fn session_id(&self) -> &SessionId {
+        &self.session_id
+    }
+
+    /// Queues a message without blocking, the header is initialized by the session.
+    /// The returned [`SendTicket`] resolves once the message is sent.
+    pub fn try_send(&self, message: Message) -> Result<SendTicket, SessionError> {
+        let (confirmation, ticket) = SendConfirmation::new();
+        self.sender
+            .try_send((message, confirmation))
+            .map_err(|err| self.error(&err))?;
+        Ok(ticket)
+    }
+
+    /// Queues a message, waiting up to `timeout` for room in the queue.
+    pub fn send_timeout(
+        &self,
+        message: Message,
+        timeout: Duration,
+    ) -> Result<SendTicket, SessionError> {
+        let deadline = Instant::now() + timeout;
+        let (confirmation, ticket) = SendConfirmation::new();
+        let mut queued = (message, confirmation);
+        loop {
+            match self.sender.try_send(queued) {
+                Ok(()) => return Ok(ticket),
+                Err(TrySendError::Full(returned)) if Instant::now() < deadline => {
+                    queued = returned;
+                    thread::sleep(Duration::from_millis(1));
+                }
+                Err(err) => return Err(self.error(&err)),
+            }
+        }
+    }
+
+    pub fn set_next_sender_msg_seq_num(&self, seq_num: u32) -> Result<(), SessionError> {
+        self.command(SessionCommand::SetNextSenderMsgSeqNum(seq_num))
+    }
+
+    pub fn set_next_target_msg_seq_num(&self, seq_num: u32) -> Result<(), SessionError> {
+        self.command(SessionCommand::SetNextTargetMsgSeqNum(seq_num))
+    }
+
+    /// Sends a SequenceReset-Reset (GapFillFlag=N) and continues sending at `new_seq_no`.
+    /// Requires the session to be logged on.
+    pub fn send_sequence_reset(&self, new_seq_no: u32) -> Result<(), SessionError> {
+        self.command(SessionCommand::SequenceReset(new_seq_no))
+    }
+
+    /// Sends a ResendRequest for `begin_seq_num..=end_seq_num`, an `end_seq_num` of 0 requests
+    /// everything after `begin_seq_num`. Requires the session to be logged on.
+    pub fn send_resend_request(
+        &self,
+        begin_seq_num: u32,
+        end_seq_num: u32,
+    ) -> Result<(), SessionError> {
+        self.command(SessionCommand::ResendRequest {
+            begin_seq_num,
+            end_seq_num,
+        })
+    }
+
+    /// Resets the message store, both sequence numbers start at 1 again.
+    pub fn reset(&self) -> Result<(), SessionError> {
+        self.command(SessionCommand::Reset)
+    }
+
+    /// Enables a session that was logged out with [`SessionHandle::logout`], an initiator sends
+    /// a Logon again.
+    pub fn logon(&self) -> Result<(), SessionError> {
+        self.command(SessionCommand::Logon)
+    }
+
+    /// Disables the session and sends a Logout with `reason` as Text, the session disconnects
+    /// once the counterparty responds.
+    pub fn logout(&self, reason: Option<String>) -> Result<(), SessionError> {
+        self.command(SessionCommand::Logout(reason))
+    }
+
+    /// Drops the connection without sending a Logout.
+    pub fn disconnect(&self) -> Result<(), SessionError> {
+        self.command(SessionCommand::Disconnect)
+    }
+
+    /// Waits up to `timeout` for the session to report its status. Must not be called from the
+    /// session's own thread, e.g. from an [`Application`](crate::session::Application) callback.
+    pub fn status(&self, timeout: Duration) -> Result<SessionStatus, SessionError> {
+        let (sender, receiver) = channel();
+        self.command(SessionCommand::Status(sender))?;
+        receiver
+            .recv_timeout(timeout)
+            .map_err(|_| SessionError::Timeout(Box::new(self.session_id.clone())))
+    }
+
+    fn command(&self, command: SessionCommand) -> Result<(), SessionError> {
+        self.control
+            .send(command)
+            .map_err(|_| SessionError::NotConnected(Box::new(self.session_id.clone())))
+    }
+
+    fn error(&self, err: &TrySendError<QueuedMessage>) -> SessionError {
+        match err {
+            TrySendError::Full(_) => SessionError::QueueFull(Box::new(self.session_id.clone())),
+            TrySendError::Disconnected(_) => {
+                SessionError::NotConnected(Box::new(self.session_id.clone()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{channel, sync_channel};
+    use std::time::Duration;
+
+    use dfx_base::message::Message;
+
+    use dfx_base::session_id::SessionId;
+
+    use super::{SessionCommand, SessionHandle};
+    use crate::session::{Session, SessionError};
+
+    #[test]
+    fn session_handle() {
+        let session_id = SessionId::new("FIX.4.4", "SENDER", "", "", "TARGET", "", "");
+        assert!(matches!(
+            Session::lookup(&session_id),
+            Err(SessionError::SessionNotFound)
+        ));
+
+        let (sender, receiver) = sync_channel(2);
+        let (control, control_receiver) = channel();
+        let handle = SessionHandle::new(session_id, sender, control);
+        handle.set_next_sender_msg_seq_num(10).unwrap();
+        handle.send_resend_request(5, 0).unwrap();
+        assert!(matches!(
+            control_receiver.recv(),
+            Ok(SessionCommand::SetNextSenderMsgSeqNum(10))
+        ));
+        assert!(matches!(
+            control_receiver.recv(),
+            Ok(SessionCommand::ResendRequest {
+                begin_seq_num: 5,
+                end_seq_num: 0
+            })
+        ));
+
+        handle.try_send(Message::default()).unwrap();
+        handle.try_send(Message::default()).unwrap();
+        assert!(matches!(
+            handle.try_send(Message::default()),
+            Err(SessionError::QueueFull(_))
+        ));
+        assert!(matches!(
+            handle.send_timeout(Message::default(), Duration::from_millis(5)),
+            Err(SessionError::QueueFull(_))
+        ));
+        // Commands do not wait for room in the message queue
+        handle.disconnect().unwrap();
+        assert!(matches!(
+            control_receiver.try_recv(),
+            Ok(SessionCommand::Disconnect)
+        ));
+        assert!(receiver.recv().is_ok());
+        handle
+            .send_timeout(Message::default(), Duration::from_millis(5))
+            .unwrap();
+
+        drop(receiver);
+        drop(control_receiver);
+        assert!(matches!(
+            handle.try_send(Message::default()),
+            Err(SessionError::NotConnected(_))
+        ));
+        assert!(matches!(handle.reset(), Err(SessionError::NotConnected(_))));
+    }
+}
@@ -1,200 +1,610 @@
 use chrono::naive::Days;
-use chrono::{DateTime, Datelike, Duration, NaiveDateTime, NaiveTime, Timelike, Utc, Weekday};
+use chrono::{
+    DateTime, Datelike, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike, Utc, Weekday,
+};
 use chrono_tz::Tz;
+use derive_builder::Builder;
+
+/// When a session is up, when it may be logged on and when its sequence numbers are reset.
+///
+/// Times are interpreted in `timezone`, the system time zone if `use_localtime` is set, or UTC.
+#[derive(Builder, Debug, PartialEq, Eq, Clone)]
+pub struct SessionSchedule {
+    /// The session period, by default sequence numbers are reset when a new period starts.
+    period: SessionPeriod,
+    /// `LogonTime`/`LogoutTime`: the part of the session period in which the session is logged on.
+    #[builder(default)]
+    logon: Option<SessionPeriod>,
+    /// `ResetTime`: reset sequence numbers at this time instead of when a new period starts.
+    #[builder(default)]
+    reset: Option<ResetTime>,
+    /// `Holidays`: dates on which the session is not logged on.
+    #[builder(default)]
+    holidays: Vec<NaiveDate>,
+    #[builder(default)]
+    timezone: Option<Tz>,
+    #[builder(default)]
+    use_localtime: bool,
+}
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub(crate) enum SessionSchedule {
+pub enum SessionPeriod {
     NonStop,
     // #[cfg(test)]
+    #[doc(hidden)]
     EvenMinutes,
     Weekly {
         start_day: Weekday,
         end_day: Weekday,
         start_time: NaiveTime,
         end_time: NaiveTime,
-        timezone: Option<Tz>,
-        use_localtime: bool,
     },
     Daily {
         start_time: NaiveTime,
         end_time: NaiveTime,
-        timezone: Option<Tz>,
-        use_localtime: bool,
+        /// `Weekdays`: days on which the daily period starts, empty for every day.
+        weekdays: Vec<Weekday>,
     },
 }
 
+/// Moment at which sequence numbers are reset, daily or weekly on `day`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ResetTime {
+    pub day: Option<Weekday>,
+    pub time: NaiveTime,
+}
+
+/// A single occurrence of the session period, both ends inclusive.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SessionWindow {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+impl SessionWindow {
+    pub fn start(&self) -> DateTime<Utc> {
+        self.start
+    }
+
+    pub fn end(&self) -> DateTime<Utc> {
+        self.end
+    }
+
+    pub fn contains(&self, time: &DateTime<Utc>) -> bool {
+        &self.start <= time && time <= &self.end
+    }
+}
+
 impl SessionSchedule {
+    pub fn builder() -> SessionScheduleBuilder {
+        SessionScheduleBuilder::create_empty()
+    }
+
+    pub fn period(&self) -> &SessionPeriod {
+        &self.period
+    }
+
+    pub fn is_session_time(&self, time: &DateTime<Utc>) -> bool {
+        self.period.is_active(self.adjust_utc_datetime(*time))
+    }
+
+    /// True if the session may be logged on: within the session period and the logon window,
+    /// and not on a holiday.
+    pub fn is_logon_time(&self, time: &DateTime<Utc>) -> bool {
+        let now = self.adjust_utc_datetime(*time);
+        self.period.is_active(now)
+            && self.logon.as_ref().is_none_or(|logon| logon.is_active(now))
+            && !self.holidays.contains(&now.date())
+    }
+
+    /// Start of the first session window after `time`, `None` for a non stop session.
+    ///
+    /// Ignores `LogonTime`/`LogoutTime` and `Holidays`, use [`Self::next_logon_start`] to show
+    /// when the session will next be logged on.
+    pub fn next_start(&self, time: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.windows(time)
+            .map(|window| window.start())
+            .find(|start| start > time)
+    }
+
+    /// End of the current session window, or of the next one when `time` is out of session.
+    /// `None` for a non stop session.
+    ///
+    /// Ignores `LogonTime`/`LogoutTime` and `Holidays`, see [`Self::next_logon_end`].
+    pub fn next_end(&self, time: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.windows(time).next().map(|window| window.end())
+    }
+
+    /// The session windows that have not ended at `time`, in order.
+    ///
+    /// A start or end falling in a daylight saving gap is moved forward by the size of the gap,
+    /// an ambiguous one resolves to the first occurrence.
+    pub fn windows(&self, time: &DateTime<Utc>) -> impl Iterator<Item = SessionWindow> + '_ {
+        let time = *time;
+        self.period
+            .local_windows(self.adjust_utc_datetime(time))
+            .map(|(start, end)| SessionWindow {
+                start: self.to_utc(start),
+                end: self.to_utc(end),
+            })
+            .skip_while(move |window| window.end < time)
+    }
+
+    /// Start of the first logon window after `time`, see [`Self::logon_windows`].
+    pub fn next_logon_start(&self, time: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.logon_windows(time)
+            .map(|window| window.start())
+            .find(|start| start > time)
+    }
+
+    /// End of the current logon window, or of the next one when the session may not be logged
+    /// on at `time`, see [`Self::logon_windows`].
+    pub fn next_logon_end(&self, time: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.logon_windows(time).next().map(|window| window.end())
+    }
+
+    /// The windows in which the session may be logged on that have not ended at `time`, in
+    /// order: the session windows clipped to `LogonTime`/`LogoutTime`, without `Holidays`. This
+    /// is what [`Self::is_logon_time`] checks.
+    ///
+    /// Only windows starting within a year of `time` are returned, none for a non stop session
+    /// without `LogonTime`/`LogoutTime`.
+    pub fn logon_windows(&self, time: &DateTime<Utc>) -> impl Iterator<Item = SessionWindow> + '_ {
+        let time = *time;
+        let now = self.adjust_utc_datetime(time);
+        let horizon = now + Days::new(366);
+        let windows: Box<dyn Iterator<Item = (NaiveDateTime, NaiveDateTime)> + '_> =
+            match self.logon.as_ref() {
+                Some(logon) if logon != &SessionPeriod::NonStop => {
+                    if self.period == SessionPeriod::NonStop {
+                        Box::new(logon.local_windows_until(now, horizon))
+                    } else {
+                        Box::new(intersect(
+                            self.period.local_windows_until(now, horizon),
+                            logon.local_windows_until(now, horizon),
+                        ))
+                    }
+                }
+                _ => Box::new(self.period.local_windows_until(now, horizon)),
+            };
+        windows
+            .flat_map(move |(start, end)| self.without_holidays(start, end))
+            .map(|(start, end)| SessionWindow {
+                start: self.to_utc(start),
+                end: self.to_utc(end),
+            })
+            .skip_while(move |window| window.end < time)
+    }
+
+    /// Splits the local window `start..=end` around the holidays it contains.
+    fn without_holidays(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        let mut windows = Vec::new();
+        let mut window_start = None;
+        for date in start
+            .date()
+            .iter_days()
+            .take_while(|date| *date <= end.date())
+        {
+            if self.holidays.contains(&date) {
+                if let Some(window_start) = window_start.take() {
+                    let day_end = date.and_time(NaiveTime::MIN) - Duration::nanoseconds(1);
+                    windows.push((window_start, day_end));
+                }
+            } else if window_start.is_none() {
+                window_start = Some(start.max(date.and_time(NaiveTime::MIN)));
+            }
+        }
+        if let Some(window_start) = window_start {
+            windows.push((window_start, end));
+        }
+        windows
+    }
+
     pub(crate) fn is_new_session(&self, old_time: DateTime<Utc>, test_time: DateTime<Utc>) -> bool {
-        if self == &SessionSchedule::NonStop {
-            false
+        if old_time >= test_time {
+            return false;
+        }
+        match &self.reset {
+            Some(reset) => reset.passed_between(
+                self.adjust_utc_datetime(old_time),
+                self.adjust_utc_datetime(test_time),
+            ),
+            None => self.next_end(&old_time).is_some_and(|end| end < test_time),
+        }
+    }
+
+    fn adjust_utc_datetime(&self, now: DateTime<Utc>) -> NaiveDateTime {
+        if self.use_localtime {
+            now.with_timezone(&Local).naive_local()
+        } else if let Some(timezone) = self.timezone {
+            now.with_timezone(&timezone).naive_local()
         } else {
-            if old_time < test_time {
-                let next_end = self.next_end(old_time);
-                return old_time <= next_end && next_end < test_time;
-            }
-            false
+            now.naive_utc()
         }
     }
 
-    //TODO convert to chrono::DateTime
-    fn next_end(&self, old_time: DateTime<Utc>) -> DateTime<Utc> {
-        assert!(self != &Self::NonStop);
+    fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        if self.use_localtime {
+            resolve_local(&Local, local)
+        } else if let Some(timezone) = self.timezone {
+            resolve_local(&timezone, local)
+        } else {
+            Utc.from_utc_datetime(&local)
+        }
+    }
+}
+
+/// The overlaps of two ordered sequences of windows, both ends inclusive.
+fn intersect(
+    a: impl Iterator<Item = (NaiveDateTime, NaiveDateTime)>,
+    b: impl Iterator<Item = (NaiveDateTime, NaiveDateTime)>,
+) -> impl Iterator<Item = (NaiveDateTime, NaiveDateTime)> {
+    let mut a = a.peekable();
+    let mut b = b.peekable();
+    std::iter::from_fn(move || loop {
+        let (a_start, a_end) = *a.peek()?;
+        let (b_start, b_end) = *b.peek()?;
+        let overlap = (a_start.max(b_start), a_end.min(b_end));
+        if a_end <= b_end {
+            a.next();
+        } else {
+            b.next();
+        }
+        if overlap.0 <= overlap.1 {
+            return Some(overlap);
+        }
+    })
+}
+
+fn resolve_local<T: TimeZone>(timezone: &T, local: NaiveDateTime) -> DateTime<Utc> {
+    match timezone.from_local_datetime(&local) {
+        LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => time.with_timezone(&Utc),
+        LocalResult::None => {
+            // Skipped by a transition, use the offset from before it.
+            let offset = timezone
+                .offset_from_utc_datetime(&(local - Days::new(1)))
+                .fix();
+            Utc.from_utc_datetime(&(local - Duration::seconds(offset.local_minus_utc().into())))
+        }
+    }
+}
+
+impl SessionPeriod {
+    fn is_active(&self, now: NaiveDateTime) -> bool {
         match self {
-            SessionSchedule::NonStop => unreachable!(),
+            SessionPeriod::NonStop => true,
             // #[cfg(test)]
-            SessionSchedule::EvenMinutes => {
-                let mut end = old_time;
-                if end.minute() % 2 == 1 {
-                    end += Duration::minutes(1);
-                }
-                end += Duration::minutes(2);
-                end
-            }
-            SessionSchedule::Weekly { end_day, .. } => {
-                let mut end = old_time;
-                let d = old_time;
-                while &end.weekday() != end_day {
-                    end = end + Days::new(1);
-                }
-                if d > end {
-                    // d is later than end
-                    end = end + Days::new(7);
-                }
-                end
-            }
-            SessionSchedule::Daily { .. } => {
-                let mut end = old_time;
-                let d = old_time;
-                if d > end {
-                    // d is later than end
-                    end = end + Days::new(1);
-                }
-                end
+            SessionPeriod::EvenMinutes => now.minute() % 2 == 0,
+            SessionPeriod::Weekly { .. } | SessionPeriod::Daily { .. } => {
+                // A window lasts at most a week, look for one that started in the past week.
+                (0..=7)
+                    .filter_map(|days| self.window_starting_on(now.date() - Days::new(days)))
+                    .any(|(start, end)| start <= now && now <= end)
             }
         }
     }
 
-    pub(crate) fn is_session_time(&self, time: &DateTime<Utc>) -> bool {
-        let now = self.adjust_utc_datetime(*time);
+    /// Windows in local time, starting with any window that could contain `now`.
+    fn local_windows(
+        &self,
+        now: NaiveDateTime,
+    ) -> Box<dyn Iterator<Item = (NaiveDateTime, NaiveDateTime)> + '_> {
         match self {
-            SessionSchedule::NonStop => true,
+            SessionPeriod::NonStop => Box::new(std::iter::empty()),
             // #[cfg(test)]
-            SessionSchedule::EvenMinutes => now.minute() % 2 == 0,
-            SessionSchedule::Weekly { .. } => self.check_day(now),
-            SessionSchedule::Daily { .. } => self.check_time(&now.time()),
+            SessionPeriod::EvenMinutes => {
+                let start = now.date().and_hms_opt(now.hour(), now.minute() & !1, 0);
+                Box::new(
+                    std::iter::successors(start, |start| Some(*start + Duration::minutes(2)))
+                        .map(|start| (start, start + Duration::seconds(59))),
+                )
+            }
+            SessionPeriod::Weekly { .. } | SessionPeriod::Daily { .. } => {
+                let first = now.date() - Days::new(7);
+                Box::new(
+                    first
+                        .iter_days()
+                        .filter_map(|date| self.window_starting_on(date)),
+                )
+            }
         }
     }
 
-    fn check_day(&self, datetime: NaiveDateTime) -> bool {
-        assert!(matches!(self, SessionSchedule::Weekly { .. }));
+    /// Like `local_windows`, without the windows starting after `horizon`.
+    fn local_windows_until(
+        &self,
+        now: NaiveDateTime,
+        horizon: NaiveDateTime,
+    ) -> impl Iterator<Item = (NaiveDateTime, NaiveDateTime)> + '_ {
+        self.local_windows(now)
+            .take_while(move |(start, _)| *start <= horizon)
+    }
+
+    /// The window starting on `date`, the period belongs to the day it starts on.
+    fn window_starting_on(&self, date: NaiveDate) -> Option<(NaiveDateTime, NaiveDateTime)> {
         match self {
-            SessionSchedule::NonStop => unreachable!(),
+            SessionPeriod::NonStop => None,
             // #[cfg(test)]
-            SessionSchedule::EvenMinutes => unreachable!(),
-            SessionSchedule::Weekly {
+            SessionPeriod::EvenMinutes => None,
+            SessionPeriod::Weekly {
                 start_day,
                 end_day,
                 start_time,
                 end_time,
-                ..
             } => {
-                if start_day.num_days_from_monday() < end_day.num_days_from_monday() {
-                    if datetime.weekday().num_days_from_monday() < start_day.num_days_from_monday()
-                        || datetime.weekday().num_days_from_monday()
-                            > end_day.num_days_from_monday()
-                    {
-                        false
-                    } else if datetime.weekday().num_days_from_monday()
-                        < end_day.num_days_from_monday()
-                    {
-                        start_day.num_days_from_monday() < datetime.weekday().num_days_from_monday()
-                            || start_time <= &datetime.time()
-                    } else {
-                        end_day.num_days_from_monday() > datetime.weekday().num_days_from_monday()
-                            || end_time >= &datetime.time()
-                    }
-                } else if end_day.num_days_from_monday() > start_day.num_days_from_monday() {
-                    if datetime.weekday().num_days_from_monday() < end_day.num_days_from_monday()
-                        || datetime.weekday().num_days_from_monday()
-                            > start_day.num_days_from_monday()
-                    {
-                        false
-                    } else if datetime.weekday().num_days_from_monday()
-                        < start_day.num_days_from_monday()
-                    {
-                        end_day.num_days_from_monday() < datetime.weekday().num_days_from_monday()
-                            || end_time <= &datetime.time()
-                    } else {
-                        start_day.num_days_from_monday() > datetime.weekday().num_days_from_monday()
-                            || start_time >= &datetime.time()
-                    }
-                } else if start_time >= end_time {
-                    &datetime.weekday() != start_day || self.check_time(&datetime.time())
-                } else {
-                    &datetime.weekday() == start_day && self.check_time(&datetime.time())
+                if &date.weekday() != start_day {
+                    return None;
+                }
+                let mut days =
+                    (7 + end_day.num_days_from_monday() - start_day.num_days_from_monday()) % 7;
+                if days == 0 && end_time <= start_time {
+                    days = 7;
                 }
+                let end = date + Days::new(days.into());
+                Some((date.and_time(*start_time), end.and_time(*end_time)))
             }
-            SessionSchedule::Daily { .. } => unreachable!(),
-        }
-    }
-
-    fn check_time(&self, time: &NaiveTime) -> bool {
-        assert!(
-            matches!(self, SessionSchedule::Daily { .. })
-                || matches!(self, SessionSchedule::Weekly { .. })
-        );
-        let (start_time, end_time) = match self {
-            SessionSchedule::NonStop => unreachable!(),
-            // #[cfg(test)]
-            SessionSchedule::EvenMinutes => unreachable!(),
-            SessionSchedule::Weekly {
-                start_time,
-                end_time,
-                ..
-            } => (start_time, end_time),
-            SessionSchedule::Daily {
+            SessionPeriod::Daily {
                 start_time,
                 end_time,
-                ..
-            } => (start_time, end_time),
-        };
-
-        if start_time < end_time {
-            time >= start_time && time <= end_time
-        } else if end_time < start_time {
-            time >= start_time || time <= end_time
-        } else {
-            true
+                weekdays,
+            } => {
+                if !weekdays.is_empty() && !weekdays.contains(&date.weekday()) {
+                    return None;
+                }
+                let end = if end_time <= start_time {
+                    date + Days::new(1)
+                } else {
+                    date
+                };
+                Some((date.and_time(*start_time), end.and_time(*end_time)))
+            }
         }
     }
+}
 
-    fn adjust_utc_datetime(&self, now: DateTime<Utc>) -> NaiveDateTime {
-        if self.use_local_time() {
-            now.naive_local()
-        } else if let Some(timezone) = self.timezone() {
-            now.with_timezone(timezone).naive_local()
-        } else {
-            now.naive_utc()
-        }
+impl ResetTime {
+    /// True if a reset moment lies in `(old_time, test_time]`.
+    fn passed_between(&self, old_time: NaiveDateTime, test_time: NaiveDateTime) -> bool {
+        old_time < test_time && old_time < self.last_before(test_time)
     }
 
-    fn use_local_time(&self) -> bool {
-        match self {
-            SessionSchedule::NonStop => false,
-            // #[cfg(test)]
-            SessionSchedule::EvenMinutes => false,
-            SessionSchedule::Weekly { use_localtime, .. } => *use_localtime,
-            SessionSchedule::Daily { use_localtime, .. } => *use_localtime,
+    /// The latest reset moment at or before `time`.
+    fn last_before(&self, time: NaiveDateTime) -> NaiveDateTime {
+        let mut reset = time.date().and_time(self.time);
+        if let Some(day) = self.day {
+            while reset.weekday() != day {
+                reset = reset - Days::new(1);
+            }
+        }
+        if reset > time {
+            reset = reset - Days::new(if self.day.is_some() { 7 } else { 1 });
         }
+        reset
     }
+}
 
-    fn timezone(&self) -> Option<&Tz> {
-        match self {
-            SessionSchedule::NonStop => None,
-            // #[cfg(test)]
-            SessionSchedule::EvenMinutes => None,
-            SessionSchedule::Weekly { timezone, .. } => timezone.as_ref(),
-            SessionSchedule::Daily { timezone, .. } => timezone.as_ref(),
-        }
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+
+    use super::{ResetTime, SessionPeriod, SessionSchedule};
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> chrono::DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn daily_weekdays() {
+        // 2023-06-05 is a monday
+        let schedule = SessionSchedule::builder()
+            .period(SessionPeriod::Daily {
+                start_time: time(22, 0),
+                end_time: time(6, 0),
+                weekdays: vec![Weekday::Mon, Weekday::Tue],
+            })
+            .build()
+            .unwrap();
+        assert!(!schedule.is_session_time(&utc(2023, 6, 5, 5, 0)));
+        assert!(schedule.is_session_time(&utc(2023, 6, 5, 23, 0)));
+        assert!(schedule.is_session_time(&utc(2023, 6, 6, 5, 0)));
+        assert!(schedule.is_session_time(&utc(2023, 6, 7, 5, 0)));
+        assert!(!schedule.is_session_time(&utc(2023, 6, 7, 23, 0)));
+        assert!(!schedule.is_session_time(&utc(2023, 6, 8, 5, 0)));
+        assert_eq!(
+            schedule.next_start(&utc(2023, 6, 7, 5, 0)),
+            Some(utc(2023, 6, 12, 22, 0))
+        );
+    }
+
+    #[test]
+    fn daily_next_end() {
+        let schedule = SessionSchedule::builder()
+            .period(SessionPeriod::Daily {
+                start_time: time(8, 0),
+                end_time: time(17, 0),
+                weekdays: Vec::new(),
+            })
+            .build()
+            .unwrap();
+        let now = utc(2023, 6, 5, 10, 0);
+        assert_eq!(schedule.next_end(&now), Some(utc(2023, 6, 5, 17, 0)));
+        assert_eq!(schedule.next_start(&now), Some(utc(2023, 6, 6, 8, 0)));
+        let now = utc(2023, 6, 5, 18, 0);
+        assert_eq!(schedule.next_end(&now), Some(utc(2023, 6, 6, 17, 0)));
+        assert_eq!(schedule.next_start(&now), Some(utc(2023, 6, 6, 8, 0)));
+
+        assert!(!schedule.is_new_session(utc(2023, 6, 5, 9, 0), utc(2023, 6, 5, 16, 0)));
+        assert!(schedule.is_new_session(utc(2023, 6, 5, 9, 0), utc(2023, 6, 6, 9, 0)));
+        assert!(!schedule.is_new_session(utc(2023, 6, 5, 18, 0), utc(2023, 6, 6, 9, 0)));
+    }
+
+    #[test]
+    fn weekly_across_weekend() {
+        let schedule = SessionSchedule::builder()
+            .period(SessionPeriod::Weekly {
+                start_day: Weekday::Sun,
+                end_day: Weekday::Fri,
+                start_time: time(18, 0),
+                end_time: time(17, 0),
+            })
+            .build()
+            .unwrap();
+        // 2023-06-07 is a wednesday
+        assert!(schedule.is_session_time(&utc(2023, 6, 7, 12, 0)));
+        assert!(!schedule.is_session_time(&utc(2023, 6, 9, 18, 0)));
+        assert!(!schedule.is_session_time(&utc(2023, 6, 10, 12, 0)));
+        assert!(schedule.is_session_time(&utc(2023, 6, 11, 18, 0)));
+
+        let windows: Vec<_> = schedule.windows(&utc(2023, 6, 7, 12, 0)).take(2).collect();
+        assert_eq!(windows[0].start(), utc(2023, 6, 4, 18, 0));
+        assert_eq!(windows[0].end(), utc(2023, 6, 9, 17, 0));
+        assert_eq!(windows[1].start(), utc(2023, 6, 11, 18, 0));
+        assert_eq!(windows[1].end(), utc(2023, 6, 16, 17, 0));
+    }
+
+    #[test]
+    fn windows_across_dst() {
+        let schedule = SessionSchedule::builder()
+            .period(SessionPeriod::Daily {
+                start_time: time(2, 30),
+                end_time: time(16, 0),
+                weekdays: Vec::new(),
+            })
+            .timezone(Some(chrono_tz::America::New_York))
+            .build()
+            .unwrap();
+        // Clocks go forward at 02:00 on 2023-03-12, 02:30 is skipped
+        let windows: Vec<_> = schedule.windows(&utc(2023, 3, 11, 12, 0)).take(3).collect();
+        assert_eq!(windows[0].start(), utc(2023, 3, 11, 7, 30));
+        assert_eq!(windows[0].end(), utc(2023, 3, 11, 21, 0));
+        assert_eq!(windows[1].start(), utc(2023, 3, 12, 7, 30));
+        assert_eq!(windows[1].end(), utc(2023, 3, 12, 20, 0));
+        assert_eq!(windows[2].start(), utc(2023, 3, 13, 6, 30));
+        assert_eq!(windows[2].end(), utc(2023, 3, 13, 20, 0));
+        assert!(windows[0].contains(&utc(2023, 3, 11, 12, 0)));
+        assert!(schedule.is_session_time(&utc(2023, 3, 13, 19, 59)));
+        assert!(!schedule.is_session_time(&utc(2023, 3, 13, 20, 1)));
+    }
+
+    #[test]
+    fn non_stop() {
+        let schedule = SessionSchedule::builder()
+            .period(SessionPeriod::NonStop)
+            .build()
+            .unwrap();
+        let now = utc(2023, 6, 5, 10, 0);
+        assert!(schedule.is_session_time(&now));
+        assert_eq!(schedule.next_start(&now), None);
+        assert_eq!(schedule.next_end(&now), None);
+        assert!(!schedule.is_new_session(now, utc(2024, 6, 5, 10, 0)));
+    }
+
+    #[test]
+    fn logon_time_and_holidays() {
+        let schedule = SessionSchedule::builder()
+            .period(SessionPeriod::Weekly {
+                start_day: Weekday::Sun,
+                end_day: Weekday::Fri,
+                start_time: time(18, 0),
+                end_time: time(17, 0),
+            })
+            .logon(Some(SessionPeriod::Daily {
+                start_time: time(7, 0),
+                end_time: time(16, 0),
+                weekdays: Vec::new(),
+            }))
+            .holidays(vec![NaiveDate::from_ymd_opt(2023, 6, 7).unwrap()])
+            .build()
+            .unwrap();
+        assert!(schedule.is_session_time(&utc(2023, 6, 6, 6, 0)));
+        assert!(!schedule.is_logon_time(&utc(2023, 6, 6, 6, 0)));
+        assert!(schedule.is_logon_time(&utc(2023, 6, 6, 12, 0)));
+        assert!(schedule.is_session_time(&utc(2023, 6, 7, 12, 0)));
+        assert!(!schedule.is_logon_time(&utc(2023, 6, 7, 12, 0)));
+        assert!(!schedule.is_logon_time(&utc(2023, 6, 10, 12, 0)));
+
+        // Logon opens at 07:00, not when the session starts on sunday 18:00
+        let now = utc(2023, 6, 4, 12, 0);
+        assert_eq!(schedule.next_start(&now), Some(utc(2023, 6, 4, 18, 0)));
+        assert_eq!(schedule.next_logon_start(&now), Some(utc(2023, 6, 5, 7, 0)));
+        assert_eq!(schedule.next_logon_end(&now), Some(utc(2023, 6, 5, 16, 0)));
+        // The holiday on wednesday is skipped
+        let now = utc(2023, 6, 6, 12, 0);
+        assert_eq!(schedule.next_logon_end(&now), Some(utc(2023, 6, 6, 16, 0)));
+        assert_eq!(schedule.next_logon_start(&now), Some(utc(2023, 6, 8, 7, 0)));
+        let windows: Vec<_> = schedule.logon_windows(&now).take(3).collect();
+        assert_eq!(windows[1].start(), utc(2023, 6, 8, 7, 0));
+        assert_eq!(windows[2].start(), utc(2023, 6, 9, 7, 0));
+        assert_eq!(windows[2].end(), utc(2023, 6, 9, 16, 0));
+    }
+
+    #[test]
+    fn logon_windows_across_holiday() {
+        let schedule = SessionSchedule::builder()
+            .period(SessionPeriod::Weekly {
+                start_day: Weekday::Mon,
+                end_day: Weekday::Fri,
+                start_time: time(8, 0),
+                end_time: time(17, 0),
+            })
+            .holidays(vec![NaiveDate::from_ymd_opt(2023, 6, 7).unwrap()])
+            .build()
+            .unwrap();
+        let windows: Vec<_> = schedule
+            .logon_windows(&utc(2023, 6, 5, 12, 0))
+            .take(3)
+            .collect();
+        assert_eq!(windows[0].start(), utc(2023, 6, 5, 8, 0));
+        assert_eq!(
+            windows[0].end(),
+            utc(2023, 6, 7, 0, 0) - chrono::Duration::nanoseconds(1)
+        );
+        assert_eq!(windows[1].start(), utc(2023, 6, 8, 0, 0));
+        assert_eq!(windows[1].end(), utc(2023, 6, 9, 17, 0));
+        assert_eq!(windows[2].start(), utc(2023, 6, 12, 8, 0));
+
+        let non_stop = SessionSchedule::builder()
+            .period(SessionPeriod::NonStop)
+            .build()
+            .unwrap();
+        assert_eq!(non_stop.next_logon_start(&utc(2023, 6, 5, 12, 0)), None);
+    }
+
+    #[test]
+    fn reset_time() {
+        let schedule = SessionSchedule::builder()
+            .period(SessionPeriod::NonStop)
+            .reset(Some(ResetTime {
+                day: None,
+                time: time(17, 0),
+            }))
+            .timezone(Some(chrono_tz::America::New_York))
+            .build()
+            .unwrap();
+        // 17:00 New York is 21:00 UTC during daylight saving time
+        assert!(!schedule.is_new_session(utc(2023, 6, 5, 20, 0), utc(2023, 6, 5, 20, 59)));
+        assert!(schedule.is_new_session(utc(2023, 6, 5, 20, 0), utc(2023, 6, 5, 21, 0)));
+        assert!(!schedule.is_new_session(utc(2023, 6, 5, 21, 0), utc(2023, 6, 6, 20, 0)));
+        assert!(schedule.is_new_session(utc(2023, 6, 5, 21, 0), utc(2023, 6, 7, 20, 0)));
+
+        let weekly = SessionSchedule::builder()
+            .period(SessionPeriod::NonStop)
+            .reset(Some(ResetTime {
+                day: Some(Weekday::Sun),
+                time: time(17, 0),
+            }))
+            .build()
+            .unwrap();
+        assert!(!weekly.is_new_session(utc(2023, 6, 5, 0, 0), utc(2023, 6, 10, 23, 0)));
+        assert!(weekly.is_new_session(utc(2023, 6, 5, 0, 0), utc(2023, 6, 11, 17, 0)));
     }
 }
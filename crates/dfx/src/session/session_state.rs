@@ -411,6 +411,7 @@ impl<Log: Logger> SessionState<Log> {
         let resend_range = ResetRange {
             begin_seq_num: begin,
             end_seq_num: end,
+            chunk_begin_seq_num: begin,
             chunk_end_seq_num: Some(chunk_end),
         };
         self.resend_range.replace(resend_range);
@@ -1,15 +1,22 @@
-use std::{fs::File, io::Read, net::ToSocketAddrs};
+use std::collections::BTreeMap;
+use std::net::ToSocketAddrs;
 
-use chrono::NaiveTime;
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use dfx_base::data_dictionary::DataDictionary;
 
-use crate::session::SessionSchedule;
+use crate::connection::{TlsAcceptor, TlsConfig, TlsConnector};
+use crate::session::{ResetTime, SessionPeriod, SessionSchedule, ThrottleAction};
+use dfx_base::encoding::MessageEncoding;
 use dfx_base::fields::converters::datetime::DateTimeFormat;
+use dfx_base::fix_values::BeginString;
 use dfx_base::session_id::SessionId;
-use native_tls::{Certificate, Identity, Protocol, TlsAcceptor, TlsConnector};
+use dfx_base::validation_rules::ValidationRules;
 
 use super::{
-    ConnectionType, LoggingOptions, Persistence, SessionSetting, SessionSettingsError,
-    SettingOption, SettingsConnection, SocketOptions, SslOptions, ValidationOptions,
+    ClientIdentity, ConnectionType, DataDictionaries, LoggingOptions, LogonOptions,
+    OutboundQueueOptions, Persistence, ProxyOptions, ProxyType, SessionSetting,
+    SessionSettingsError, SettingOption, SettingsConnection, SocketOptions, SslOptions,
+    ThrottleOptions, ValidationOptions,
 };
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -24,6 +31,7 @@ pub(crate) struct DynamicSessionSettingBuilder {
     target_location_id: Option<String>,
     session_qualifier: Option<String>,
     default_appl_ver_id: Option<String>,
+    message_encoding: Option<String>,
     connection_type: Option<String>,
     non_stop_session: Option<String>,
     use_local_time: Option<String>,
@@ -32,6 +40,14 @@ pub(crate) struct DynamicSessionSettingBuilder {
     end_day: Option<String>,
     start_time: Option<String>,
     end_time: Option<String>,
+    weekdays: Option<String>,
+    logon_time: Option<String>,
+    logout_time: Option<String>,
+    logon_day: Option<String>,
+    logout_day: Option<String>,
+    reset_time: Option<String>,
+    reset_day: Option<String>,
+    holidays: Option<String>,
     milliseconds_in_time_stamp: Option<String>,
     refresh_on_logon: Option<String>,
     reset_on_logon: Option<String>,
@@ -41,6 +57,7 @@ pub(crate) struct DynamicSessionSettingBuilder {
     resend_session_level_rejects: Option<String>,
     time_stamp_precision: Option<String>,
     enable_last_msg_seq_num_processed: Option<String>,
+    enable_next_expected_msg_seq_num: Option<String>,
     max_messages_in_resend_request: Option<String>,
     send_logout_before_disconnect_from_timeout: Option<String>,
     ignore_poss_dup_resend_requests: Option<String>,
@@ -51,6 +68,10 @@ pub(crate) struct DynamicSessionSettingBuilder {
     data_dictionary: Option<String>,
     transport_data_dictionary: Option<String>,
     app_data_dictionary: Option<String>,
+    data_dictionary_overlays: BTreeMap<u32, String>,
+    transport_data_dictionary_overlays: BTreeMap<u32, String>,
+    app_data_dictionary_overlays: BTreeMap<u32, String>,
+    validation_rules: Option<String>,
     validate_fields_out_of_order: Option<String>,
     validate_fields_have_values: Option<String>,
     validate_user_defined_fields: Option<String>,
@@ -59,6 +80,21 @@ pub(crate) struct DynamicSessionSettingBuilder {
     check_latency: Option<String>,
     max_latency: Option<String>,
 
+    // logon options
+    username: Option<String>,
+    password: Option<String>,
+    raw_data: Option<String>,
+
+    // throttle options
+    throttle_rate: Option<String>,
+    throttle_burst: Option<String>,
+    throttle_msg_type_rates: Option<String>,
+    throttle_action: Option<String>,
+
+    // outbound queue options
+    outbound_queue_capacity: Option<String>,
+    persist_outbound_queue: Option<String>,
+
     // initiator options
     reconnect_interval: Option<String>,
     heart_bt_int: Option<String>,
@@ -90,6 +126,13 @@ pub(crate) struct DynamicSessionSettingBuilder {
     socket_send_timeout: Option<String>,
     socket_receive_timeout: Option<String>,
 
+    // Proxy options
+    socket_proxy_type: Option<String>,
+    socket_proxy_host: Option<String>,
+    socket_proxy_port: Option<String>,
+    socket_proxy_user: Option<String>,
+    socket_proxy_password: Option<String>,
+
     // SSL options
     ssl_enable: Option<String>,
     ssl_server_name: Option<String>,
@@ -103,12 +146,14 @@ pub(crate) struct DynamicSessionSettingBuilder {
     ssl_certificate_password: Option<String>,
     ssl_require_client_certificate: Option<String>,
     ssl_ca_certificate: Option<String>,
+    ssl_client_certificate_identity: Option<String>,
+    ssl_reload_certificates: Option<String>,
 }
 
-pub(crate) struct Validated(DynamicSessionSettingBuilder);
+pub(crate) struct Validated(DynamicSessionSettingBuilder, DataDictionaries);
 impl Validated {
     pub(crate) fn build(self) -> Result<SessionSetting, SessionSettingsError> {
-        self.0.build()
+        self.0.build(self.1)
     }
 }
 
@@ -125,6 +170,7 @@ impl DynamicSessionSettingBuilder {
             SettingOption::TargetLocationID => self.target_location_id = Some(value.into()),
             SettingOption::SessionQualifier => self.session_qualifier = Some(value.into()),
             SettingOption::DefaultApplVerID => self.default_appl_ver_id = Some(value.into()),
+            SettingOption::MessageEncoding => self.message_encoding = Some(value.into()),
             SettingOption::ConnectionType => self.connection_type = Some(value.into()),
             SettingOption::UseDataDictionary => self.use_data_dictionary = Some(value.into()),
             SettingOption::NonStopSession => self.non_stop_session = Some(value.into()),
@@ -134,6 +180,14 @@ impl DynamicSessionSettingBuilder {
             SettingOption::EndDay => self.end_day = Some(value.into()),
             SettingOption::StartTime => self.start_time = Some(value.into()),
             SettingOption::EndTime => self.end_time = Some(value.into()),
+            SettingOption::Weekdays => self.weekdays = Some(value.into()),
+            SettingOption::LogonTime => self.logon_time = Some(value.into()),
+            SettingOption::LogoutTime => self.logout_time = Some(value.into()),
+            SettingOption::LogonDay => self.logon_day = Some(value.into()),
+            SettingOption::LogoutDay => self.logout_day = Some(value.into()),
+            SettingOption::ResetTime => self.reset_time = Some(value.into()),
+            SettingOption::ResetDay => self.reset_day = Some(value.into()),
+            SettingOption::Holidays => self.holidays = Some(value.into()),
             SettingOption::HeartBtInt => self.heart_bt_int = Some(value.into()),
             SettingOption::SocketAcceptHost => self.socket_accept_host = Some(value.into()),
             SettingOption::SocketAcceptPort => self.socket_accept_port = Some(value.into()),
@@ -167,6 +221,7 @@ impl DynamicSessionSettingBuilder {
                 self.transport_data_dictionary = Some(value.into());
             }
             SettingOption::AppDataDictionary => self.app_data_dictionary = Some(value.into()),
+            SettingOption::ValidationRules => self.validation_rules = Some(value.into()),
             SettingOption::PersistMessages => self.persist_messages = Some(value.into()),
             SettingOption::LogonTimeout => self.logon_timeout = Some(value.into()),
             SettingOption::LogoutTimeout => self.logout_timeout = Some(value.into()),
@@ -183,6 +238,9 @@ impl DynamicSessionSettingBuilder {
             SettingOption::EnableLastMsgSeqNumProcessed => {
                 self.enable_last_msg_seq_num_processed = Some(value.into());
             }
+            SettingOption::EnableNextExpectedMsgSeqNum => {
+                self.enable_next_expected_msg_seq_num = Some(value.into());
+            }
             SettingOption::MaxMessagesInResendRequest => {
                 self.max_messages_in_resend_request = Some(value.into());
             }
@@ -198,6 +256,13 @@ impl DynamicSessionSettingBuilder {
             }
             SettingOption::SocketSendTimeout => self.socket_send_timeout = Some(value.into()),
             SettingOption::SocketReceiveTimeout => self.socket_receive_timeout = Some(value.into()),
+            SettingOption::SocketProxyType => self.socket_proxy_type = Some(value.into()),
+            SettingOption::SocketProxyHost => self.socket_proxy_host = Some(value.into()),
+            SettingOption::SocketProxyPort => self.socket_proxy_port = Some(value.into()),
+            SettingOption::SocketProxyUser => self.socket_proxy_user = Some(value.into()),
+            SettingOption::SocketProxyPassword => {
+                self.socket_proxy_password = Some(value.into());
+            }
             SettingOption::IgnorePossDupResendRequests => {
                 self.ignore_poss_dup_resend_requests = Some(value.into());
             }
@@ -206,6 +271,21 @@ impl DynamicSessionSettingBuilder {
             }
             SettingOption::CheckLatency => self.check_latency = Some(value.into()),
             SettingOption::MaxLatency => self.max_latency = Some(value.into()),
+            SettingOption::Username => self.username = Some(value.into()),
+            SettingOption::Password => self.password = Some(value.into()),
+            SettingOption::RawData => self.raw_data = Some(value.into()),
+            SettingOption::ThrottleRate => self.throttle_rate = Some(value.into()),
+            SettingOption::ThrottleBurst => self.throttle_burst = Some(value.into()),
+            SettingOption::ThrottleMsgTypeRates => {
+                self.throttle_msg_type_rates = Some(value.into());
+            }
+            SettingOption::ThrottleAction => self.throttle_action = Some(value.into()),
+            SettingOption::OutboundQueueCapacity => {
+                self.outbound_queue_capacity = Some(value.into());
+            }
+            SettingOption::PersistOutboundQueue => {
+                self.persist_outbound_queue = Some(value.into());
+            }
             SettingOption::SSLEnable => self.ssl_enable = Some(value.into()),
             SettingOption::SSLServerName => self.ssl_server_name = Some(value.into()),
             SettingOption::SSLMinProtocol => self.ssl_min_protocol = Some(value.into()),
@@ -228,9 +308,34 @@ impl DynamicSessionSettingBuilder {
                 self.ssl_require_client_certificate = Some(value.into());
             }
             SettingOption::SSLCACertificate => self.ssl_ca_certificate = Some(value.into()),
+            SettingOption::SSLClientCertificateIdentity => {
+                self.ssl_client_certificate_identity = Some(value.into());
+            }
+            SettingOption::SSLReloadCertificates => {
+                self.ssl_reload_certificates = Some(value.into());
+            }
         }
     }
 
+    /// `DataDictionary.1`, `TransportDataDictionary.1` or `AppDataDictionary.1`, overlays merged
+    /// into the dictionary in the order of their index.
+    fn set_overlay(&mut self, key: &str, value: &str) -> Result<bool, SessionSettingsError> {
+        let Some((name, Ok(index))) = key
+            .rsplit_once('.')
+            .map(|(name, index)| (name, index.parse::<u32>()))
+        else {
+            return Ok(false);
+        };
+        let overlays = match name.try_into()? {
+            SettingOption::DataDictionary => &mut self.data_dictionary_overlays,
+            SettingOption::TransportDataDictionary => &mut self.transport_data_dictionary_overlays,
+            SettingOption::AppDataDictionary => &mut self.app_data_dictionary_overlays,
+            _ => return Err(SessionSettingsError::NoSuchSetting(key.into())),
+        };
+        overlays.insert(index, value.into());
+        Ok(true)
+    }
+
     pub(crate) fn set_from_line(
         &mut self,
         line_num: usize,
@@ -247,9 +352,11 @@ impl DynamicSessionSettingBuilder {
                 reason: "Too many '=' in line.".into(),
             })
         } else if setting.len() == 2 {
-            let option: SettingOption = setting[0].try_into()?;
             let value = setting[1];
-            self.set(option, value);
+            if !self.set_overlay(setting[0], value)? {
+                let option: SettingOption = setting[0].try_into()?;
+                self.set(option, value);
+            }
             Ok(())
         } else {
             Err(SessionSettingsError::LineParseError {
@@ -273,6 +380,7 @@ impl DynamicSessionSettingBuilder {
         self.default_appl_ver_id = self
             .default_appl_ver_id
             .or(other.default_appl_ver_id.clone());
+        self.message_encoding = self.message_encoding.or(other.message_encoding.clone());
         self.connection_type = self.connection_type.or(other.connection_type.clone());
         self.non_stop_session = self.non_stop_session.or(other.non_stop_session.clone());
         self.use_local_time = self.use_local_time.or(other.use_local_time.clone());
@@ -281,6 +389,14 @@ impl DynamicSessionSettingBuilder {
         self.end_day = self.end_day.or(other.end_day.clone());
         self.start_time = self.start_time.or(other.start_time.clone());
         self.end_time = self.end_time.or(other.end_time.clone());
+        self.weekdays = self.weekdays.or(other.weekdays.clone());
+        self.logon_time = self.logon_time.or(other.logon_time.clone());
+        self.logout_time = self.logout_time.or(other.logout_time.clone());
+        self.logon_day = self.logon_day.or(other.logon_day.clone());
+        self.logout_day = self.logout_day.or(other.logout_day.clone());
+        self.reset_time = self.reset_time.or(other.reset_time.clone());
+        self.reset_day = self.reset_day.or(other.reset_day.clone());
+        self.holidays = self.holidays.or(other.holidays.clone());
         self.milliseconds_in_time_stamp = self
             .milliseconds_in_time_stamp
             .or(other.milliseconds_in_time_stamp.clone());
@@ -302,6 +418,9 @@ impl DynamicSessionSettingBuilder {
         self.enable_last_msg_seq_num_processed = self
             .enable_last_msg_seq_num_processed
             .or(other.enable_last_msg_seq_num_processed.clone());
+        self.enable_next_expected_msg_seq_num = self
+            .enable_next_expected_msg_seq_num
+            .or(other.enable_next_expected_msg_seq_num.clone());
         self.max_messages_in_resend_request = self
             .max_messages_in_resend_request
             .or(other.max_messages_in_resend_request.clone());
@@ -326,6 +445,17 @@ impl DynamicSessionSettingBuilder {
         self.app_data_dictionary = self
             .app_data_dictionary
             .or(other.app_data_dictionary.clone());
+        if self.data_dictionary_overlays.is_empty() {
+            self.data_dictionary_overlays = other.data_dictionary_overlays.clone();
+        }
+        if self.transport_data_dictionary_overlays.is_empty() {
+            self.transport_data_dictionary_overlays =
+                other.transport_data_dictionary_overlays.clone();
+        }
+        if self.app_data_dictionary_overlays.is_empty() {
+            self.app_data_dictionary_overlays = other.app_data_dictionary_overlays.clone();
+        }
+        self.validation_rules = self.validation_rules.or(other.validation_rules.clone());
         self.validate_fields_out_of_order = self
             .validate_fields_out_of_order
             .or(other.validate_fields_out_of_order.clone());
@@ -344,6 +474,26 @@ impl DynamicSessionSettingBuilder {
         self.check_latency = self.check_latency.or(other.check_latency.clone());
         self.max_latency = self.max_latency.or(other.max_latency.clone());
 
+        // logon options
+        self.username = self.username.or(other.username.clone());
+        self.password = self.password.or(other.password.clone());
+        self.raw_data = self.raw_data.or(other.raw_data.clone());
+
+        // throttle options
+        self.throttle_rate = self.throttle_rate.or(other.throttle_rate.clone());
+        self.throttle_burst = self.throttle_burst.or(other.throttle_burst.clone());
+        self.throttle_msg_type_rates = self
+            .throttle_msg_type_rates
+            .or(other.throttle_msg_type_rates.clone());
+        self.throttle_action = self.throttle_action.or(other.throttle_action.clone());
+        // outbound queue options
+        self.outbound_queue_capacity = self
+            .outbound_queue_capacity
+            .or(other.outbound_queue_capacity.clone());
+        self.persist_outbound_queue = self
+            .persist_outbound_queue
+            .or(other.persist_outbound_queue.clone());
+
         // initiator options
         self.reconnect_interval = self.reconnect_interval.or(other.reconnect_interval.clone());
         self.heart_bt_int = self.heart_bt_int.or(other.heart_bt_int.clone());
@@ -392,6 +542,15 @@ impl DynamicSessionSettingBuilder {
             .socket_receive_timeout
             .or(other.socket_receive_timeout.clone());
 
+        // Proxy options
+        self.socket_proxy_type = self.socket_proxy_type.or(other.socket_proxy_type.clone());
+        self.socket_proxy_host = self.socket_proxy_host.or(other.socket_proxy_host.clone());
+        self.socket_proxy_port = self.socket_proxy_port.or(other.socket_proxy_port.clone());
+        self.socket_proxy_user = self.socket_proxy_user.or(other.socket_proxy_user.clone());
+        self.socket_proxy_password = self
+            .socket_proxy_password
+            .or(other.socket_proxy_password.clone());
+
         // SSL options
 
         self.ssl_server_name = self.ssl_server_name.or(other.ssl_server_name.clone());
@@ -415,6 +574,12 @@ impl DynamicSessionSettingBuilder {
             .ssl_require_client_certificate
             .or(other.ssl_require_client_certificate.clone());
         self.ssl_ca_certificate = self.ssl_ca_certificate.or(other.ssl_ca_certificate.clone());
+        self.ssl_client_certificate_identity = self
+            .ssl_client_certificate_identity
+            .or(other.ssl_client_certificate_identity.clone());
+        self.ssl_reload_certificates = self
+            .ssl_reload_certificates
+            .or(other.ssl_reload_certificates.clone());
         self
     }
 
@@ -443,15 +608,243 @@ impl DynamicSessionSettingBuilder {
             errors.push("TargetCompID must be set.".into());
         }
 
+        if let Some(proxy_type) = self.socket_proxy_type.as_deref() {
+            if ProxyType::try_from(proxy_type).is_err() {
+                errors.push("SocketProxyType must be set to either 'http' or 'socks5'.".into());
+            }
+            if self.socket_proxy_host.is_none() {
+                errors.push("SocketProxyHost must be set when SocketProxyType is set.".into());
+            }
+            if self
+                .socket_proxy_port
+                .as_ref()
+                .and_then(|v| v.parse::<u16>().ok())
+                .is_none()
+            {
+                errors.push("SocketProxyPort must be set to a valid port number.".into());
+            }
+            if self.socket_proxy_user.is_some() != self.socket_proxy_password.is_some() {
+                errors.push("SocketProxyUser and SocketProxyPassword must be set together.".into());
+            }
+        }
+
+        for (setting, value) in [
+            (SettingOption::StartTime, &self.start_time),
+            (SettingOption::EndTime, &self.end_time),
+            (SettingOption::LogonTime, &self.logon_time),
+            (SettingOption::LogoutTime, &self.logout_time),
+            (SettingOption::ResetTime, &self.reset_time),
+        ] {
+            if value.as_deref().is_some_and(|v| parse_time(v).is_none()) {
+                let setting: &str = setting.into();
+                errors.push(format!("{setting} must be formatted as HH:MM:SS."));
+            }
+        }
+        for (setting, value) in [
+            (SettingOption::StartDay, &self.start_day),
+            (SettingOption::EndDay, &self.end_day),
+            (SettingOption::LogonDay, &self.logon_day),
+            (SettingOption::LogoutDay, &self.logout_day),
+            (SettingOption::ResetDay, &self.reset_day),
+        ] {
+            if value
+                .as_deref()
+                .is_some_and(|v| v.parse::<Weekday>().is_err())
+            {
+                let setting: &str = setting.into();
+                errors.push(format!("{setting} must be a day of the week."));
+            }
+        }
+        if self
+            .weekdays
+            .as_deref()
+            .is_some_and(|v| parse_weekdays(v).is_none())
+        {
+            errors.push("Weekdays must be a comma separated list of days of the week.".into());
+        }
+        if self
+            .holidays
+            .as_deref()
+            .is_some_and(|v| parse_holidays(v).is_none())
+        {
+            errors.push("Holidays must be a comma separated list of YYYY-MM-DD dates.".into());
+        }
+        if self.logon_time.is_some() != self.logout_time.is_some() {
+            errors.push("LogonTime and LogoutTime must be set together.".into());
+        }
+        if self.logon_day.is_some() != self.logout_day.is_some() {
+            errors.push("LogonDay and LogoutDay must be set together.".into());
+        }
+        if self.logon_day.is_some() && self.logon_time.is_none() {
+            errors.push("LogonDay requires LogonTime and LogoutTime.".into());
+        }
+        if self.reset_day.is_some() && self.reset_time.is_none() {
+            errors.push("ResetDay requires ResetTime.".into());
+        }
+        if self.weekdays.is_some() && (self.start_day.is_some() || self.end_day.is_some()) {
+            errors.push("Weekdays cannot be combined with StartDay and EndDay.".into());
+        }
+        if self.start_time.is_some() != self.end_time.is_some() {
+            errors.push("StartTime and EndTime must be set together.".into());
+        }
+        if self.start_day.is_some() != self.end_day.is_some() {
+            errors.push("StartDay and EndDay must be set together.".into());
+        }
+        if self.start_day.is_some() && self.start_time.is_none() {
+            errors.push("StartDay requires StartTime and EndTime.".into());
+        }
+        if self.weekdays.is_some() && self.start_time.is_none() {
+            errors.push("Weekdays requires StartTime and EndTime.".into());
+        }
+
+        if self
+            .enable_next_expected_msg_seq_num
+            .as_deref()
+            .is_some_and(|v| v == "Y")
+            && self
+                .begin_string
+                .as_deref()
+                .is_some_and(|v| v != BeginString::FIXT11 && v < BeginString::FIX44)
+        {
+            errors.push("EnableNextExpectedMsgSeqNum requires FIX.4.4 or later.".into());
+        }
+
+        for (setting, value) in [
+            (SettingOption::ThrottleRate, &self.throttle_rate),
+            (SettingOption::ThrottleBurst, &self.throttle_burst),
+        ] {
+            if value
+                .as_deref()
+                .is_some_and(|v| !v.parse::<u32>().is_ok_and(|v| v > 0))
+            {
+                let setting: &str = setting.into();
+                errors.push(format!("{setting} must be a positive number."));
+            }
+        }
+        if self
+            .throttle_msg_type_rates
+            .as_deref()
+            .is_some_and(|v| parse_msg_type_rates(v).is_none())
+        {
+            errors.push(
+                "ThrottleMsgTypeRates must be a comma separated list of MsgType:rate.".into(),
+            );
+        }
+        if self
+            .use_data_dictionary
+            .as_deref()
+            .is_some_and(|v| !matches!(v, "Y" | "N" | "Report"))
+        {
+            errors.push("UseDataDictionary must be set to 'Y', 'N' or 'Report'.".into());
+        }
+        if self
+            .throttle_action
+            .as_deref()
+            .is_some_and(|v| ThrottleAction::try_from(v).is_err())
+        {
+            errors.push("ThrottleAction must be set to 'Queue', 'Reject' or 'Block'.".into());
+        }
+        if self
+            .message_encoding
+            .as_deref()
+            .is_some_and(|v| MessageEncoding::for_label(v).is_none())
+        {
+            errors.push(
+                "MessageEncoding must be a supported character set, e.g. 'UTF-8' or 'Shift_JIS'."
+                    .into(),
+            );
+        }
+        if self
+            .outbound_queue_capacity
+            .as_deref()
+            .is_some_and(|v| !v.parse::<usize>().is_ok_and(|v| v > 0))
+        {
+            errors.push("OutboundQueueCapacity must be a positive number.".into());
+        }
+
+        let data_dictionaries = self.load_validation_files(&mut errors);
+
         if !errors.is_empty() {
             Err(SessionSettingsError::ValidationErrors(errors))
         } else {
-            Ok(Validated(self))
+            Ok(Validated(self, data_dictionaries))
+        }
+    }
+
+    /// Loads the dictionaries and rules the session validates with, a file that is missing or
+    /// does not parse is added to `errors`. The dictionary path defaults to the BeginString like
+    /// QuickFIX/n.
+    fn load_validation_files(&self, errors: &mut Vec<String>) -> DataDictionaries {
+        let error_count = errors.len();
+        let (session, application) = self.load_data_dictionaries(errors);
+        let validation_rules = match self.validation_rules.as_deref() {
+            // rules name fields from a dictionary that failed to load, don't report them twice
+            Some(_) if errors.len() > error_count => ValidationRules::new(),
+            Some(path) => {
+                let dd = application.as_ref().or(session.as_ref());
+                ValidationRules::from_file(path, dd.unwrap_or(&DataDictionary::default()))
+                    .unwrap_or_else(|e| {
+                        errors.push(format!(
+                            "ValidationRules could not be loaded from {path}: {e}"
+                        ));
+                        ValidationRules::new()
+                    })
+            }
+            None => ValidationRules::new(),
+        };
+        DataDictionaries::new(session, application, validation_rules)
+    }
+
+    fn load_data_dictionaries(
+        &self,
+        errors: &mut Vec<String>,
+    ) -> (Option<DataDictionary>, Option<DataDictionary>) {
+        if !self
+            .use_data_dictionary
+            .as_deref()
+            .is_some_and(|v| v == "Y" || v == "Report")
+        {
+            return (None, None);
+        }
+        let Some(begin_string) = self.begin_string.as_deref() else {
+            return (None, None);
+        };
+        if begin_string.starts_with("FIXT") {
+            // https://github.com/connamara/quickfixn/blob/c4e8171e9a702be29078eab3b6dc26b713002de2/QuickFIXn/SessionFactory.cs#L193
+            if self.default_appl_ver_id.is_none() {
+                return (None, None);
+            }
+            let application = load_data_dictionary(
+                "AppDataDictionary",
+                self.app_data_dictionary.as_deref().unwrap_or(begin_string),
+                &self.app_data_dictionary_overlays,
+                errors,
+            );
+            let session = load_data_dictionary(
+                "TransportDataDictionary",
+                self.transport_data_dictionary
+                    .as_deref()
+                    .unwrap_or(begin_string),
+                &self.transport_data_dictionary_overlays,
+                errors,
+            );
+            (session, application)
+        } else {
+            let session = load_data_dictionary(
+                "DataDictionary",
+                self.data_dictionary.as_deref().unwrap_or(begin_string),
+                &self.data_dictionary_overlays,
+                errors,
+            );
+            (session, None)
         }
     }
 
     // TODO check if these are the correct default values
-    fn build(self) -> Result<SessionSetting, SessionSettingsError> {
+    fn build(
+        self,
+        data_dictionaries: DataDictionaries,
+    ) -> Result<SessionSetting, SessionSettingsError> {
         let mut builder = SessionSetting::builder();
 
         let session_id = SessionId::new(
@@ -465,6 +858,39 @@ impl DynamicSessionSettingBuilder {
         );
         builder.session_id(session_id);
 
+        let proxy_options = match (
+            self.socket_proxy_type.as_deref(),
+            self.connection_type.as_deref(),
+        ) {
+            (Some(proxy_type), Some("initiator")) => Some(
+                ProxyOptions::builder()
+                    .proxy_type(proxy_type.try_into()?)
+                    .host(self.socket_proxy_host.clone().unwrap_or_default())
+                    .port(
+                        self.socket_proxy_port
+                            .as_ref()
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or_default(),
+                    )
+                    .credentials(
+                        self.socket_proxy_user
+                            .clone()
+                            .zip(self.socket_proxy_password.clone()),
+                    )
+                    .target_host(self.socket_connect_host.clone().unwrap_or_default())
+                    .target_port(
+                        self.socket_connect_port
+                            .as_ref()
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or_default(),
+                    )
+                    .build()
+                    .unwrap(),
+            ),
+            _ => None,
+        };
+        builder.proxy_options(proxy_options);
+
         let connection = match self.connection_type.unwrap().as_str() {
             "acceptor" => SettingsConnection::Acceptor {
                 is_dynamic: self.is_dynamic.is_some_and(|v| v == "Y"),
@@ -559,6 +985,11 @@ impl DynamicSessionSettingBuilder {
         };
         builder.persistence(persistence);
         builder.default_appl_ver_id(self.default_appl_ver_id);
+        builder.message_encoding(
+            self.message_encoding
+                .as_deref()
+                .and_then(MessageEncoding::for_label),
+        );
 
         let any_set = self
             .start_day
@@ -567,51 +998,51 @@ impl DynamicSessionSettingBuilder {
             .or(self.end_time.as_ref())
             .or(self.start_time.as_ref())
             .is_some();
-        let schedule = if self.non_stop_session.map_or(true, |v| v == "Y") && !any_set {
-            SessionSchedule::NonStop
+        let period = if self.non_stop_session.map_or(true, |v| v == "Y") && !any_set {
+            SessionPeriod::NonStop
         // } else if self.even_minutes_session.map(|v| v == "Y").unwrap_or(true) && !any_set {
         //     #[cfg(test)]
-        //     SessionSchedule::EvenMinutes
+        //     SessionPeriod::EvenMinutes
         } else {
-            match (
-                self.start_day,
-                self.end_day,
-                self.start_time,
-                self.end_time,
-                self.time_zone,
-            ) {
-                (Some(start_day), Some(end_day), Some(start_time), Some(end_time), timezone) => {
-                    let start_day = start_day.parse().unwrap();
-                    let end_day = end_day.parse().unwrap();
-                    let start_time = NaiveTime::parse_from_str(&start_time, "%H:%M:%S").unwrap();
-                    let end_time = NaiveTime::parse_from_str(&end_time, "%H:%M:%S").unwrap();
-                    let use_localtime: bool = self.use_local_time.is_some_and(|v| v == "Y");
-                    let timezone = timezone.and_then(|tz| tz.parse().ok());
-                    SessionSchedule::Weekly {
-                        start_day,
-                        end_day,
-                        start_time,
-                        end_time,
-                        timezone,
-                        use_localtime,
-                    }
-                }
-                (None, None, Some(start_time), Some(end_time), timezone) => {
-                    let start_time = NaiveTime::parse_from_str(&start_time, "%H:%M:%S").unwrap();
-                    let end_time = NaiveTime::parse_from_str(&end_time, "%H:%M:%S").unwrap();
-                    let use_localtime: bool = self.use_local_time.is_some_and(|v| v == "Y");
-                    let timezone = timezone.and_then(|tz| tz.parse().ok());
-                    SessionSchedule::Daily {
-                        start_time,
-                        end_time,
-                        timezone,
-                        use_localtime,
-                    }
-                }
-                (None, None, None, None, None) => SessionSchedule::NonStop,
-                _ => unreachable!(),
-            }
+            session_period(
+                self.start_day.as_deref(),
+                self.end_day.as_deref(),
+                self.start_time.as_deref(),
+                self.end_time.as_deref(),
+                self.weekdays.as_deref(),
+            )
         };
+        let logon = self.logon_time.is_some().then(|| {
+            session_period(
+                self.logon_day.as_deref(),
+                self.logout_day.as_deref(),
+                self.logon_time.as_deref(),
+                self.logout_time.as_deref(),
+                self.weekdays.as_deref(),
+            )
+        });
+        let reset = self
+            .reset_time
+            .as_deref()
+            .and_then(parse_time)
+            .map(|time| ResetTime {
+                day: self.reset_day.as_deref().and_then(|v| v.parse().ok()),
+                time,
+            });
+        let schedule = SessionSchedule::builder()
+            .period(period)
+            .logon(logon)
+            .reset(reset)
+            .holidays(
+                self.holidays
+                    .as_deref()
+                    .and_then(parse_holidays)
+                    .unwrap_or_default(),
+            )
+            .timezone(self.time_zone.and_then(|tz| tz.parse().ok()))
+            .use_localtime(self.use_local_time.is_some_and(|v| v == "Y"))
+            .build()
+            .unwrap();
         builder.schedule(schedule);
 
         let validation_options = ValidationOptions::builder()
@@ -636,6 +1067,10 @@ impl DynamicSessionSettingBuilder {
                 self.enable_last_msg_seq_num_processed
                     .is_some_and(|v| v == "Y"),
             )
+            .enable_next_expected_msg_seq_num(
+                self.enable_next_expected_msg_seq_num
+                    .is_some_and(|v| v == "Y"),
+            )
             .max_messages_in_resend_request(
                 self.max_messages_in_resend_request
                     .and_then(|v| v.parse().ok())
@@ -650,10 +1085,23 @@ impl DynamicSessionSettingBuilder {
                     .is_some_and(|v| v == "Y"),
             )
             .requires_orig_sending_time(self.requires_orig_sending_time.map_or(true, |v| v == "Y"))
-            .use_data_dictionary(self.use_data_dictionary.is_some_and(|v| v == "Y"))
+            .use_data_dictionary(
+                self.use_data_dictionary
+                    .as_deref()
+                    .is_some_and(|v| v == "Y" || v == "Report"),
+            )
+            .validation_report(self.use_data_dictionary.as_deref() == Some("Report"))
             .data_dictionary(self.data_dictionary)
             .transport_data_dictionary(self.transport_data_dictionary)
             .app_data_dictionary(self.app_data_dictionary)
+            .data_dictionary_overlays(self.data_dictionary_overlays.into_values().collect())
+            .transport_data_dictionary_overlays(
+                self.transport_data_dictionary_overlays
+                    .into_values()
+                    .collect(),
+            )
+            .app_data_dictionary_overlays(self.app_data_dictionary_overlays.into_values().collect())
+            .validation_rules(self.validation_rules)
             .validate_fields_out_of_order(
                 self.validate_fields_out_of_order.map_or(true, |v| v == "Y"),
             )
@@ -672,69 +1120,93 @@ impl DynamicSessionSettingBuilder {
             .build()
             .unwrap();
         builder.validation_options(validation_options);
+        builder.data_dictionaries(data_dictionaries);
 
+        let logon_options = LogonOptions::builder()
+            .username(self.username)
+            .password(self.password)
+            .raw_data(self.raw_data)
+            .build()
+            .unwrap();
+        builder.logon_options(logon_options);
+
+        let throttle_options = ThrottleOptions::builder()
+            .rate(self.throttle_rate.and_then(|v| v.parse().ok()))
+            .burst(self.throttle_burst.and_then(|v| v.parse().ok()))
+            .msg_type_rates(
+                self.throttle_msg_type_rates
+                    .as_deref()
+                    .and_then(parse_msg_type_rates)
+                    .unwrap_or_default(),
+            )
+            .action(
+                self.throttle_action
+                    .as_deref()
+                    .and_then(|v| v.try_into().ok())
+                    .unwrap_or(ThrottleAction::Queue),
+            )
+            .build()
+            .unwrap();
+        builder.throttle_options(throttle_options);
+
+        let outbound_queue_options = OutboundQueueOptions::builder()
+            .capacity(
+                self.outbound_queue_capacity
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10000),
+            )
+            .persist(self.persist_outbound_queue.is_some_and(|v| v == "Y"))
+            .build()
+            .unwrap();
+        builder.outbound_queue_options(outbound_queue_options);
+
+        let tls_config = TlsConfig {
+            certificate: self.ssl_certificate.as_ref().map(Into::into),
+            certificate_password: self.ssl_certificate_password.clone(),
+            ca_certificate: self.ssl_ca_certificate.as_ref().map(Into::into),
+            min_protocol: self.ssl_min_protocol.clone(),
+            max_protocol: self.ssl_max_protocol.clone(),
+            use_sni: self.ssl_use_sni.as_ref().map_or(true, |v| v == "Y"),
+            accept_invalid_certs: self
+                .ssl_accept_invalid_certs
+                .as_ref()
+                .is_some_and(|v| v == "Y"),
+            accept_invalid_hostnames: self
+                .ssl_accept_invalid_hostnames
+                .as_ref()
+                .is_some_and(|v| v == "Y"),
+            disable_built_in_roots: self
+                .ssl_disable_built_in_roots
+                .as_ref()
+                .is_some_and(|v| v == "Y"),
+            require_client_certificate: self
+                .ssl_require_client_certificate
+                .as_ref()
+                .is_some_and(|v| v == "Y"),
+            reload_on_change: self
+                .ssl_reload_certificates
+                .as_ref()
+                .is_some_and(|v| v == "Y"),
+        };
         let ssl_options = match (self.ssl_enable.as_deref(), is_initiator) {
             (Some(_x @ "Y"), true) => {
-                let mut builder = TlsConnector::builder();
-                if let Some(certificate_file) = self.ssl_certificate {
-                    let mut file = File::open(certificate_file).unwrap();
-                    let mut buf = vec![];
-                    file.read_to_end(&mut buf).unwrap();
-                    let certificate = Certificate::from_pem(&buf).unwrap();
-                    let identity = Identity::from_pkcs12(
-                        &certificate.to_der().unwrap(),
-                        self.ssl_certificate_password.as_ref().unwrap(),
-                    )
-                    .unwrap();
-                    builder.identity(identity);
-                }
-                builder
-                    .min_protocol_version(self.ssl_min_protocol.as_ref().and_then(|r| protocol(r)));
-                builder
-                    .max_protocol_version(self.ssl_max_protocol.as_ref().and_then(|r| protocol(r)));
-                builder.use_sni(self.ssl_use_sni.as_ref().map_or(true, |v| v == "Y"));
-                builder.danger_accept_invalid_certs(
-                    self.ssl_accept_invalid_certs
-                        .as_ref()
-                        .is_some_and(|v| v == "Y"),
-                );
-                builder.danger_accept_invalid_hostnames(
-                    self.ssl_accept_invalid_hostnames
-                        .as_ref()
-                        .is_some_and(|v| v == "Y"),
-                );
-                builder.disable_built_in_roots(
-                    self.ssl_disable_built_in_roots
-                        .as_ref()
-                        .is_some_and(|v| v == "Y"),
-                );
-
-                // root_certificates: vec![],
-                // #[cfg(feature = "alpn")]
-                // alpn: vec![],
-
                 let host = self.socket_connect_host.clone();
                 let domain = self.ssl_server_name.clone().or(host).unwrap_or_default();
-                let initiator = builder.build().unwrap();
+                let initiator = TlsConnector::new(tls_config)?;
                 Some(SslOptions::Initiator { initiator, domain })
             }
             (Some(_x @ "Y"), false) => {
-                let mut file = File::open(self.ssl_certificate.unwrap()).unwrap();
-                let mut buf = vec![];
-                file.read_to_end(&mut buf).unwrap();
-                let certificate = Certificate::from_pem(&buf).unwrap();
-                let identity = Identity::from_pkcs12(
-                    &certificate.to_der().unwrap(),
-                    self.ssl_certificate_password.as_ref().unwrap(),
-                )
-                .unwrap();
-                let mut builder = TlsAcceptor::builder(identity);
-                builder
-                    .min_protocol_version(self.ssl_min_protocol.as_ref().and_then(|r| protocol(r)));
-                builder
-                    .max_protocol_version(self.ssl_max_protocol.as_ref().and_then(|r| protocol(r)));
-                let acceptor = builder.build().unwrap();
-                Some(SslOptions::Acceptor { acceptor })
+                let client_identity = tls_config.require_client_certificate.then(|| {
+                    match self.ssl_client_certificate_identity.clone() {
+                        Some(name) => ClientIdentity::Name(name),
+                        None => ClientIdentity::TargetCompID,
+                    }
+                });
+                let acceptor = TlsAcceptor::new(tls_config)?;
+                Some(SslOptions::Acceptor {
+                    acceptor,
+                    client_identity,
+                })
             }
             _ => None,
         };
@@ -743,12 +1215,72 @@ impl DynamicSessionSettingBuilder {
     }
 }
 
-fn protocol(protocol: &str) -> Option<Protocol> {
-    match protocol {
-        "TLSv1.0" => Some(Protocol::Tlsv10),
-        "TLSv1.1" => Some(Protocol::Tlsv11),
-        "TLSv1.2" => Some(Protocol::Tlsv12),
-        "SSLv3" => Some(Protocol::Sslv3),
-        _ => None,
+fn load_data_dictionary(
+    setting: &str,
+    path: &str,
+    overlays: &BTreeMap<u32, String>,
+    errors: &mut Vec<String>,
+) -> Option<DataDictionary> {
+    let overlays: Vec<&String> = overlays.values().collect();
+    DataDictionary::from_file_with_overlays(path, &overlays)
+        .map_err(|e| errors.push(format!("{setting} could not be loaded: {e}")))
+        .ok()
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M:%S").ok()
+}
+
+fn parse_weekdays(value: &str) -> Option<Vec<Weekday>> {
+    value
+        .split(',')
+        .map(|day| day.trim().parse().ok())
+        .collect()
+}
+
+/// Parses `D:5,F:2` into the rate per second of each MsgType.
+fn parse_msg_type_rates(value: &str) -> Option<Vec<(String, u32)>> {
+    value
+        .split(',')
+        .map(|rate| {
+            let (msg_type, rate) = rate.trim().split_once(':')?;
+            let rate = rate.parse().ok().filter(|rate| *rate > 0)?;
+            Some((msg_type.into(), rate))
+        })
+        .collect()
+}
+
+fn parse_holidays(value: &str) -> Option<Vec<NaiveDate>> {
+    value
+        .split(',')
+        .map(|date| NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok())
+        .collect()
+}
+
+/// Builds a weekly or daily period from already validated settings.
+fn session_period(
+    start_day: Option<&str>,
+    end_day: Option<&str>,
+    start_time: Option<&str>,
+    end_time: Option<&str>,
+    weekdays: Option<&str>,
+) -> SessionPeriod {
+    let start_time = start_time.and_then(parse_time);
+    let end_time = end_time.and_then(parse_time);
+    match (start_day, end_day, start_time, end_time) {
+        (Some(start_day), Some(end_day), Some(start_time), Some(end_time)) => {
+            SessionPeriod::Weekly {
+                start_day: start_day.parse().unwrap(),
+                end_day: end_day.parse().unwrap(),
+                start_time,
+                end_time,
+            }
+        }
+        (None, None, Some(start_time), Some(end_time)) => SessionPeriod::Daily {
+            start_time,
+            end_time,
+            weekdays: weekdays.and_then(parse_weekdays).unwrap_or_default(),
+        },
+        _ => SessionPeriod::NonStop,
     }
 }
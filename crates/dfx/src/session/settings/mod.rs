@@ -7,6 +7,8 @@ pub(crate) use setting::*;
 
 use dfx_base::session_id::SessionId;
 
+use crate::session::SessionSchedule;
+
 #[derive(Debug)]
 pub(crate) enum SettingOption {
     IsDynamic,
@@ -19,6 +21,7 @@ pub(crate) enum SettingOption {
     TargetLocationID,
     SessionQualifier,
     DefaultApplVerID,
+    MessageEncoding,
     ConnectionType,
     UseDataDictionary,
     NonStopSession,
@@ -28,6 +31,14 @@ pub(crate) enum SettingOption {
     EndDay,
     StartTime,
     EndTime,
+    Weekdays,
+    LogonTime,
+    LogoutTime,
+    LogonDay,
+    LogoutDay,
+    ResetTime,
+    ResetDay,
+    Holidays,
     HeartBtInt,
     SocketAcceptHost,
     SocketAcceptPort,
@@ -49,6 +60,7 @@ pub(crate) enum SettingOption {
     DataDictionary,
     TransportDataDictionary,
     AppDataDictionary,
+    ValidationRules,
     PersistMessages,
     LogonTimeout,
     LogoutTimeout,
@@ -57,6 +69,7 @@ pub(crate) enum SettingOption {
     MillisecondsInTimeStamp,
     TimeStampPrecision,
     EnableLastMsgSeqNumProcessed,
+    EnableNextExpectedMsgSeqNum,
     MaxMessagesInResendRequest,
     SendLogoutBeforeDisconnectFromTimeout,
     SocketNodelay,
@@ -64,10 +77,24 @@ pub(crate) enum SettingOption {
     SocketReceiveBufferSize,
     SocketSendTimeout,
     SocketReceiveTimeout,
+    SocketProxyType,
+    SocketProxyHost,
+    SocketProxyPort,
+    SocketProxyUser,
+    SocketProxyPassword,
     IgnorePossDupResendRequests,
     RequiresOrigSendingTime,
     CheckLatency,
     MaxLatency,
+    Username,
+    Password,
+    RawData,
+    ThrottleRate,
+    ThrottleBurst,
+    ThrottleMsgTypeRates,
+    ThrottleAction,
+    OutboundQueueCapacity,
+    PersistOutboundQueue,
     // TODO add feature ssl
     SSLEnable,
     SSLServerName,
@@ -83,6 +110,8 @@ pub(crate) enum SettingOption {
     SSLCertificatePassword,
     SSLRequireClientCertificate,
     SSLCACertificate,
+    SSLClientCertificateIdentity,
+    SSLReloadCertificates,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -107,6 +136,7 @@ pub enum SessionSettingsError {
         reason: String,
         line_number: usize,
     },
+    TlsError(String),
 }
 
 impl TryFrom<&str> for SettingOption {
@@ -124,6 +154,7 @@ impl TryFrom<&str> for SettingOption {
             "TargetLocationID" => Ok(Self::TargetLocationID),
             "SessionQualifier" => Ok(Self::SessionQualifier),
             "DefaultApplVerID" => Ok(Self::DefaultApplVerID),
+            "MessageEncoding" => Ok(Self::MessageEncoding),
             "ConnectionType" => Ok(Self::ConnectionType),
             "UseDataDictionary" => Ok(Self::UseDataDictionary),
             "NonStopSession" => Ok(Self::NonStopSession),
@@ -133,6 +164,14 @@ impl TryFrom<&str> for SettingOption {
             "EndDay" => Ok(Self::EndDay),
             "StartTime" => Ok(Self::StartTime),
             "EndTime" => Ok(Self::EndTime),
+            "Weekdays" => Ok(Self::Weekdays),
+            "LogonTime" => Ok(Self::LogonTime),
+            "LogoutTime" => Ok(Self::LogoutTime),
+            "LogonDay" => Ok(Self::LogonDay),
+            "LogoutDay" => Ok(Self::LogoutDay),
+            "ResetTime" => Ok(Self::ResetTime),
+            "ResetDay" => Ok(Self::ResetDay),
+            "Holidays" => Ok(Self::Holidays),
             "HeartBtInt" => Ok(Self::HeartBtInt),
             "SocketAcceptHost" => Ok(Self::SocketAcceptHost),
             "SocketAcceptPort" => Ok(Self::SocketAcceptPort),
@@ -154,6 +193,7 @@ impl TryFrom<&str> for SettingOption {
             "DataDictionary" => Ok(Self::DataDictionary),
             "TransportDataDictionary" => Ok(Self::TransportDataDictionary),
             "AppDataDictionary" => Ok(Self::AppDataDictionary),
+            "ValidationRules" => Ok(Self::ValidationRules),
             "PersistMessages" => Ok(Self::PersistMessages),
             "LogonTimeout" => Ok(Self::LogonTimeout),
             "LogoutTimeout" => Ok(Self::LogoutTimeout),
@@ -162,6 +202,7 @@ impl TryFrom<&str> for SettingOption {
             "MillisecondsInTimeStamp" => Ok(Self::MillisecondsInTimeStamp),
             "TimeStampPrecision" => Ok(Self::TimeStampPrecision),
             "EnableLastMsgSeqNumProcessed" => Ok(Self::EnableLastMsgSeqNumProcessed),
+            "EnableNextExpectedMsgSeqNum" => Ok(Self::EnableNextExpectedMsgSeqNum),
             "MaxMessagesInResendRequest" => Ok(Self::MaxMessagesInResendRequest),
             "SendLogoutBeforeDisconnectFromTimeout" => {
                 Ok(Self::SendLogoutBeforeDisconnectFromTimeout)
@@ -171,10 +212,24 @@ impl TryFrom<&str> for SettingOption {
             "SocketReceiveBufferSize" => Ok(Self::SocketReceiveBufferSize),
             "SocketSendTimeout" => Ok(Self::SocketSendTimeout),
             "SocketReceiveTimeout" => Ok(Self::SocketReceiveTimeout),
+            "SocketProxyType" => Ok(Self::SocketProxyType),
+            "SocketProxyHost" => Ok(Self::SocketProxyHost),
+            "SocketProxyPort" => Ok(Self::SocketProxyPort),
+            "SocketProxyUser" => Ok(Self::SocketProxyUser),
+            "SocketProxyPassword" => Ok(Self::SocketProxyPassword),
             "IgnorePossDupResendRequests" => Ok(Self::IgnorePossDupResendRequests),
             "RequiresOrigSendingTime" => Ok(Self::RequiresOrigSendingTime),
             "CheckLatency" => Ok(Self::CheckLatency),
             "MaxLatency" => Ok(Self::MaxLatency),
+            "Username" => Ok(Self::Username),
+            "Password" => Ok(Self::Password),
+            "RawData" => Ok(Self::RawData),
+            "ThrottleRate" => Ok(Self::ThrottleRate),
+            "ThrottleBurst" => Ok(Self::ThrottleBurst),
+            "ThrottleMsgTypeRates" => Ok(Self::ThrottleMsgTypeRates),
+            "ThrottleAction" => Ok(Self::ThrottleAction),
+            "OutboundQueueCapacity" => Ok(Self::OutboundQueueCapacity),
+            "PersistOutboundQueue" => Ok(Self::PersistOutboundQueue),
             // TODO add feature ssl
             "SSLEnable" => Ok(Self::SSLEnable),
             "SSLServerName" => Ok(Self::SSLServerName),
@@ -190,6 +245,8 @@ impl TryFrom<&str> for SettingOption {
             "SSLCertificatePassword" => Ok(Self::SSLCertificatePassword),
             "SSLRequireClientCertificate" => Ok(Self::SSLRequireClientCertificate),
             "SSLCACertificate" => Ok(Self::SSLCACertificate),
+            "SSLClientCertificateIdentity" => Ok(Self::SSLClientCertificateIdentity),
+            "SSLReloadCertificates" => Ok(Self::SSLReloadCertificates),
             _ => Err(Self::Error::NoSuchSetting(value.into())),
         }
     }
@@ -215,6 +272,7 @@ impl From<SettingOption> for &'static str {
             SettingOption::TargetLocationID => "TargetLocationID",
             SettingOption::SessionQualifier => "SessionQualifier",
             SettingOption::DefaultApplVerID => "DefaultApplVerID",
+            SettingOption::MessageEncoding => "MessageEncoding",
             SettingOption::ConnectionType => "ConnectionType",
             SettingOption::UseDataDictionary => "UseDataDictionary",
             SettingOption::NonStopSession => "NonStopSession",
@@ -224,6 +282,14 @@ impl From<SettingOption> for &'static str {
             SettingOption::EndDay => "EndDay",
             SettingOption::StartTime => "StartTime",
             SettingOption::EndTime => "EndTime",
+            SettingOption::Weekdays => "Weekdays",
+            SettingOption::LogonTime => "LogonTime",
+            SettingOption::LogoutTime => "LogoutTime",
+            SettingOption::LogonDay => "LogonDay",
+            SettingOption::LogoutDay => "LogoutDay",
+            SettingOption::ResetTime => "ResetTime",
+            SettingOption::ResetDay => "ResetDay",
+            SettingOption::Holidays => "Holidays",
             SettingOption::HeartBtInt => "HeartBtInt",
             SettingOption::SocketAcceptHost => "SocketAcceptHost",
             SettingOption::SocketAcceptPort => "SocketAcceptPort",
@@ -245,6 +311,7 @@ impl From<SettingOption> for &'static str {
             SettingOption::DataDictionary => "DataDictionary",
             SettingOption::TransportDataDictionary => "TransportDataDictionary",
             SettingOption::AppDataDictionary => "AppDataDictionary",
+            SettingOption::ValidationRules => "ValidationRules",
             SettingOption::PersistMessages => "PersistMessages",
             SettingOption::LogonTimeout => "LogonTimeout",
             SettingOption::LogoutTimeout => "LogoutTimeout",
@@ -253,6 +320,7 @@ impl From<SettingOption> for &'static str {
             SettingOption::MillisecondsInTimeStamp => "MillisecondsInTimeStamp",
             SettingOption::TimeStampPrecision => "TimeStampPrecision",
             SettingOption::EnableLastMsgSeqNumProcessed => "EnableLastMsgSeqNumProcessed",
+            SettingOption::EnableNextExpectedMsgSeqNum => "EnableNextExpectedMsgSeqNum",
             SettingOption::MaxMessagesInResendRequest => "MaxMessagesInResendRequest",
             SettingOption::SendLogoutBeforeDisconnectFromTimeout => {
                 "SendLogoutBeforeDisconnectFromTimeout"
@@ -262,10 +330,24 @@ impl From<SettingOption> for &'static str {
             SettingOption::SocketReceiveBufferSize => "SocketReceiveBufferSize",
             SettingOption::SocketSendTimeout => "SocketSendTimeout",
             SettingOption::SocketReceiveTimeout => "SocketReceiveTimeout",
+            SettingOption::SocketProxyType => "SocketProxyType",
+            SettingOption::SocketProxyHost => "SocketProxyHost",
+            SettingOption::SocketProxyPort => "SocketProxyPort",
+            SettingOption::SocketProxyUser => "SocketProxyUser",
+            SettingOption::SocketProxyPassword => "SocketProxyPassword",
             SettingOption::IgnorePossDupResendRequests => "IgnorePossDupResendRequests",
             SettingOption::RequiresOrigSendingTime => "RequiresOrigSendingTime",
             SettingOption::CheckLatency => "CheckLatency",
             SettingOption::MaxLatency => "MaxLatency",
+            SettingOption::Username => "Username",
+            SettingOption::Password => "Password",
+            SettingOption::RawData => "RawData",
+            SettingOption::ThrottleRate => "ThrottleRate",
+            SettingOption::ThrottleBurst => "ThrottleBurst",
+            SettingOption::ThrottleMsgTypeRates => "ThrottleMsgTypeRates",
+            SettingOption::ThrottleAction => "ThrottleAction",
+            SettingOption::OutboundQueueCapacity => "OutboundQueueCapacity",
+            SettingOption::PersistOutboundQueue => "PersistOutboundQueue",
             // TODO add feature ssl
             SettingOption::SSLEnable => "SSLEnable",
             SettingOption::SSLServerName => "SSLServerName",
@@ -281,6 +363,8 @@ impl From<SettingOption> for &'static str {
             SettingOption::SSLCertificatePassword => "SSLCertificatePassword",
             SettingOption::SSLRequireClientCertificate => "SSLRequireClientCertificate",
             SettingOption::SSLCACertificate => "SSLCACertificate",
+            SettingOption::SSLClientCertificateIdentity => "SSLClientCertificateIdentity",
+            SettingOption::SSLReloadCertificates => "SSLReloadCertificates",
         }
     }
 }
@@ -393,6 +477,12 @@ impl SessionSettings {
         best_match
     }
 
+    /// The schedule of the session best matching `session_id`.
+    pub fn schedule(&self, session_id: &SessionId) -> Option<&SessionSchedule> {
+        self.for_session_id(session_id)
+            .map(|setting| setting.schedule())
+    }
+
     pub(crate) fn sessions(&self) -> &Vec<SessionSetting> {
         self.sessions.as_ref()
     }
@@ -410,7 +500,9 @@ impl SessionSettings {
 #[cfg(test)]
 mod tests {
     use crate::session::SessionSettingsError;
+    use dfx_base::message::Message;
     use dfx_base::session_id::SessionId;
+    use dfx_base::tags;
 
     use super::SessionSettings;
 
@@ -552,6 +644,499 @@ TargetCompID=*
         ));
     }
 
+    #[test]
+    fn settings_test_proxy() {
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+BeginString=TEST
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+TargetCompID=target1
+SocketProxyType=socks5
+SocketProxyHost=proxy.local
+SocketProxyPort=1080
+SocketProxyUser=user
+SocketProxyPassword=secret
+";
+        let settings = SessionSettings::from_string(data).unwrap();
+        let proxy = settings.sessions[0].proxy_options().unwrap();
+        assert_eq!(proxy.proxy_type(), &super::ProxyType::Socks5);
+        assert_eq!(proxy.host(), "proxy.local");
+        assert_eq!(proxy.port(), 1080);
+        assert_eq!(proxy.credentials(), Some(("user", "secret")));
+        assert_eq!(proxy.target_host(), "127.0.0.1");
+        assert_eq!(proxy.target_port(), 5000);
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+BeginString=TEST
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+TargetCompID=target1
+SocketProxyType=ftp
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors)) if errors.len() == 3
+        ));
+    }
+
+    #[test]
+    fn settings_test_schedule() {
+        use crate::session::{ResetTime, SessionPeriod, SessionSchedule};
+        use chrono::{NaiveDate, NaiveTime, Weekday};
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+BeginString=TEST
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+TargetCompID=target1
+StartTime=06:00:00
+EndTime=22:00:00
+Weekdays=Mon,Wed,Fri
+LogonTime=08:00:00
+LogoutTime=17:00:00
+ResetTime=05:00:00
+ResetDay=Mon
+Holidays=2023-12-25, 2024-01-01
+TimeZone=Europe/Amsterdam
+";
+        let settings = SessionSettings::from_string(data).unwrap();
+        let time = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+        let weekdays = vec![Weekday::Mon, Weekday::Wed, Weekday::Fri];
+        let expected = SessionSchedule::builder()
+            .period(SessionPeriod::Daily {
+                start_time: time(6),
+                end_time: time(22),
+                weekdays: weekdays.clone(),
+            })
+            .logon(Some(SessionPeriod::Daily {
+                start_time: time(8),
+                end_time: time(17),
+                weekdays,
+            }))
+            .reset(Some(ResetTime {
+                day: Some(Weekday::Mon),
+                time: time(5),
+            }))
+            .holidays(vec![
+                NaiveDate::from_ymd_opt(2023, 12, 25).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            ])
+            .timezone(Some(chrono_tz::Europe::Amsterdam))
+            .build()
+            .unwrap();
+        assert_eq!(settings.sessions[0].schedule(), &expected);
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+BeginString=TEST
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+TargetCompID=target1
+StartTime=6:00
+EndTime=22:00:00
+StartDay=Mon
+EndDay=Fri
+Weekdays=Mon
+LogonTime=08:00:00
+ResetDay=Someday
+Holidays=25-12-2023
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors)) if errors.len() == 6
+        ));
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+BeginString=TEST
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+TargetCompID=target1
+Weekdays=Mon,Tue
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors))
+                if errors == ["Weekdays requires StartTime and EndTime."]
+        ));
+    }
+
+    #[test]
+    fn settings_test_logon() {
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+Username=user
+Password=secret
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+RawData=token
+EnableNextExpectedMsgSeqNum=Y
+";
+        let settings = SessionSettings::from_string(data).unwrap();
+        let logon_options = settings.sessions[0].logon_options();
+        assert_eq!(logon_options.username(), Some(&"user".into()));
+        assert_eq!(logon_options.password(), Some(&"secret".into()));
+        assert_eq!(logon_options.raw_data(), Some(&"token".into()));
+        assert!(settings.sessions[0]
+            .validation_options()
+            .enable_next_expected_msg_seq_num());
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+BeginString=FIX.4.2
+TargetCompID=target1
+EnableNextExpectedMsgSeqNum=Y
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors)) if errors.len() == 1
+        ));
+    }
+
+    #[test]
+    fn settings_test_throttle() {
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+ThrottleRate=50
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+ThrottleMsgTypeRates=D:5,F:2
+ThrottleAction=Reject
+";
+        let settings = SessionSettings::from_string(data).unwrap();
+        let throttle_options = settings.sessions[0].throttle_options();
+        assert_eq!(throttle_options.rate(), Some(50));
+        assert_eq!(throttle_options.burst(), None);
+        assert_eq!(
+            throttle_options.msg_type_rates(),
+            &[("D".to_string(), 5), ("F".to_string(), 2)]
+        );
+        assert_eq!(
+            throttle_options.action(),
+            crate::session::ThrottleAction::Reject
+        );
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+ThrottleRate=0
+ThrottleBurst=many
+ThrottleMsgTypeRates=D5
+ThrottleAction=Wait
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors)) if errors.len() == 4
+        ));
+    }
+
+    #[test]
+    fn settings_test_outbound_queue() {
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target2
+OutboundQueueCapacity=100
+PersistOutboundQueue=Y
+";
+        let settings = SessionSettings::from_string(data).unwrap();
+        let outbound_queue_options = settings.sessions[0].outbound_queue_options();
+        assert_eq!(outbound_queue_options.capacity(), 10000);
+        assert!(!outbound_queue_options.persist());
+        let outbound_queue_options = settings.sessions[1].outbound_queue_options();
+        assert_eq!(outbound_queue_options.capacity(), 100);
+        assert!(outbound_queue_options.persist());
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+OutboundQueueCapacity=0
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors)) if errors.len() == 1
+        ));
+    }
+
+    #[test]
+    fn settings_test_message_encoding() {
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target2
+MessageEncoding=Shift_JIS
+";
+        let settings = SessionSettings::from_string(data).unwrap();
+        assert_eq!(settings.sessions[0].message_encoding(), None);
+        assert_eq!(
+            settings.sessions[1].message_encoding().map(|e| e.name()),
+            Some("Shift_JIS")
+        );
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+MessageEncoding=EBCDIC
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors)) if errors.len() == 1
+        ));
+    }
+
+    #[test]
+    fn settings_test_data_dictionary_overlays() {
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+DataDictionary=spec/FIX44.xml
+DataDictionary.2=venue/orders.xml
+DataDictionary.1=venue/fields.xml
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+[SESSION]
+BeginString=FIXT.1.1
+DefaultApplVerID=FIX.5.0SP2
+TargetCompID=target2
+AppDataDictionary=spec/FIX50SP2.xml
+AppDataDictionary.1=venue/fix50.xml
+";
+        let settings = SessionSettings::from_string(data).unwrap();
+        let options = settings.sessions[0].validation_options();
+        assert_eq!(
+            options.data_dictionary_overlays(),
+            ["venue/fields.xml", "venue/orders.xml"]
+        );
+        let options = settings.sessions[1].validation_options();
+        assert_eq!(options.app_data_dictionary_overlays(), ["venue/fix50.xml"]);
+        assert!(options.transport_data_dictionary_overlays().is_empty());
+
+        let data = r"# Comment
+[DEFAULT]
+ValidationRules.1=rules.txt
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::NoSuchSetting(_))
+        ));
+    }
+
+    #[test]
+    fn settings_test_data_dictionary() {
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+UseDataDictionary=Y
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+DataDictionary=../../spec/FIX44.xml
+[SESSION]
+BeginString=FIXT.1.1
+DefaultApplVerID=FIX.5.0SP2
+TargetCompID=target2
+TransportDataDictionary=../../spec/FIXT11.xml
+AppDataDictionary=../../spec/FIX50SP2.xml
+";
+        let settings = SessionSettings::from_string(data).unwrap();
+        let dictionaries = settings.sessions[0].data_dictionaries();
+        assert!(dictionaries.session().is_some());
+        assert!(dictionaries.application().is_none());
+        let dictionaries = settings.sessions[1].data_dictionaries();
+        assert!(dictionaries.session().is_some());
+        assert!(dictionaries.application().is_some());
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+UseDataDictionary=Y
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+DataDictionary=../../spec/FIX44.xml
+DataDictionary.1=../../spec/missing.xml
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors))
+                if errors.len() == 1 && errors[0].starts_with("DataDictionary could not be loaded")
+        ));
+    }
+
+    #[test]
+    fn settings_test_validation_rules() {
+        let path = std::env::temp_dir().join(format!("dfx-rules-{}.txt", std::process::id()));
+        let data = format!(
+            r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+UseDataDictionary=Y
+DataDictionary=../../spec/FIX44.xml
+ValidationRules={}
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+",
+            path.display()
+        );
+
+        std::fs::write(&path, "D: Price required when OrdType == 2\n").unwrap();
+        let settings = SessionSettings::from_string(&data).unwrap();
+        let mut order = Message::default();
+        order.set_tag_value(tags::OrdType, "2");
+        let rules = settings.sessions[0].data_dictionaries().validation_rules();
+        assert!(rules.validate(&order, "D").is_err());
+
+        std::fs::write(&path, "D: NoSuchField required\n").unwrap();
+        let settings = SessionSettings::from_string(&data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors))
+                if errors.len() == 1 && errors[0].starts_with("ValidationRules could not be loaded")
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+        let settings = SessionSettings::from_string(&data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors))
+                if errors.len() == 1 && errors[0].starts_with("ValidationRules could not be loaded")
+        ));
+    }
+
+    #[test]
+    fn settings_test_validation_report() {
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+DataDictionary=../../spec/FIX44.xml
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+UseDataDictionary=Report
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target2
+UseDataDictionary=Y
+";
+        let settings = SessionSettings::from_string(data).unwrap();
+        let options = settings.sessions[0].validation_options();
+        assert!(options.use_data_dictionary());
+        assert!(options.validation_report());
+        let options = settings.sessions[1].validation_options();
+        assert!(options.use_data_dictionary());
+        assert!(!options.validation_report());
+
+        let data = r"# Comment
+[DEFAULT]
+ConnectionType=initiator
+SenderCompID=sender
+SocketConnectHost=127.0.0.1
+SocketConnectPort=5000
+[SESSION]
+BeginString=FIX.4.4
+TargetCompID=target1
+UseDataDictionary=Log
+";
+        let settings = SessionSettings::from_string(data);
+        assert!(matches!(
+            settings,
+            Err(SessionSettingsError::ValidationErrors(errors)) if errors.len() == 1
+        ));
+    }
+
     #[test]
     fn settings_test_invalid_setting() {
         let data = r"# Comment
@@ -3,9 +3,12 @@ use std::{net::SocketAddr, path::PathBuf};
 
 use derive_builder::Builder;
 
-use crate::{connection::SocketSettings, session::SessionSchedule};
+use crate::connection::{SocketSettings, TlsAcceptor, TlsConnector};
+use crate::session::{SessionSchedule, ThrottleAction};
+use dfx_base::data_dictionary::DataDictionary;
+use dfx_base::encoding::MessageEncoding;
 use dfx_base::fields::converters::datetime::DateTimeFormat;
-use native_tls::{TlsAcceptor, TlsConnector};
+use dfx_base::validation_rules::ValidationRules;
 
 use dfx_base::session_id::SessionId;
 
@@ -133,10 +136,94 @@ impl SocketOptions {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ProxyType {
+    Http,
+    Socks5,
+}
+
+impl TryFrom<&str> for ProxyType {
+    type Error = SessionSettingsError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "http" => Ok(Self::Http),
+            "socks5" => Ok(Self::Socks5),
+            e => Err(SessionSettingsError::InvalidValue {
+                setting: SettingOption::SocketProxyType.into(),
+                value: e.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Builder, Clone, PartialEq, Eq)]
+pub(crate) struct ProxyOptions {
+    proxy_type: ProxyType,
+    host: String,
+    port: u16,
+    credentials: Option<(String, String)>,
+    target_host: String,
+    target_port: u16,
+}
+
+impl ProxyOptions {
+    pub(crate) fn builder() -> ProxyOptionsBuilder {
+        ProxyOptionsBuilder::create_empty()
+    }
+
+    pub(crate) fn proxy_type(&self) -> &ProxyType {
+        &self.proxy_type
+    }
+
+    pub(crate) fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub(crate) fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub(crate) fn credentials(&self) -> Option<(&str, &str)> {
+        self.credentials
+            .as_ref()
+            .map(|(user, password)| (user.as_str(), password.as_str()))
+    }
+
+    pub(crate) fn target_host(&self) -> &str {
+        &self.target_host
+    }
+
+    pub(crate) fn target_port(&self) -> u16 {
+        self.target_port
+    }
+}
+
+impl std::fmt::Debug for ProxyOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxyOptions")
+            .field("proxy_type", &self.proxy_type)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field(
+                "user",
+                &self.credentials.as_ref().map(|(user, _)| user.as_str()),
+            )
+            .field("target_host", &self.target_host)
+            .field("target_port", &self.target_port)
+            .finish()
+    }
+}
+
 #[derive(Clone)]
 pub(crate) enum SslOptions {
     Acceptor {
         acceptor: TlsAcceptor,
+        /// Name expected in the client certificate subject or subject alternative names,
+        /// set when `SSLRequireClientCertificate=Y`.
+        /// `None` means no client certificate is required,
+        /// `Some(ClientIdentity::TargetCompID)` expects the TargetCompID of the session.
+        client_identity: Option<ClientIdentity>,
     },
     Initiator {
         initiator: TlsConnector,
@@ -144,10 +231,22 @@ pub(crate) enum SslOptions {
     },
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ClientIdentity {
+    TargetCompID,
+    Name(String),
+}
+
 impl std::fmt::Debug for SslOptions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Acceptor { acceptor: _ } => f.debug_struct("Acceptor").finish(),
+            Self::Acceptor {
+                acceptor: _,
+                client_identity,
+            } => f
+                .debug_struct("Acceptor")
+                .field("client_identity", client_identity)
+                .finish(),
             Self::Initiator { initiator, domain } => f
                 .debug_struct("Initiator")
                 .field("initiator", initiator)
@@ -177,6 +276,82 @@ impl LoggingOptions {
     }
 }
 
+#[derive(Builder, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LogonOptions {
+    username: Option<String>,
+    password: Option<String>,
+    raw_data: Option<String>,
+}
+
+impl LogonOptions {
+    pub(crate) fn builder() -> LogonOptionsBuilder {
+        LogonOptionsBuilder::create_empty()
+    }
+
+    pub(crate) fn username(&self) -> Option<&String> {
+        self.username.as_ref()
+    }
+
+    pub(crate) fn password(&self) -> Option<&String> {
+        self.password.as_ref()
+    }
+
+    pub(crate) fn raw_data(&self) -> Option<&String> {
+        self.raw_data.as_ref()
+    }
+}
+
+#[derive(Builder, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ThrottleOptions {
+    rate: Option<u32>,
+    burst: Option<u32>,
+    msg_type_rates: Vec<(String, u32)>,
+    action: ThrottleAction,
+}
+
+impl ThrottleOptions {
+    pub(crate) fn builder() -> ThrottleOptionsBuilder {
+        ThrottleOptionsBuilder::create_empty()
+    }
+
+    pub(crate) fn rate(&self) -> Option<u32> {
+        self.rate
+    }
+
+    pub(crate) fn burst(&self) -> Option<u32> {
+        self.burst
+    }
+
+    pub(crate) fn msg_type_rates(&self) -> &[(String, u32)] {
+        &self.msg_type_rates
+    }
+
+    pub(crate) fn action(&self) -> ThrottleAction {
+        self.action
+    }
+}
+
+#[derive(Builder, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct OutboundQueueOptions {
+    capacity: usize,
+    /// Keep messages queued while disconnected and send them after the next logon.
+    persist: bool,
+}
+
+impl OutboundQueueOptions {
+    pub(crate) fn builder() -> OutboundQueueOptionsBuilder {
+        OutboundQueueOptionsBuilder::create_empty()
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) fn persist(&self) -> bool {
+        self.persist
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum Persistence {
     FileStore { path: PathBuf },
@@ -195,6 +370,7 @@ pub(crate) struct ValidationOptions {
     resend_session_level_rejects: bool,
     time_stamp_precision: DateTimeFormat,
     enable_last_msg_seq_num_processed: bool,
+    enable_next_expected_msg_seq_num: bool,
     max_messages_in_resend_request: u32,
     send_logout_before_disconnect_from_timeout: bool,
     ignore_poss_dup_resend_requests: bool,
@@ -202,9 +378,15 @@ pub(crate) struct ValidationOptions {
 
     // validation options
     use_data_dictionary: bool,
+    /// `UseDataDictionary=Report`, validation errors are reported instead of rejected.
+    validation_report: bool,
     data_dictionary: Option<String>,
     transport_data_dictionary: Option<String>,
     app_data_dictionary: Option<String>,
+    data_dictionary_overlays: Vec<String>,
+    transport_data_dictionary_overlays: Vec<String>,
+    app_data_dictionary_overlays: Vec<String>,
+    validation_rules: Option<String>,
     validate_fields_out_of_order: bool,
     validate_fields_have_values: bool,
     validate_user_defined_fields: bool,
@@ -255,6 +437,10 @@ impl ValidationOptions {
         self.enable_last_msg_seq_num_processed
     }
 
+    pub(crate) fn enable_next_expected_msg_seq_num(&self) -> bool {
+        self.enable_next_expected_msg_seq_num
+    }
+
     pub(crate) fn max_messages_in_resend_request(&self) -> u32 {
         self.max_messages_in_resend_request
     }
@@ -275,6 +461,10 @@ impl ValidationOptions {
         self.use_data_dictionary
     }
 
+    pub(crate) fn validation_report(&self) -> bool {
+        self.validation_report
+    }
+
     pub(crate) fn data_dictionary(&self) -> Option<&String> {
         self.data_dictionary.as_ref()
     }
@@ -287,6 +477,22 @@ impl ValidationOptions {
         self.app_data_dictionary.as_ref()
     }
 
+    pub(crate) fn data_dictionary_overlays(&self) -> &[String] {
+        &self.data_dictionary_overlays
+    }
+
+    pub(crate) fn transport_data_dictionary_overlays(&self) -> &[String] {
+        &self.transport_data_dictionary_overlays
+    }
+
+    pub(crate) fn app_data_dictionary_overlays(&self) -> &[String] {
+        &self.app_data_dictionary_overlays
+    }
+
+    pub(crate) fn validation_rules(&self) -> Option<&String> {
+        self.validation_rules.as_ref()
+    }
+
     pub(crate) fn validate_fields_out_of_order(&self) -> bool {
         self.validate_fields_out_of_order
     }
@@ -316,17 +522,62 @@ impl ValidationOptions {
     }
 }
 
+/// Data dictionaries and validation rules loaded while building the settings, so a missing
+/// or malformed file is a settings error instead of a failure when the session is created.
+/// The dictionaries are `None` unless `UseDataDictionary` is set.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DataDictionaries {
+    /// `DataDictionary`, or `TransportDataDictionary` for FIXT sessions.
+    session: Option<DataDictionary>,
+    /// `AppDataDictionary`, only loaded for FIXT sessions.
+    application: Option<DataDictionary>,
+    /// `ValidationRules`, parsed with the application dictionary.
+    validation_rules: ValidationRules,
+}
+
+impl DataDictionaries {
+    pub(crate) fn new(
+        session: Option<DataDictionary>,
+        application: Option<DataDictionary>,
+        validation_rules: ValidationRules,
+    ) -> Self {
+        DataDictionaries {
+            session,
+            application,
+            validation_rules,
+        }
+    }
+
+    pub(crate) fn session(&self) -> Option<&DataDictionary> {
+        self.session.as_ref()
+    }
+
+    pub(crate) fn application(&self) -> Option<&DataDictionary> {
+        self.application.as_ref()
+    }
+
+    pub(crate) fn validation_rules(&self) -> &ValidationRules {
+        &self.validation_rules
+    }
+}
+
 #[derive(Builder, Clone, Debug)]
 pub(crate) struct SessionSetting {
     session_id: SessionId,
     connection: SettingsConnection,
     socket_options: SocketOptions,
+    proxy_options: Option<ProxyOptions>,
     ssl_options: Option<SslOptions>,
     logging: LoggingOptions,
     persistence: Persistence,
     default_appl_ver_id: Option<String>,
+    message_encoding: Option<MessageEncoding>,
     schedule: SessionSchedule,
     validation_options: ValidationOptions,
+    data_dictionaries: DataDictionaries,
+    logon_options: LogonOptions,
+    throttle_options: ThrottleOptions,
+    outbound_queue_options: OutboundQueueOptions,
 }
 
 impl SessionSetting {
@@ -346,6 +597,10 @@ impl SessionSetting {
         &self.socket_options
     }
 
+    pub(crate) fn proxy_options(&self) -> Option<&ProxyOptions> {
+        self.proxy_options.as_ref()
+    }
+
     pub(crate) fn ssl_options(&self) -> Option<&SslOptions> {
         self.ssl_options.as_ref()
     }
@@ -362,6 +617,10 @@ impl SessionSetting {
         self.default_appl_ver_id.as_ref()
     }
 
+    pub(crate) fn message_encoding(&self) -> Option<MessageEncoding> {
+        self.message_encoding
+    }
+
     pub(crate) fn schedule(&self) -> &SessionSchedule {
         &self.schedule
     }
@@ -369,6 +628,22 @@ impl SessionSetting {
     pub(crate) fn validation_options(&self) -> &ValidationOptions {
         &self.validation_options
     }
+
+    pub(crate) fn data_dictionaries(&self) -> &DataDictionaries {
+        &self.data_dictionaries
+    }
+
+    pub(crate) fn logon_options(&self) -> &LogonOptions {
+        &self.logon_options
+    }
+
+    pub(crate) fn throttle_options(&self) -> &ThrottleOptions {
+        &self.throttle_options
+    }
+
+    pub(crate) fn outbound_queue_options(&self) -> &OutboundQueueOptions {
+        &self.outbound_queue_options
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -519,10 +794,40 @@ impl SessionSetting {
         SocketSettings::new(
             *self.connection.socket_addr(),
             self.socket_options.clone(),
+            self.proxy_options.clone(),
             self.ssl_options.clone(),
         )
     }
 
+    /// The name the client certificate has to present for `session_id`,
+    /// `None` if no client certificate is required.
+    pub(crate) fn expected_client_identity<'a>(
+        &'a self,
+        session_id: &'a SessionId,
+    ) -> Option<&'a str> {
+        match self.ssl_options.as_ref()? {
+            SslOptions::Acceptor {
+                client_identity: Some(ClientIdentity::TargetCompID),
+                ..
+            } => Some(session_id.target_comp_id()),
+            SslOptions::Acceptor {
+                client_identity: Some(ClientIdentity::Name(name)),
+                ..
+            } => Some(name.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Reloads the certificates from `SSLCertificate` and `SSLCACertificate`,
+    /// only new connections use the reloaded certificates.
+    pub(crate) fn reload_certificates(&self) -> Result<(), SessionSettingsError> {
+        match self.ssl_options.as_ref() {
+            Some(SslOptions::Acceptor { acceptor, .. }) => acceptor.reload(),
+            Some(SslOptions::Initiator { initiator, .. }) => initiator.reload(),
+            None => Ok(()),
+        }
+    }
+
     pub(crate) fn reconnect_interval(&self) -> Option<u32> {
         match self.connection {
             SettingsConnection::Acceptor { .. } => None,
@@ -0,0 +1,267 @@
+//! An in-process acceptor session for tests, driven without a socket.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use chrono::Utc;
+use dfx_base::data_dictionary::Violation;
+use dfx_base::data_dictionary_provider::DefaultDataDictionaryProvider;
+use dfx_base::encoding::encode_latin1;
+use dfx_base::field_map::{FieldMapError, Tag};
+use dfx_base::message::Message;
+use dfx_base::message_factory::DefaultMessageFactory;
+use dfx_base::session_id::SessionId;
+use dfx_base::tags;
+
+use crate::logging::tests::TestLogger;
+use crate::message_store::MemoryStoreFactory;
+use crate::session::{
+    Application, ApplicationError, ChannelResponder, DoNotAccept, FromAppError, ISession,
+    LogonReject, MessageSigner, ResponderEvent, SessionSettings,
+};
+
+/// Held while a test has sessions registered, so [`crate::session::Session::sessions`] only
+/// returns the sessions of that test.
+static REGISTRY: Mutex<()> = Mutex::new(());
+
+pub(crate) fn lock_registry() -> MutexGuard<'static, ()> {
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Records the application callbacks, shared between clones.
+#[derive(Clone, Default)]
+pub(crate) struct RecordingApplication {
+    events: Arc<Mutex<Vec<String>>>,
+    do_not_send: Arc<AtomicBool>,
+    signer: Option<Arc<dyn MessageSigner + Send + Sync>>,
+}
+
+impl RecordingApplication {
+    pub(crate) fn events(&self) -> Vec<String> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Returns `signer` from `message_signer`.
+    #[cfg(feature = "hmac")]
+    pub(crate) fn with_signer(
+        mut self,
+        signer: impl MessageSigner + Send + Sync + 'static,
+    ) -> Self {
+        self.signer = Some(Arc::new(signer));
+        self
+    }
+
+    /// Makes `to_app` return `DoNotSend`.
+    pub(crate) fn set_do_not_send(&self, do_not_send: bool) {
+        self.do_not_send.store(do_not_send, Ordering::Relaxed);
+    }
+
+    fn record(&self, event: String) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+impl Application for RecordingApplication {
+    fn on_create(&mut self, _session_id: &SessionId) -> Result<(), DoNotAccept> {
+        Ok(())
+    }
+
+    fn on_logon(&mut self, _session_id: &SessionId) -> Result<(), LogonReject> {
+        Ok(())
+    }
+
+    fn on_logout(&mut self, _session_id: &SessionId) -> Result<(), ApplicationError> {
+        Ok(())
+    }
+
+    fn to_admin(
+        &mut self,
+        message: Message,
+        _session_id: &SessionId,
+    ) -> Result<Message, FieldMapError> {
+        Ok(message)
+    }
+
+    fn from_admin(
+        &mut self,
+        _message: &Message,
+        _session_id: &SessionId,
+    ) -> Result<(), FieldMapError> {
+        Ok(())
+    }
+
+    fn to_app(
+        &mut self,
+        _message: &mut Message,
+        _session_id: &SessionId,
+    ) -> Result<(), ApplicationError> {
+        if self.do_not_send.load(Ordering::Relaxed) {
+            return Err(ApplicationError::DoNotSend);
+        }
+        Ok(())
+    }
+
+    fn message_signer(&self) -> Option<&dyn MessageSigner> {
+        self.signer
+            .as_deref()
+            .map(|signer| signer as &dyn MessageSigner)
+    }
+
+    fn from_app(&mut self, message: &Message, _session_id: &SessionId) -> Result<(), FromAppError> {
+        self.record(format!(
+            "from_app {}",
+            message.header().get_string(tags::MsgType)?
+        ));
+        Ok(())
+    }
+
+    fn on_validation_report(
+        &mut self,
+        _message: &Message,
+        errors: &[Violation],
+        _session_id: &SessionId,
+    ) {
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        self.record(format!("report {}", errors.join(", ")));
+    }
+}
+
+/// A logged on acceptor session `FIX.4.4:ISLD->{target_comp_id}`, registered like a connected
+/// session until dropped.
+pub(crate) struct TestSession {
+    pub(crate) session: ISession<
+        RecordingApplication,
+        DefaultDataDictionaryProvider,
+        TestLogger,
+        DefaultMessageFactory,
+    >,
+    pub(crate) app: RecordingApplication,
+    pub(crate) log: TestLogger,
+    sent: Receiver<ResponderEvent>,
+    _registry: MutexGuard<'static, ()>,
+}
+
+impl TestSession {
+    /// Creates the session with the extra session `settings` and logs on.
+    pub(crate) fn logged_on(target_comp_id: &str, settings: &str) -> Self {
+        let mut test_session = Self::connected(target_comp_id, settings);
+        test_session.logon();
+        test_session
+    }
+
+    /// Creates the session with the extra session `settings`, connected but not logged on.
+    pub(crate) fn connected(target_comp_id: &str, settings: &str) -> Self {
+        Self::connected_with(target_comp_id, settings, RecordingApplication::default())
+    }
+
+    /// Like [`Self::connected`], with the callbacks going to `app`.
+    pub(crate) fn connected_with(
+        target_comp_id: &str,
+        settings: &str,
+        app: RecordingApplication,
+    ) -> Self {
+        let registry = lock_registry();
+        let settings = SessionSettings::from_string(&format!(
+            "[DEFAULT]
+ConnectionType=acceptor
+SocketAcceptPort=0
+NonStopSession=Y
+BeginString=FIX.4.4
+SenderCompID=ISLD
+HeartBtInt=30
+[SESSION]
+TargetCompID={target_comp_id}
+{settings}"
+        ))
+        .unwrap();
+        let setting = settings.sessions()[0].clone();
+        let session_id = setting.session_id().clone();
+        let log = TestLogger::default();
+        let mut session = ISession::from_settings(
+            session_id.clone(),
+            app.clone(),
+            MemoryStoreFactory::boxed(),
+            DefaultDataDictionaryProvider::new(),
+            log.clone(),
+            DefaultMessageFactory::new(),
+            setting,
+        );
+        let (responder, sent) = ChannelResponder::new();
+        session.set_responder(Box::new(responder));
+        session.set_connected(&session_id).unwrap();
+        session.next();
+        TestSession {
+            session,
+            app,
+            log,
+            sent,
+            _registry: registry,
+        }
+    }
+
+    /// Receives the counterparty's Logon with MsgSeqNum 1 and checks it is answered.
+    pub(crate) fn logon(&mut self) {
+        self.receive(
+            1,
+            "A",
+            &[(tags::EncryptMethod, "0"), (tags::HeartBtInt, "30")],
+        );
+        assert_eq!(self.sent_msg_types(), ["A"]);
+    }
+
+    pub(crate) fn session_id(&self) -> SessionId {
+        self.session.session_id().clone()
+    }
+
+    /// Passes a message from the counterparty to the session.
+    pub(crate) fn receive(&mut self, seq_num: u32, msg_type: &str, fields: &[(Tag, &str)]) {
+        let session_id = self.session_id();
+        let mut message = Message::default();
+        let header = message.header_mut();
+        header.set_tag_value(tags::BeginString, session_id.begin_string());
+        header.set_tag_value(tags::MsgType, msg_type);
+        header.set_tag_value(tags::SenderCompID, session_id.target_comp_id());
+        header.set_tag_value(tags::TargetCompID, session_id.sender_comp_id());
+        header.set_tag_value(tags::MsgSeqNum, i64::from(seq_num));
+        header.set_tag_value(
+            tags::SendingTime,
+            Utc::now().format("%Y%m%d-%H:%M:%S%.3f").to_string(),
+        );
+        for (tag, value) in fields {
+            message.set_tag_value(*tag, *value);
+        }
+        self.session
+            .next_msg(encode_latin1(&message.to_string_mut()));
+    }
+
+    /// The messages sent since the last call, resolving their tickets as written.
+    pub(crate) fn sent(&self) -> Vec<Message> {
+        self.sent
+            .try_iter()
+            .filter_map(|event| match event {
+                ResponderEvent::Send(message, confirmation) => {
+                    if let Some(confirmation) = confirmation {
+                        confirmation.written();
+                    }
+                    Some(Message::new(&encode_latin1(&message)).unwrap())
+                }
+                ResponderEvent::Disconnect => None,
+            })
+            .collect()
+    }
+
+    /// The MsgType of the messages sent since the last call.
+    pub(crate) fn sent_msg_types(&self) -> Vec<String> {
+        self.sent()
+            .iter()
+            .map(|message| message.header().get_string(tags::MsgType).unwrap())
+            .collect()
+    }
+}
+
+impl Drop for TestSession {
+    fn drop(&mut self) {
+        let session_id = self.session_id();
+        self.session.set_disconnected(&session_id);
+    }
+}
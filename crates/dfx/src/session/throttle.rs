@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Instant;
+
+use dfx_base::message::Message;
+use dfx_base::tags;
+
+use crate::session::{SendConfirmation, SessionSettingsError, SettingOption, ThrottleOptions};
+
+/// What happens to an application message that exceeds `ThrottleRate` or
+/// `ThrottleMsgTypeRates`, configured with the `ThrottleAction` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleAction {
+    /// Queue the message and keep processing other outbound messages, queued messages are sent
+    /// in order as the rate allows.
+    Queue,
+    /// Drop the message.
+    Reject,
+    /// Stop processing outbound messages until the message can be sent.
+    Block,
+}
+
+impl TryFrom<&str> for ThrottleAction {
+    type Error = SessionSettingsError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "Queue" => Ok(Self::Queue),
+            "Reject" => Ok(Self::Reject),
+            "Block" => Ok(Self::Block),
+            e => Err(SessionSettingsError::InvalidValue {
+                setting: SettingOption::ThrottleAction.into(),
+                value: e.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32, burst: u32, now: Instant) -> Self {
+        let capacity = f64::from(burst.max(1));
+        TokenBucket {
+            rate: f64::from(rate),
+            capacity,
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    fn available(&mut self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+        self.tokens >= 1.0
+    }
+
+    fn take(&mut self) {
+        self.tokens -= 1.0;
+    }
+}
+
+/// Token buckets for the outbound application messages of a session.
+#[derive(Debug)]
+pub(crate) struct Throttle {
+    action: ThrottleAction,
+    session: Option<TokenBucket>,
+    msg_types: BTreeMap<String, TokenBucket>,
+    pending: VecDeque<(Message, SendConfirmation)>,
+}
+
+impl Throttle {
+    /// Returns `None` when no rate is configured.
+    pub(crate) fn new(options: &ThrottleOptions, now: Instant) -> Option<Self> {
+        if options.rate().is_none() && options.msg_type_rates().is_empty() {
+            return None;
+        }
+        let session = options
+            .rate()
+            .map(|rate| TokenBucket::new(rate, options.burst().unwrap_or(rate), now));
+        let msg_types = options
+            .msg_type_rates()
+            .iter()
+            .map(|(msg_type, rate)| (msg_type.clone(), TokenBucket::new(*rate, *rate, now)))
+            .collect();
+        Some(Throttle {
+            action: options.action(),
+            session,
+            msg_types,
+            pending: VecDeque::new(),
+        })
+    }
+
+    pub(crate) fn action(&self) -> ThrottleAction {
+        self.action
+    }
+
+    pub(crate) fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    pub(crate) fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub(crate) fn push(&mut self, message: Message, confirmation: SendConfirmation) {
+        self.pending.push_back((message, confirmation));
+    }
+
+    /// Takes a token from the session bucket and the bucket of `msg_type`, only when both have
+    /// one available.
+    pub(crate) fn try_acquire(&mut self, msg_type: &str, now: Instant) -> bool {
+        let session_available = self
+            .session
+            .as_mut()
+            .is_none_or(|bucket| bucket.available(now));
+        let msg_type_available = self
+            .msg_types
+            .get_mut(msg_type)
+            .is_none_or(|bucket| bucket.available(now));
+        if !(session_available && msg_type_available) {
+            return false;
+        }
+        if let Some(bucket) = self.session.as_mut() {
+            bucket.take();
+        }
+        if let Some(bucket) = self.msg_types.get_mut(msg_type) {
+            bucket.take();
+        }
+        true
+    }
+
+    /// Returns the oldest pending message once it may be sent.
+    pub(crate) fn pop_ready(&mut self, now: Instant) -> Option<(Message, SendConfirmation)> {
+        let msg_type = self
+            .pending
+            .front()?
+            .0
+            .header()
+            .get_string(tags::MsgType)
+            .unwrap_or_default();
+        if self.try_acquire(&msg_type, now) {
+            self.pending.pop_front()
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use dfx_base::message::Message;
+    use dfx_base::tags;
+
+    use super::{Throttle, ThrottleAction};
+    use crate::session::{SendConfirmation, ThrottleOptions};
+
+    fn message(msg_type: &str) -> (Message, SendConfirmation) {
+        let mut message = Message::default();
+        message.header_mut().set_tag_value(tags::MsgType, msg_type);
+        (message, SendConfirmation::new().0)
+    }
+
+    #[test]
+    fn throttle() {
+        let options = ThrottleOptions::builder()
+            .rate(Some(10))
+            .burst(Some(2))
+            .msg_type_rates(vec![("D".into(), 1)])
+            .action(ThrottleAction::Queue)
+            .build()
+            .unwrap();
+        let now = Instant::now();
+        let mut throttle = Throttle::new(&options, now).unwrap();
+
+        assert!(throttle.try_acquire("D", now));
+        assert!(!throttle.try_acquire("D", now));
+        assert!(throttle.try_acquire("F", now));
+        assert!(!throttle.try_acquire("F", now));
+
+        let (order, confirmation) = message("D");
+        throttle.push(order, confirmation);
+        let (execution_report, confirmation) = message("F");
+        throttle.push(execution_report, confirmation);
+        // The session bucket refills first, D is still limited to 1 per second
+        assert!(throttle
+            .pop_ready(now + Duration::from_millis(500))
+            .is_none());
+        assert!(throttle.pop_ready(now + Duration::from_secs(1)).is_some());
+        assert!(throttle.pop_ready(now + Duration::from_secs(1)).is_some());
+        assert!(!throttle.has_pending());
+
+        let options = ThrottleOptions::builder()
+            .rate(None)
+            .burst(None)
+            .msg_type_rates(Vec::new())
+            .action(ThrottleAction::Queue)
+            .build()
+            .unwrap();
+        assert!(Throttle::new(&options, now).is_none());
+    }
+}
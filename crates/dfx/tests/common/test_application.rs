@@ -152,7 +152,7 @@ impl Application for TestApplication {
             "R" => self.echo(message, session_id),
             _ => {
                 return Err(FromAppError::UnknownMessageType {
-                    message: message.clone(),
+                    message: Box::new(message.clone()),
                     msg_type,
                 })
             }
@@ -189,8 +189,8 @@ impl Application for SendTestApplication {
         let mut message = Message::default();
         message.header_mut().set_tag_value(tags::MsgType, "V");
 
-        Session::send_to_session(session_id, message.clone()).unwrap_or(());
-        Session::send_to_session(session_id, message).unwrap_or(());
+        let _ = Session::send_to_session(session_id, message.clone());
+        let _ = Session::send_to_session(session_id, message);
         Ok(())
     }
 
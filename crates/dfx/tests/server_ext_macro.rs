@@ -16,6 +16,7 @@ fn cfg_from_version(version: &str) -> &str {
         "fix43" => include_str!("cfg/at_43.cfg"),
         "fix44" => include_str!("cfg/at_44.cfg"),
         "fix44noreset" => include_str!("cfg/at_44_noreset.cfg"),
+        "fix44nextexpected" => include_str!("cfg/at_44_next_expected.cfg"),
         "fix50" => include_str!("cfg/at_50.cfg"),
         "fix50sp1" => include_str!("cfg/at_50_sp1.cfg"),
         "fix50sp2" => include_str!("cfg/at_50_sp2.cfg"),
@@ -34,6 +35,7 @@ fn path_from_version(version: &str) -> &str {
         "fix43" => "tests/definitions/server-ext/fix43/",
         "fix44" => "tests/definitions/server-ext/fix44/",
         "fix44noreset" => "tests/definitions/server-ext/fix44noreset/",
+        "fix44nextexpected" => "tests/definitions/server-ext/fix44nextexpected/",
         "fix50" => "tests/definitions/server-ext/fix50/",
         "fix50sp1" => "tests/definitions/server-ext/fix50sp1/",
         "fix50sp2" => "tests/definitions/server-ext/fix50sp2/",
@@ -438,6 +440,8 @@ mod fix44 {
     acceptor!(fix44 -> "SessionReset");
     acceptor!(fix44 -> "issue146_MissingGroupDelimiter");
     acceptor!(fix44noreset -> "SessionResetAfterDisconnect");
+    acceptor!(fix44nextexpected -> "NextExpectedMsgSeqNumGapFill");
+    acceptor!(fix44nextexpected -> "NextExpectedMsgSeqNumTooHigh");
 }
 
 mod fix50 {
@@ -643,6 +647,7 @@ mod misc {
     imports!();
     acceptor!(misc -> "FIX42LastMsgSeqNumProcessed");
     acceptor!(misc -> "FIX42MaxMessagesInResend");
+    acceptor!(misc -> "FIX42MaxMessagesInResendGapFill");
     acceptor!(misc -> "FIX42Subs");
     acceptor!(misc -> "FIX42TestRequest");
 }
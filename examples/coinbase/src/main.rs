@@ -11,15 +11,14 @@ use dfx::{
     logging::PrintlnLogFactory,
     message_factory::DefaultMessageFactory,
     message_store::DefaultStoreFactory,
-    session::{Application, SessionSettings},
+    session::{Application, HmacSigner, MessageSigner, SessionSettings},
     tags::{self},
 };
-use hmac_sha256::HMAC;
 
-#[derive(Default, Clone, Debug)]
+#[derive(Clone, Debug)]
 struct CoinbaseApp {
     password: String,
-    secret: Vec<u8>,
+    signer: HmacSigner,
 }
 
 impl CoinbaseApp {
@@ -28,7 +27,7 @@ impl CoinbaseApp {
         let secret = engine.decode(secret.replace('+', "-").replace('/', "_"))?;
         Ok(CoinbaseApp {
             password: password.into(),
-            secret,
+            signer: HmacSigner::new(secret).with_field(tags::Password),
         })
     }
 }
@@ -60,35 +59,25 @@ impl Application for CoinbaseApp {
 
     fn to_admin(
         &mut self,
-        mut message: dfx::message::Message,
+        message: dfx::message::Message,
         _session_id: &dfx::session_id::SessionId,
     ) -> Result<dfx::message::Message, dfx::field_map::FieldMapError> {
-        let msg_type = message.header().get_string(tags::MsgType)?;
-        match msg_type.as_str() {
-            "A" => {
-                message.set_tag_value(tags::ResetSeqNumFlag, "Y");
-                message.set_tag_value(tags::Password, &self.password);
-                let sending_time = message.header().get_string(tags::SendingTime)?;
-                let msg_seq_num = message.header().get_string(tags::MsgSeqNum)?;
-                let sender_comp_id = _session_id.sender_comp_id();
-                let target_comp_id = _session_id.target_comp_id();
-                let prehash = prehash(
-                    &sending_time,
-                    &msg_type,
-                    &msg_seq_num,
-                    sender_comp_id,
-                    target_comp_id,
-                    &self.password,
-                );
-                let signature = sign(prehash, &self.secret);
-                message.set_tag_value(tags::RawDataLength, signature.len());
-                message.set_tag_value(tags::RawData, signature);
-                message.set_tag_value(8013, "S");
-                message.set_tag_value(9406, "N");
-                Ok(message)
-            }
-            _ => Ok(message),
-        }
+        Ok(message)
+    }
+
+    fn sign_logon(
+        &mut self,
+        logon: &mut dfx::message::Message,
+        _session_id: &dfx::session_id::SessionId,
+    ) -> Result<(), dfx::field_map::FieldMapError> {
+        logon.set_tag_value(tags::Password, &self.password);
+        logon.set_tag_value(8013, "S");
+        logon.set_tag_value(9406, "N");
+        Ok(())
+    }
+
+    fn message_signer(&self) -> Option<&dyn MessageSigner> {
+        Some(&self.signer)
     }
 
     fn from_admin(
@@ -116,27 +105,6 @@ impl Application for CoinbaseApp {
     }
 }
 
-//
-fn sign(prehash: String, key: &[u8]) -> String {
-    let mut hmac = HMAC::new(key);
-    hmac.update(prehash.as_bytes());
-    let bytes = hmac.finalize();
-    general_purpose::STANDARD.encode(bytes)
-}
-
-fn prehash(
-    sending_time: &str,
-    msg_type: &str,
-    msg_seq_num: &str,
-    sender_comp_id: &str,
-    target_comp_id: &str,
-    password: &str,
-) -> String {
-    format!(
-        "{sending_time}\x01{msg_type}\x01{msg_seq_num}\x01{sender_comp_id}\x01{target_comp_id}\x01{password}"
-    )
-}
-
 fn main() {
     let key_var_key = "CB_SECRET";
     let pass_var_key = "CB_PASSPHRASE";